@@ -10,6 +10,7 @@ use scale_typegen_description::scale_typegen::typegen::{
     settings::substitutes::path_segments,
     validation::{registry_contains_type_path, similar_type_paths_in_registry},
 };
+use std::path::PathBuf;
 use subxt_codegen::CodegenBuilder;
 use subxt_metadata::Metadata;
 
@@ -59,6 +60,28 @@ pub struct Opts {
     /// Defaults to `false` (all types are generated).
     #[clap(long)]
     runtime_types_only: bool,
+    /// Additionally generate an interface that is bound to a `subxt` client, for example
+    /// `api::client_api().storage(&client).system().account(id).fetch().await`.
+    ///
+    /// Defaults to `false` (no client bindings are generated).
+    #[clap(long)]
+    client_bindings: bool,
+    /// Gate each pallet module behind a `pallet-<name>` feature and each runtime API behind a
+    /// `runtime-api-<name>` feature, so that only the enabled parts of the code are compiled.
+    ///
+    /// Defaults to `false` (nothing is feature gated).
+    #[clap(long)]
+    feature_gates: bool,
+    /// Write a Cargo `[features]` table declaring the features used by `--feature-gates` to this file.
+    #[clap(long, requires = "feature_gates")]
+    features_out: Option<PathBuf>,
+    /// Derive `serde::Serialize` and `serde::Deserialize` on all of the generated types, using
+    /// human readable representations (SS58 account IDs, hex bytes and hashes, and large integers
+    /// as strings).
+    ///
+    /// Defaults to `false` (no serde derives are generated).
+    #[clap(long)]
+    serde: bool,
     /// Do not provide default trait derivations for the generated types.
     ///
     /// Defaults to `false` (default trait derivations are provided).
@@ -72,6 +95,12 @@ pub struct Opts {
     /// Allow insecure URLs e.g. URLs starting with ws:// or http:// without SSL encryption
     #[clap(long, short)]
     allow_insecure: bool,
+    /// Write the generated code to this directory, as one file per pallet plus a `runtime_types`
+    /// directory, rather than outputting a single module to stdout.
+    ///
+    /// Example: `subxt codegen --out-dir src/polkadot && rustfmt --edition=2021 src/polkadot/mod.rs`
+    #[clap(long)]
+    out_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -156,8 +185,13 @@ pub async fn run(opts: Opts, output: &mut impl std::io::Write) -> color_eyre::Re
         opts.crate_path,
         opts.no_docs,
         opts.runtime_types_only,
+        opts.client_bindings,
+        opts.feature_gates,
+        opts.serde,
         opts.no_default_derives,
         opts.no_default_substitutions,
+        opts.out_dir,
+        opts.features_out,
         output,
     )?;
     Ok(())
@@ -183,8 +217,13 @@ fn codegen(
     crate_path: Option<String>,
     no_docs: bool,
     runtime_types_only: bool,
+    client_bindings: bool,
+    feature_gates: bool,
+    serde: bool,
     no_default_derives: bool,
     no_default_substitutions: bool,
+    out_dir: Option<PathBuf>,
+    features_out: Option<PathBuf>,
     output: &mut impl std::io::Write,
 ) -> color_eyre::Result<()> {
     let mut codegen = CodegenBuilder::new();
@@ -200,6 +239,15 @@ fn codegen(
     if runtime_types_only {
         codegen.runtime_types_only()
     }
+    if client_bindings {
+        codegen.generate_client_bindings()
+    }
+    if feature_gates {
+        codegen.generate_feature_gates()
+    }
+    if serde {
+        codegen.enable_serde()
+    }
     if no_default_derives {
         codegen.disable_default_derives()
     }
@@ -265,11 +313,28 @@ fn codegen(
         codegen.set_type_substitute(from, to);
     }
 
-    let code = codegen
-        .generate(metadata)
+    let module_tree = codegen
+        .generate_module_tree(metadata)
         .map_err(|e| eyre!("Cannot generate code: {e}"))?;
 
-    writeln!(output, "{code}")?;
+    match out_dir {
+        Some(out_dir) => module_tree.write_to_dir(&out_dir).map_err(|e| {
+            eyre!(
+                "Cannot write generated code to {}: {e}",
+                out_dir.to_string_lossy()
+            )
+        })?,
+        None => writeln!(output, "{}", module_tree.to_token_stream())?,
+    }
+
+    if let Some(features_out) = features_out {
+        std::fs::write(&features_out, module_tree.cargo_features()).map_err(|e| {
+            eyre!(
+                "Cannot write features to {}: {e}",
+                features_out.to_string_lossy()
+            )
+        })?;
+    }
     Ok(())
 }
 
@@ -367,4 +432,41 @@ mod tests {
             assert!(output.unwrap_err().to_string().contains(valid_type));
         }
     }
+
+    #[tokio::test]
+    async fn out_dir_writes_module_tree() {
+        let out_dir = std::env::temp_dir().join(format!("subxt-codegen-{}", std::process::id()));
+        let output = run(&format!("--out-dir {}", out_dir.display())).await;
+        assert!(output.unwrap().is_empty());
+
+        let root = std::fs::read_to_string(out_dir.join("mod.rs")).unwrap();
+        assert!(root.contains("pub mod system ;"));
+        assert!(root.contains("pub mod runtime_types ;"));
+        assert!(out_dir.join("system.rs").is_file());
+        assert!(out_dir.join("runtime_types/mod.rs").is_file());
+        assert!(out_dir.join("runtime_types/frame_system.rs").is_file());
+
+        std::fs::remove_dir_all(out_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn feature_gates() {
+        let features_out = std::env::temp_dir().join(format!(
+            "subxt-codegen-features-{}.toml",
+            std::process::id()
+        ));
+        let output = run(&format!(
+            "--feature-gates --features-out {}",
+            features_out.display()
+        ))
+        .await
+        .unwrap();
+        assert!(output.contains(r#"# [cfg (feature = "pallet-system")] pub mod system"#));
+
+        let features = std::fs::read_to_string(&features_out).unwrap();
+        assert!(features.starts_with("[features]\nall = [\"pallet-system\", "));
+        assert!(features.contains("\npallet-system = []\n"));
+
+        std::fs::remove_file(features_out).unwrap();
+    }
 }
@@ -0,0 +1,109 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::utils::validate_url_security;
+use clap::{Args, Parser as ClapParser, Subcommand};
+use std::{io::Write, path::PathBuf};
+use subxt::backend::{legacy::LegacyBackend, rpc::RpcClient, snapshot::Snapshot};
+use subxt::utils::H256;
+use subxt::PolkadotConfig;
+use subxt_codegen::fetch_metadata::Url;
+
+/// Work with snapshots of the state of a chain, which can be queried without a node by
+/// using `subxt::backend::snapshot::SnapshotBackend`.
+///
+/// # Example
+///
+/// Export the storage of the System and Balances pallets at the latest finalized block:
+///
+/// ```text
+/// subxt snapshot export --pallets System,Balances --output-file state.snapshot
+/// ```
+#[derive(Debug, ClapParser)]
+pub struct Opts {
+    #[command(subcommand)]
+    command: SnapshotSubcommand,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum SnapshotSubcommand {
+    Export(ExportOpts),
+}
+
+/// Export the raw storage key value pairs of a chain at some block, along with the
+/// header and metadata of that block, into a snapshot file.
+#[derive(Debug, Clone, Args)]
+pub struct ExportOpts {
+    /// The url of the substrate node to export the state of.
+    ///
+    /// Defaults to `ws://localhost:9944`.
+    #[clap(long)]
+    url: Option<Url>,
+    /// The hash of the block to export the state at.
+    ///
+    /// Defaults to the latest finalized block.
+    #[clap(long, value_parser = parse_block_hash)]
+    at: Option<H256>,
+    /// Only export the storage of the pallets given.
+    ///
+    /// Defaults to exporting all storage.
+    #[clap(long, use_value_delimiter = true, value_parser)]
+    pallets: Option<Vec<String>>,
+    /// Write the snapshot to the provided file path.
+    #[clap(long, short, value_parser)]
+    output_file: Option<PathBuf>,
+    /// Allow insecure URLs e.g. URLs starting with ws:// or http:// without SSL encryption
+    #[clap(long, short)]
+    allow_insecure: bool,
+}
+
+pub async fn run(opts: Opts, output: &mut impl Write) -> color_eyre::Result<()> {
+    match opts.command {
+        SnapshotSubcommand::Export(opts) => export(opts, output).await,
+    }
+}
+
+async fn export(opts: ExportOpts, output: &mut impl Write) -> color_eyre::Result<()> {
+    validate_url_security(opts.url.as_ref(), opts.allow_insecure)?;
+    let url = match opts.url {
+        Some(url) => url,
+        None => Url::parse("ws://localhost:9944").expect("Valid URL; qed"),
+    };
+
+    let rpc_client = RpcClient::from_insecure_url(url.as_str()).await?;
+    let backend = LegacyBackend::<PolkadotConfig>::builder().build(rpc_client);
+    let snapshot =
+        Snapshot::export::<PolkadotConfig>(&backend, opts.at, opts.pallets.as_deref()).await?;
+
+    let mut output: Box<dyn Write> = match opts.output_file {
+        Some(path) => Box::new(std::fs::File::create(path)?),
+        None => Box::new(output),
+    };
+    output.write_all(&snapshot.to_bytes())?;
+    Ok(())
+}
+
+fn parse_block_hash(s: &str) -> Result<H256, String> {
+    let bytes = hex::decode(s.trim_start_matches("0x")).map_err(|e| e.to_string())?;
+    if bytes.len() != 32 {
+        return Err(format!(
+            "A block hash is 32 bytes long, but {} bytes were given",
+            bytes.len()
+        ));
+    }
+    Ok(H256::from_slice(&bytes))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_block_hashes() {
+        let hash = parse_block_hash(&format!("0x{}", "ab".repeat(32))).unwrap();
+        assert_eq!(hash, H256::repeat_byte(0xab));
+        assert!(parse_block_hash("0xabab").is_err());
+        assert!(parse_block_hash("not hex").is_err());
+    }
+}
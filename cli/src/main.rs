@@ -19,6 +19,7 @@ enum Command {
     Version(commands::version::Opts),
     Explore(commands::explore::Opts),
     ChainSpec(commands::chain_spec::Opts),
+    Snapshot(commands::snapshot::Opts),
 }
 
 #[tokio::main]
@@ -34,5 +35,6 @@ async fn main() -> color_eyre::Result<()> {
         Command::Version(opts) => commands::version::run(opts, &mut output),
         Command::Explore(opts) => commands::explore::run(opts, &mut output).await,
         Command::ChainSpec(opts) => commands::chain_spec::run(opts, &mut output).await,
+        Command::Snapshot(opts) => commands::snapshot::run(opts, &mut output).await,
     }
 }
@@ -11,7 +11,7 @@ use scale_typegen_description::{format_type_description, type_description};
 use std::fmt::Display;
 use std::str::FromStr;
 use std::{fs, io::Read, path::PathBuf};
-use subxt::{OnlineClient, PolkadotConfig};
+use subxt::{utils::UnifiedAddress, OnlineClient, PolkadotConfig};
 
 use scale_value::Value;
 use subxt_codegen::fetch_metadata::{fetch_metadata_from_url, MetadataVersion, Url};
@@ -224,14 +224,38 @@ pub async fn create_client(
 }
 
 pub fn parse_string_into_scale_value(str: &str) -> color_eyre::Result<Value> {
-    let value = scale_value::stringify::from_str(str).0.map_err(|err| {
-        eyre!(
-            "scale_value::stringify::from_str led to a ParseError.\n\ntried parsing: \"{str}\"\n\n{err}",
-        )
-    })?;
+    let value = scale_value::stringify::from_str_custom()
+        .add_custom_parser(parse_unified_address)
+        .parse(str)
+        .0
+        .map_err(|err| {
+            eyre!(
+                "scale_value::stringify::from_str led to a ParseError.\n\ntried parsing: \"{str}\"\n\n{err}",
+            )
+        })?;
     Ok(value)
 }
 
+/// A custom parser which allows accounts to be given as SS58 addresses, or as `0x` prefixed
+/// 32 byte account IDs or 20 byte Ethereum addresses. These are parsed into the account ID bytes.
+fn parse_unified_address(
+    s: &mut &str,
+) -> Option<Result<Value<()>, scale_value::stringify::ParseError>> {
+    let end_idx = s
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(s.len());
+    let (maybe_address, rest) = s.split_at(end_idx);
+
+    // Leave variant names like `Id(..)` or `Foo { .. }` to the default parser:
+    if rest.trim_start().starts_with(['(', '{']) {
+        return None;
+    }
+
+    let address = UnifiedAddress::from_str(maybe_address).ok()?;
+    *s = rest;
+    Some(Ok(Value::from_bytes(address.as_bytes())))
+}
+
 pub trait SyntaxHighlight {
     fn highlight(&self) -> String;
 }
@@ -368,4 +392,34 @@ mod tests {
             })
         ));
     }
+
+    #[test]
+    fn parsing_addresses_into_values() {
+        use super::parse_string_into_scale_value;
+        use scale_value::Value;
+
+        let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let alice_bytes =
+            hex::decode("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d")
+                .unwrap();
+        let eth = "0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac";
+        let eth_bytes = hex::decode(&eth[2..]).unwrap();
+
+        assert_eq!(
+            parse_string_into_scale_value(alice).unwrap(),
+            Value::from_bytes(&alice_bytes)
+        );
+        assert_eq!(
+            parse_string_into_scale_value(&format!("0x{}", hex::encode(&alice_bytes))).unwrap(),
+            Value::from_bytes(&alice_bytes)
+        );
+        assert_eq!(
+            parse_string_into_scale_value(&format!("Id({alice})")).unwrap(),
+            Value::unnamed_variant("Id", [Value::from_bytes(&alice_bytes)])
+        );
+        assert_eq!(
+            parse_string_into_scale_value(&format!("({eth}, 1)")).unwrap(),
+            Value::unnamed_composite([Value::from_bytes(&eth_bytes), Value::u128(1)])
+        );
+    }
 }
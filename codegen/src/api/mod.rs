@@ -21,16 +21,19 @@ use subxt_metadata::Metadata;
 use syn::{parse_quote, Ident};
 
 use crate::error::CodegenError;
+use crate::module_tree::ModuleTree;
 use crate::subxt_type_gen_settings;
 use crate::{api::custom_values::generate_custom_values, ir};
 
-use heck::{ToSnakeCase as _, ToUpperCamelCase};
+use heck::{ToKebabCase as _, ToSnakeCase as _, ToUpperCamelCase};
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote};
 
 /// Create the API for interacting with a Substrate runtime.
 pub struct RuntimeGenerator {
     metadata: Metadata,
+    client_crate_path: Option<syn::Path>,
+    feature_gates: bool,
 }
 
 impl RuntimeGenerator {
@@ -46,10 +49,32 @@ impl RuntimeGenerator {
     /// Supported versions: v14 and v15.
     pub fn new(mut metadata: Metadata) -> Self {
         scale_typegen::utils::ensure_unique_type_paths(metadata.types_mut());
-        RuntimeGenerator { metadata }
+        RuntimeGenerator {
+            metadata,
+            client_crate_path: None,
+            feature_gates: false,
+        }
     }
 
-    /// Generate the API for interacting with a Substrate runtime.
+    /// Gate each pallet module behind a `pallet-<name>` feature, and each runtime API
+    /// behind a `runtime-api-<name>` feature, so that crates using the generated code
+    /// only compile the parts of it that they need.
+    pub fn generate_feature_gates(&mut self) {
+        self.feature_gates = true;
+    }
+
+    /// Additionally generate an interface which is bound to some client, so that storage
+    /// entries and runtime APIs can be accessed without manually constructing and handing
+    /// addresses and payloads to the client.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_crate_path` - Path to the `subxt` crate (not `subxt_core`) that the client is from.
+    pub fn generate_client_bindings(&mut self, client_crate_path: syn::Path) {
+        self.client_crate_path = Some(client_crate_path);
+    }
+
+    /// Generate the types for a Substrate runtime.
     ///
     /// # Arguments
     ///
@@ -65,7 +90,7 @@ impl RuntimeGenerator {
         type_substitutes: scale_typegen::TypeSubstitutes,
         crate_path: syn::Path,
         should_gen_docs: bool,
-    ) -> Result<TokenStream2, CodegenError> {
+    ) -> Result<ModuleTree, CodegenError> {
         let item_mod_attrs = item_mod.attrs.clone();
         let item_mod_ir = ir::ItemMod::try_from(item_mod)?;
 
@@ -73,18 +98,13 @@ impl RuntimeGenerator {
             subxt_type_gen_settings(derives, type_substitutes, &crate_path, should_gen_docs);
 
         let type_gen = TypeGenerator::new(self.metadata.types(), &settings);
-        let types_mod = type_gen
-            .generate_types_mod()?
-            .to_token_stream(type_gen.settings());
-        let mod_ident = &item_mod_ir.ident;
+        let (types_root_items, types_children) = generate_types_mod_items(&type_gen)?;
         let rust_items = item_mod_ir.rust_items();
 
-        Ok(quote! {
-            #( #item_mod_attrs )*
-            #[allow(dead_code, unused_imports, non_camel_case_types)]
-            #[allow(clippy::all)]
-            #[allow(rustdoc::broken_intra_doc_links)]
-            pub mod #mod_ident {
+        Ok(ModuleTree {
+            attrs: module_attrs(item_mod_attrs),
+            ident: item_mod_ir.ident.clone(),
+            root_items: quote! {
                 // Preserve any Rust items that were previously defined in the adorned module
                 #( #rust_items ) *
 
@@ -94,9 +114,12 @@ impl RuntimeGenerator {
                 mod root_mod {
                     pub use super::*;
                 }
-
-                #types_mod
-            }
+            },
+            pallets: Vec::new(),
+            types_mod_ident: type_gen.types_mod_ident().clone(),
+            types_root_items,
+            types_children,
+            features: Vec::new(),
         })
     }
 
@@ -116,7 +139,7 @@ impl RuntimeGenerator {
         type_substitutes: scale_typegen::TypeSubstitutes,
         crate_path: syn::Path,
         should_gen_docs: bool,
-    ) -> Result<TokenStream2, CodegenError> {
+    ) -> Result<ModuleTree, CodegenError> {
         let item_mod_attrs = item_mod.attrs.clone();
         let item_mod_ir = ir::ItemMod::try_from(item_mod)?;
 
@@ -124,17 +147,21 @@ impl RuntimeGenerator {
             subxt_type_gen_settings(derives, type_substitutes, &crate_path, should_gen_docs);
 
         let type_gen = TypeGenerator::new(self.metadata.types(), &settings);
-        let types_mod = type_gen
-            .generate_types_mod()?
-            .to_token_stream(type_gen.settings());
+        let (types_root_items, types_children) = generate_types_mod_items(&type_gen)?;
         let types_mod_ident = type_gen.types_mod_ident();
         let pallets_with_mod_names = self
             .metadata
             .pallets()
             .map(|pallet| {
+                let cfg = if self.feature_gates {
+                    feature_gate(&pallet_feature_name(pallet.name()))
+                } else {
+                    Default::default()
+                };
                 (
                     pallet,
                     format_ident!("{}", pallet.name().to_string().to_snake_case()),
+                    cfg,
                 )
             })
             .collect::<Vec<_>>();
@@ -157,22 +184,26 @@ impl RuntimeGenerator {
         let runtime_api_names_len = runtime_api_names.len();
 
         let metadata_hash = self.metadata.hasher().hash();
+        let client_crate_path = self.client_crate_path.as_ref();
 
         let modules = pallets_with_mod_names
             .iter()
-            .map(|(pallet, mod_name)| {
+            .map(|(pallet, mod_name, cfg)| {
                 let calls = calls::generate_calls(&type_gen, pallet, &crate_path)?;
 
                 let event = events::generate_events(&type_gen, pallet, &crate_path)?;
 
-                let storage_mod = storage::generate_storage(&type_gen, pallet, &crate_path)?;
+                let storage_mod =
+                    storage::generate_storage(&type_gen, pallet, &crate_path, client_crate_path)?;
 
                 let constants_mod = constants::generate_constants(&type_gen, pallet, &crate_path)?;
 
                 let errors = errors::generate_error_type_alias(&type_gen, pallet)?;
 
-                Ok(quote! {
-                    pub mod #mod_name {
+                Ok((
+                    cfg.clone(),
+                    mod_name.clone(),
+                    quote! {
                         use super::root_mod;
                         use super::#types_mod_ident;
                         #errors
@@ -180,32 +211,38 @@ impl RuntimeGenerator {
                         #event
                         #storage_mod
                         #constants_mod
-                    }
-                })
+                    },
+                ))
             })
             .collect::<Result<Vec<_>, CodegenError>>()?;
 
-        let mod_ident = &item_mod_ir.ident;
-        let pallets_with_constants: Vec<_> = pallets_with_mod_names
-            .iter()
-            .filter_map(|(pallet, pallet_mod_name)| {
-                pallet
-                    .constants()
-                    .next()
-                    .is_some()
-                    .then_some(pallet_mod_name)
-            })
-            .collect();
+        let (pallets_with_constants_cfgs, pallets_with_constants): (Vec<_>, Vec<_>) =
+            pallets_with_mod_names
+                .iter()
+                .filter_map(|(pallet, pallet_mod_name, cfg)| {
+                    pallet
+                        .constants()
+                        .next()
+                        .is_some()
+                        .then_some((cfg, pallet_mod_name))
+                })
+                .unzip();
 
-        let pallets_with_storage: Vec<_> = pallets_with_mod_names
-            .iter()
-            .filter_map(|(pallet, pallet_mod_name)| pallet.storage().map(|_| pallet_mod_name))
-            .collect();
+        let (pallets_with_storage_cfgs, pallets_with_storage): (Vec<_>, Vec<_>) =
+            pallets_with_mod_names
+                .iter()
+                .filter_map(|(pallet, pallet_mod_name, cfg)| {
+                    pallet.storage().map(|_| (cfg, pallet_mod_name))
+                })
+                .unzip();
 
-        let pallets_with_calls: Vec<_> = pallets_with_mod_names
-            .iter()
-            .filter_map(|(pallet, pallet_mod_name)| pallet.call_ty_id().map(|_| pallet_mod_name))
-            .collect();
+        let (pallets_with_calls_cfgs, pallets_with_calls): (Vec<_>, Vec<_>) =
+            pallets_with_mod_names
+                .iter()
+                .filter_map(|(pallet, pallet_mod_name, cfg)| {
+                    pallet.call_ty_id().map(|_| (cfg, pallet_mod_name))
+                })
+                .unzip();
 
         let rust_items = item_mod_ir.rust_items();
 
@@ -214,8 +251,62 @@ impl RuntimeGenerator {
             &type_gen,
             types_mod_ident,
             &crate_path,
+            client_crate_path,
+            self.feature_gates,
         )?;
 
+        let client_bindings = client_crate_path.map(|client_crate_path| {
+            quote! {
+                /// An interface which is bound to a client, so that storage entries and runtime APIs
+                /// can be accessed directly, for example `client_api().storage(&client).system().account(id).fetch().await`.
+                pub fn client_api() -> ClientApi {
+                    ClientApi
+                }
+
+                pub struct ClientApi;
+
+                impl ClientApi {
+                    pub fn storage<'client, T, Client>(&self, client: &'client Client) -> BoundStorageApi<'client, T, Client>
+                    where
+                        T: #client_crate_path::Config,
+                        Client: #client_crate_path::client::OnlineClientT<T>,
+                    {
+                        BoundStorageApi(#client_crate_path::client::Binding::new(client))
+                    }
+
+                    pub fn runtime_apis<'client, T, Client>(&self, client: &'client Client) -> runtime_apis::BoundRuntimeApi<'client, T, Client>
+                    where
+                        T: #client_crate_path::Config,
+                        Client: #client_crate_path::client::OnlineClientT<T>,
+                    {
+                        runtime_apis::BoundRuntimeApi(#client_crate_path::client::Binding::new(client))
+                    }
+                }
+
+                pub struct BoundStorageApi<'client, T: #client_crate_path::Config, Client>(
+                    pub #client_crate_path::client::Binding<'client, T, Client>,
+                );
+
+                impl<'client, T, Client> BoundStorageApi<'client, T, Client>
+                where
+                    T: #client_crate_path::Config,
+                    Client: #client_crate_path::client::OnlineClientT<T>,
+                {
+                    /// Access storage at the block given rather than the latest finalized block.
+                    pub fn at(self, block_ref: impl Into<#client_crate_path::backend::BlockRef<T::Hash>>) -> Self {
+                        BoundStorageApi(self.0.at(block_ref))
+                    }
+
+                    #(
+                        #pallets_with_storage_cfgs
+                        pub fn #pallets_with_storage(&self) -> #pallets_with_storage::storage::BoundStorageApi<'client, T, Client> {
+                            #pallets_with_storage::storage::BoundStorageApi(self.0.clone())
+                        }
+                    )*
+                }
+            }
+        });
+
         // Fetch the paths of the outer enums.
         // Substrate exposes those under `kitchensink_runtime`, while Polkadot under `polkadot_runtime`.
 
@@ -231,108 +322,184 @@ impl RuntimeGenerator {
 
         let custom_values = generate_custom_values(&self.metadata, &type_gen, &crate_path);
 
-        Ok(quote! {
-            #( #item_mod_attrs )*
-            #[allow(dead_code, unused_imports, non_camel_case_types)]
-            #[allow(clippy::all)]
-            #[allow(rustdoc::broken_intra_doc_links)]
-            pub mod #mod_ident {
-                // Preserve any Rust items that were previously defined in the adorned module.
-                #( #rust_items ) *
+        let root_items = quote! {
+            // Preserve any Rust items that were previously defined in the adorned module.
+            #( #rust_items ) *
 
-                // Make it easy to access the root items via `root_mod` at different levels
-                // without reaching out of this module.
-                #[allow(unused_imports)]
-                mod root_mod {
-                    pub use super::*;
-                }
+            // Make it easy to access the root items via `root_mod` at different levels
+            // without reaching out of this module.
+            #[allow(unused_imports)]
+            mod root_mod {
+                pub use super::*;
+            }
 
-                // Identify the pallets composing the static metadata by name.
-                pub static PALLETS: [&str; #pallet_names_len] = [ #(#pallet_names,)* ];
+            // Identify the pallets composing the static metadata by name.
+            pub static PALLETS: [&str; #pallet_names_len] = [ #(#pallet_names,)* ];
 
-                // Runtime APIs in the metadata by name.
-                pub static RUNTIME_APIS: [&str; #runtime_api_names_len] = [ #(#runtime_api_names,)* ];
+            // Runtime APIs in the metadata by name.
+            pub static RUNTIME_APIS: [&str; #runtime_api_names_len] = [ #(#runtime_api_names,)* ];
 
-                /// The error type returned when there is a runtime issue.
-                pub type DispatchError = #types_mod_ident::sp_runtime::DispatchError;
+            /// The error type returned when there is a runtime issue.
+            pub type DispatchError = #types_mod_ident::sp_runtime::DispatchError;
 
-                /// The outer event enum.
-                pub type Event = #event_path;
+            /// The outer event enum.
+            pub type Event = #event_path;
 
-                /// The outer extrinsic enum.
-                pub type Call = #call_path;
+            /// The outer extrinsic enum.
+            pub type Call = #call_path;
 
-                /// The outer error enum represents the DispatchError's Module variant.
-                pub type Error = #error_path;
+            /// The outer error enum represents the DispatchError's Module variant.
+            pub type Error = #error_path;
 
-                pub fn constants() -> ConstantsApi {
-                    ConstantsApi
-                }
+            pub fn constants() -> ConstantsApi {
+                ConstantsApi
+            }
 
-                pub fn storage() -> StorageApi {
-                    StorageApi
-                }
+            pub fn storage() -> StorageApi {
+                StorageApi
+            }
 
-                pub fn tx() -> TransactionApi {
-                    TransactionApi
-                }
+            pub fn tx() -> TransactionApi {
+                TransactionApi
+            }
 
-                pub fn apis() -> runtime_apis::RuntimeApi {
-                    runtime_apis::RuntimeApi
-                }
+            pub fn apis() -> runtime_apis::RuntimeApi {
+                runtime_apis::RuntimeApi
+            }
 
-                #apis_mod
+            #apis_mod
 
-                pub fn custom() -> CustomValuesApi {
-                    CustomValuesApi
-                }
+            #client_bindings
 
-                #custom_values
+            pub fn custom() -> CustomValuesApi {
+                CustomValuesApi
+            }
 
-                pub struct ConstantsApi;
-                impl ConstantsApi {
-                    #(
-                        pub fn #pallets_with_constants(&self) -> #pallets_with_constants::constants::ConstantsApi {
-                            #pallets_with_constants::constants::ConstantsApi
-                        }
-                    )*
-                }
+            #custom_values
 
-                pub struct StorageApi;
-                impl StorageApi {
-                    #(
-                        pub fn #pallets_with_storage(&self) -> #pallets_with_storage::storage::StorageApi {
-                            #pallets_with_storage::storage::StorageApi
-                        }
-                    )*
-                }
+            pub struct ConstantsApi;
+            impl ConstantsApi {
+                #(
+                    #pallets_with_constants_cfgs
+                    pub fn #pallets_with_constants(&self) -> #pallets_with_constants::constants::ConstantsApi {
+                        #pallets_with_constants::constants::ConstantsApi
+                    }
+                )*
+            }
 
-                pub struct TransactionApi;
-                impl TransactionApi {
-                    #(
-                        pub fn #pallets_with_calls(&self) -> #pallets_with_calls::calls::TransactionApi {
-                            #pallets_with_calls::calls::TransactionApi
-                        }
-                    )*
-                }
+            pub struct StorageApi;
+            impl StorageApi {
+                #(
+                    #pallets_with_storage_cfgs
+                    pub fn #pallets_with_storage(&self) -> #pallets_with_storage::storage::StorageApi {
+                        #pallets_with_storage::storage::StorageApi
+                    }
+                )*
+            }
 
-                /// check whether the metadata provided is aligned with this statically generated code.
-                pub fn is_codegen_valid_for(metadata: &#crate_path::Metadata) -> bool {
-                    let runtime_metadata_hash = metadata
-                        .hasher()
-                        .only_these_pallets(&PALLETS)
-                        .only_these_runtime_apis(&RUNTIME_APIS)
-                        .hash();
-                    runtime_metadata_hash == [ #(#metadata_hash,)* ]
-                }
+            pub struct TransactionApi;
+            impl TransactionApi {
+                #(
+                    #pallets_with_calls_cfgs
+                    pub fn #pallets_with_calls(&self) -> #pallets_with_calls::calls::TransactionApi {
+                        #pallets_with_calls::calls::TransactionApi
+                    }
+                )*
+            }
 
-                #( #modules )*
-                #types_mod
+            /// check whether the metadata provided is aligned with this statically generated code.
+            pub fn is_codegen_valid_for(metadata: &#crate_path::Metadata) -> bool {
+                let runtime_metadata_hash = metadata
+                    .hasher()
+                    .only_these_pallets(&PALLETS)
+                    .only_these_runtime_apis(&RUNTIME_APIS)
+                    .hash();
+                runtime_metadata_hash == [ #(#metadata_hash,)* ]
             }
+        };
+
+        let features = if self.feature_gates {
+            let pallet_features = self
+                .metadata
+                .pallets()
+                .map(|pallet| pallet_feature_name(pallet.name()));
+            let runtime_api_features = self
+                .metadata
+                .runtime_api_traits()
+                .map(|api| runtime_api_feature_name(api.name()));
+            pallet_features.chain(runtime_api_features).collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(ModuleTree {
+            attrs: module_attrs(item_mod_attrs),
+            ident: item_mod_ir.ident.clone(),
+            root_items,
+            pallets: modules,
+            types_mod_ident: types_mod_ident.clone(),
+            types_root_items,
+            types_children,
+            features,
         })
     }
 }
 
+/// The name of the feature which the module for the given pallet is gated behind.
+pub fn pallet_feature_name(pallet_name: &str) -> String {
+    format!("pallet-{}", pallet_name.to_kebab_case())
+}
+
+/// The name of the feature which the given runtime API is gated behind.
+pub fn runtime_api_feature_name(runtime_api_name: &str) -> String {
+    format!("runtime-api-{}", runtime_api_name.to_kebab_case())
+}
+
+/// A `#[cfg(..)]` attribute which enables whatever follows only if the given feature is enabled.
+pub fn feature_gate(feature_name: &str) -> TokenStream2 {
+    quote!(#[cfg(feature = #feature_name)])
+}
+
+/// The attributes to decorate the generated module with, given the attributes that were
+/// already on the target module.
+fn module_attrs(item_mod_attrs: Vec<syn::Attribute>) -> Vec<syn::Attribute> {
+    let mut attrs = item_mod_attrs;
+    attrs.push(parse_quote!(#[allow(dead_code, unused_imports, non_camel_case_types)]));
+    attrs.push(parse_quote!(#[allow(clippy::all)]));
+    attrs.push(parse_quote!(#[allow(rustdoc::broken_intra_doc_links)]));
+    attrs
+}
+
+/// Generate the types module, returning the items at the root of it (ie the types
+/// which aren't part of any crate) and separately the contents of each child module.
+fn generate_types_mod_items(
+    type_gen: &TypeGenerator,
+) -> Result<(TokenStream2, Vec<(Ident, TokenStream2)>), CodegenError> {
+    let types_mod = type_gen.generate_types_mod()?;
+    let settings = type_gen.settings();
+    let root_mod = types_mod.root_mod();
+
+    let root_items = types_mod
+        .types()
+        .map(|(_, ty)| ty.to_token_stream(settings));
+    let children = types_mod
+        .children()
+        .map(|(name, child)| {
+            let modules = child.children().map(|(_, m)| m.to_token_stream(settings));
+            let types = child.types().map(|(_, ty)| ty.to_token_stream(settings));
+            let items = quote! {
+                use super::#root_mod;
+
+                #( #modules )*
+                #( #types )*
+            };
+            (name.clone(), items)
+        })
+        .collect();
+
+    Ok((quote!( #( #root_items )* ), children))
+}
+
 /// Return a vector of tuples of variant names and corresponding struct definitions.
 pub fn generate_structs_from_variants<F>(
     type_gen: &TypeGenerator,
@@ -14,6 +14,7 @@ use subxt_metadata::{Metadata, RuntimeApiMetadata};
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote};
 
+use super::{feature_gate, runtime_api_feature_name};
 use crate::CodegenError;
 
 /// Generates runtime functions for the given API metadata.
@@ -21,9 +22,16 @@ fn generate_runtime_api(
     api: RuntimeApiMetadata,
     type_gen: &TypeGenerator,
     crate_path: &syn::Path,
-) -> Result<(TokenStream2, TokenStream2), CodegenError> {
+    client_crate_path: Option<&syn::Path>,
+    feature_gates: bool,
+) -> Result<(TokenStream2, TokenStream2, TokenStream2), CodegenError> {
     // Trait name must remain as is (upper case) to identify the runtime call.
     let trait_name_str = api.name();
+    let cfg = if feature_gates {
+        feature_gate(&runtime_api_feature_name(trait_name_str))
+    } else {
+        Default::default()
+    };
     // The snake case for the trait name.
     let trait_name_snake = format_ident!("{}", api.name().to_snake_case());
     let docs = api.docs();
@@ -33,6 +41,8 @@ fn generate_runtime_api(
         .then_some(quote! { #( #[doc = #docs ] )* })
         .unwrap_or_default();
 
+    let trait_name = format_ident!("{}", trait_name_str);
+
     let structs_and_methods: Vec<_> = api
         .methods()
         .map(|method| {
@@ -145,17 +155,49 @@ fn generate_runtime_api(
                 }
             );
 
-            Ok((struct_input, method))
+            // The client bound version of the method calls the above and awaits the result.
+            let bound_method = client_crate_path.map(|client_crate_path| {
+                let fn_params = inputs.iter().map(|(fn_param, _, _, _)| fn_param);
+                let param_names = inputs.iter().map(|(_, _, name, _)| name);
+                quote!(
+                    #docs
+                    pub async fn #method_name(&self, #( #fn_params, )* ) -> ::core::result::Result<types::#method_name::output::Output, #client_crate_path::Error> {
+                        self.0.call(#trait_name.#method_name(#( #param_names, )*)).await
+                    }
+                )
+            });
+
+            Ok((struct_input, method, bound_method))
         })
         .collect::<Result<_, _>>()?;
 
-    let trait_name = format_ident!("{}", trait_name_str);
-
-    let structs = structs_and_methods.iter().map(|(struct_, _)| struct_);
-    let methods = structs_and_methods.iter().map(|(_, method)| method);
+    let structs = structs_and_methods.iter().map(|(struct_, _, _)| struct_);
+    let methods = structs_and_methods.iter().map(|(_, method, _)| method);
     let types_mod_ident = type_gen.types_mod_ident();
 
+    let bound_trait_name = format_ident!("Bound{}", trait_name_str);
+    let bound_runtime_api = client_crate_path.map(|client_crate_path| {
+        let bound_methods = structs_and_methods
+            .iter()
+            .map(|(_, _, bound_method)| bound_method);
+        quote!(
+            #docs
+            pub struct #bound_trait_name<'client, T: #client_crate_path::Config, Client>(
+                pub #client_crate_path::client::Binding<'client, T, Client>,
+            );
+
+            impl<'client, T, Client> #bound_trait_name<'client, T, Client>
+            where
+                T: #client_crate_path::Config,
+                Client: #client_crate_path::client::OnlineClientT<T>,
+            {
+                #( #bound_methods )*
+            }
+        )
+    });
+
     let runtime_api = quote!(
+        #cfg
         pub mod #trait_name_snake {
             use super::root_mod;
             use super::#types_mod_ident;
@@ -167,6 +209,8 @@ fn generate_runtime_api(
                 #( #methods )*
             }
 
+            #bound_runtime_api
+
             pub mod types {
                 use super::#types_mod_ident;
 
@@ -177,12 +221,24 @@ fn generate_runtime_api(
 
     // A getter for the `RuntimeApi` to get the trait structure.
     let trait_getter = quote!(
+        #cfg
         pub fn #trait_name_snake(&self) -> #trait_name_snake::#trait_name {
             #trait_name_snake::#trait_name
         }
     );
 
-    Ok((runtime_api, trait_getter))
+    // A getter for the client bound version of the trait structure.
+    let bound_trait_getter = match client_crate_path {
+        Some(_) => quote!(
+            #cfg
+            pub fn #trait_name_snake(&self) -> #trait_name_snake::#bound_trait_name<'client, T, Client> {
+                #trait_name_snake::#bound_trait_name(self.0.clone())
+            }
+        ),
+        None => quote!(),
+    };
+
+    Ok((runtime_api, trait_getter, bound_trait_getter))
 }
 
 /// Generate the runtime APIs.
@@ -191,14 +247,40 @@ pub fn generate_runtime_apis(
     type_gen: &TypeGenerator,
     types_mod_ident: &syn::Ident,
     crate_path: &syn::Path,
+    client_crate_path: Option<&syn::Path>,
+    feature_gates: bool,
 ) -> Result<TokenStream2, CodegenError> {
     let runtime_fns: Vec<_> = metadata
         .runtime_api_traits()
-        .map(|api| generate_runtime_api(api, type_gen, crate_path))
+        .map(|api| {
+            generate_runtime_api(api, type_gen, crate_path, client_crate_path, feature_gates)
+        })
         .collect::<Result<_, _>>()?;
 
-    let runtime_apis_def = runtime_fns.iter().map(|(apis, _)| apis);
-    let runtime_apis_getters = runtime_fns.iter().map(|(_, getters)| getters);
+    let runtime_apis_def = runtime_fns.iter().map(|(apis, _, _)| apis);
+    let runtime_apis_getters = runtime_fns.iter().map(|(_, getters, _)| getters);
+    let bound_runtime_apis_getters = runtime_fns.iter().map(|(_, _, getters)| getters);
+
+    let bound_runtime_api = client_crate_path.map(|client_crate_path| {
+        quote! {
+            pub struct BoundRuntimeApi<'client, T: #client_crate_path::Config, Client>(
+                pub #client_crate_path::client::Binding<'client, T, Client>,
+            );
+
+            impl<'client, T, Client> BoundRuntimeApi<'client, T, Client>
+            where
+                T: #client_crate_path::Config,
+                Client: #client_crate_path::client::OnlineClientT<T>,
+            {
+                /// Call runtime APIs at the block given rather than the latest finalized block.
+                pub fn at(self, block_ref: impl Into<#client_crate_path::backend::BlockRef<T::Hash>>) -> Self {
+                    BoundRuntimeApi(self.0.at(block_ref))
+                }
+
+                #( #bound_runtime_apis_getters )*
+            }
+        }
+    });
 
     Ok(quote! {
         pub mod runtime_apis {
@@ -213,6 +295,8 @@ pub fn generate_runtime_apis(
                 #( #runtime_apis_getters )*
             }
 
+            #bound_runtime_api
+
             #( #runtime_apis_def )*
         }
     })
@@ -272,7 +356,7 @@ mod tests {
                 false,
             )
             .expect("should be able to generate runtime");
-        generated.to_string()
+        generated.to_token_stream().to_string()
     }
 
     #[test]
@@ -438,4 +522,54 @@ mod tests {
         assert!(code.contains(&structure.to_string()));
         assert!(code.contains(&expected_alias.to_string()));
     }
+
+    #[test]
+    fn client_bindings() {
+        let runtime_apis = vec![RuntimeApiMetadata {
+            name: "Test",
+            methods: vec![RuntimeApiMethodMetadata {
+                name: "test",
+                inputs: vec![RuntimeApiMethodParamMetadata {
+                    name: "foo",
+                    ty: meta_type::<bool>(),
+                }],
+                output: meta_type::<bool>(),
+                docs: vec![],
+            }],
+
+            docs: vec![],
+        }];
+
+        let metadata = metadata_with_runtime_apis(runtime_apis);
+        let item_mod = syn::parse_quote!(
+            pub mod api {}
+        );
+        let mut generator = RuntimeGenerator::new(metadata);
+        generator.generate_client_bindings(syn::parse_str("::client_path").unwrap());
+        let code = generator
+            .generate_runtime(
+                item_mod,
+                Default::default(),
+                Default::default(),
+                syn::parse_str("::subxt_path").unwrap(),
+                false,
+            )
+            .expect("should be able to generate runtime")
+            .to_token_stream()
+            .to_string();
+
+        let bound_method = quote! {
+            pub async fn test(&self, foo: types::test::Foo,) -> ::core::result::Result<types::test::output::Output, ::client_path::Error> {
+                self.0.call(Test.test(foo,)).await
+            }
+        };
+        let bound_getter = quote! {
+            pub fn test(&self) -> test::BoundTest<'client, T, Client> {
+                test::BoundTest(self.0.clone())
+            }
+        };
+
+        assert!(code.contains(&bound_method.to_string()));
+        assert!(code.contains(&bound_getter.to_string()));
+    }
 }
@@ -23,24 +23,46 @@ use scale_typegen::typegen::ir::ToTokensWithSettings;
 /// - `type_gen` - [`scale_typegen::TypeGenerator`] that contains settings and all types from the runtime metadata.
 /// - `pallet` - Pallet metadata from which the storage items are generated.
 /// - `crate_path` - The crate path under which the `subxt-core` crate is located, e.g. `::subxt::ext::subxt_core` when using subxt as a dependency.
+/// - `client_crate_path` - If given, the crate path under which the `subxt` crate is located, in order to
+///   additionally generate storage functions which are bound to a client.
 pub fn generate_storage(
     type_gen: &TypeGenerator,
     pallet: &PalletMetadata,
     crate_path: &syn::Path,
+    client_crate_path: Option<&syn::Path>,
 ) -> Result<TokenStream2, CodegenError> {
     let Some(storage) = pallet.storage() else {
         return Ok(quote!());
     };
 
-    let (storage_fns, alias_modules): (Vec<TokenStream2>, Vec<TokenStream2>) = storage
-        .entries()
-        .iter()
-        .map(|entry| generate_storage_entry_fns(type_gen, pallet, entry, crate_path))
-        .collect::<Result<Vec<_>, CodegenError>>()?
-        .into_iter()
-        .unzip();
+    let mut storage_fns = Vec::new();
+    let mut bound_storage_fns = Vec::new();
+    let mut alias_modules = Vec::new();
+    for entry in storage.entries() {
+        let (fns, bound_fns, alias_module) =
+            generate_storage_entry_fns(type_gen, pallet, entry, crate_path, client_crate_path)?;
+        storage_fns.push(fns);
+        bound_storage_fns.push(bound_fns);
+        alias_modules.push(alias_module);
+    }
     let types_mod_ident = type_gen.types_mod_ident();
 
+    let bound_storage_api = client_crate_path.map(|client_crate_path| {
+        quote! {
+            pub struct BoundStorageApi<'client, T: #client_crate_path::Config, Client>(
+                pub #client_crate_path::client::Binding<'client, T, Client>,
+            );
+
+            impl<'client, T, Client> BoundStorageApi<'client, T, Client>
+            where
+                T: #client_crate_path::Config,
+                Client: #client_crate_path::client::OnlineClientT<T>,
+            {
+                #( #bound_storage_fns )*
+            }
+        }
+    });
+
     Ok(quote! {
         pub mod storage {
             use super::#types_mod_ident;
@@ -56,17 +78,21 @@ pub fn generate_storage(
             impl StorageApi {
                 #( #storage_fns )*
             }
+
+            #bound_storage_api
         }
     })
 }
 
-/// Returns storage entry functions and alias modules.
+/// Returns storage entry functions, client bound storage entry functions (if a client
+/// crate path is given) and alias modules.
 fn generate_storage_entry_fns(
     type_gen: &TypeGenerator,
     pallet: &PalletMetadata,
     storage_entry: &StorageEntryMetadata,
     crate_path: &syn::Path,
-) -> Result<(TokenStream2, TokenStream2), CodegenError> {
+    client_crate_path: Option<&syn::Path>,
+) -> Result<(TokenStream2, TokenStream2, TokenStream2), CodegenError> {
     let snake_case_name = storage_entry.name().to_snake_case();
     let storage_entry_ty = storage_entry.entry_type().value_ty();
     let storage_entry_value_ty = type_gen
@@ -245,26 +271,34 @@ fn generate_storage_entry_fns(
             }
         };
 
-        let key_args = keys_slice.iter().map(
-            |MapEntryKey {
-                 arg_name,
-                 alias_type_path,
-                 ..
-             }| quote!( #arg_name: impl ::core::borrow::Borrow<#alias_type_path> ),
-        );
+        let key_args: Vec<_> = keys_slice
+            .iter()
+            .map(
+                |MapEntryKey {
+                     arg_name,
+                     alias_type_path,
+                     ..
+                 }| quote!( #arg_name: impl ::core::borrow::Borrow<#alias_type_path> ),
+            )
+            .collect();
+        let arg_names = keys_slice.iter().map(|key| &key.arg_name);
 
-        quote!(
-            #docs
-            pub fn #fn_name(
-                &self,
-                #(#key_args,)*
-            ) -> #crate_path::storage::address::StaticAddress::<
+        let address_type = quote!(
+            #crate_path::storage::address::StaticAddress::<
                 #keys_type,
                 #alias_storage_path,
                 #is_fetchable_type,
                 #is_defaultable_type,
                 #is_iterable_type
-            > {
+            >
+        );
+
+        let storage_fn = quote!(
+            #docs
+            pub fn #fn_name(
+                &self,
+                #(#key_args,)*
+            ) -> #address_type {
                 #crate_path::storage::address::StaticAddress::new_static(
                     #pallet_name,
                     #storage_name,
@@ -272,8 +306,23 @@ fn generate_storage_entry_fns(
                     [#(#storage_hash,)*]
                 )
             }
-        )
+        );
+
+        let bound_storage_fn = client_crate_path.map(|client_crate_path| {
+            quote!(
+                #docs
+                pub fn #fn_name(
+                    &self,
+                    #(#key_args,)*
+                ) -> #client_crate_path::client::BoundAddress<'client, T, Client, #address_type> {
+                    self.0.bind_address(StorageApi.#fn_name(#(#arg_names,)*))
+                }
+            )
+        });
+
+        (storage_fn, bound_storage_fn)
     });
+    let (all_fns, all_bound_fns): (Vec<_>, Vec<_>) = all_fns.unzip();
 
     let alias_types = keys
         .iter()
@@ -296,6 +345,9 @@ fn generate_storage_entry_fns(
         quote! {
             #( #all_fns )*
         },
+        quote! {
+            #( #all_bound_fns )*
+        },
         alias_module,
     ))
 }
@@ -421,7 +473,7 @@ mod tests {
                 false,
             )
             .expect("should be able to generate runtime");
-        let generated_str = generated.to_string();
+        let generated_str = generated.to_token_stream().to_string();
 
         for ((name, _), expected_type) in storage_entries
             .into_iter()
@@ -0,0 +1,432 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Generate an interface from a `build.rs` script, pinning the metadata it was generated
+//! from with a lockfile.
+//!
+//! The first time that the build script runs, the metadata is fetched from a node and
+//! saved to a file, alongside a lockfile which records the spec version of the runtime
+//! and the hash of the metadata (as well as the hash of each pallet and runtime API within
+//! it). Both files are expected to be checked in. From then on, the saved metadata is used,
+//! and the code is regenerated only when one of these files changes. If the metadata and
+//! lockfile ever disagree, the build fails with a summary of what differs.
+//!
+//! To update the metadata, run the build with the `SUBXT_UPDATE_METADATA` environment
+//! variable set; this fetches the metadata again and rewrites both files.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! // build.rs
+//! use subxt_codegen::build::Build;
+//!
+//! fn main() {
+//!     let mut build = Build::new("metadata/polkadot.scale");
+//!     build.set_url("wss://rpc.polkadot.io:443".parse().unwrap());
+//!     build.run().unwrap();
+//! }
+//! ```
+//!
+//! The generated code is then included with:
+//!
+//! ```rust,ignore
+//! include!(concat!(env!("OUT_DIR"), "/subxt_interface.rs"));
+//! ```
+
+use crate::error::BuildError;
+use crate::fetch_metadata::{fetch_metadata_from_url_blocking, MetadataVersion, Url};
+use crate::CodegenBuilder;
+use codec::Decode;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use subxt_metadata::Metadata;
+
+/// When this environment variable is set to anything other than `0`, the metadata is
+/// fetched again and the metadata file and lockfile are overwritten.
+pub const UPDATE_METADATA_ENV_VAR: &str = "SUBXT_UPDATE_METADATA";
+
+/// Generate an interface from a build script. See the [module docs](self) for more.
+pub struct Build {
+    metadata_path: PathBuf,
+    lockfile_path: Option<PathBuf>,
+    url: Option<Url>,
+    metadata_version: MetadataVersion,
+    codegen: CodegenBuilder,
+    out_file: String,
+}
+
+impl Build {
+    /// Generate code from the metadata at the path given, relative to the crate root. The
+    /// lockfile is stored alongside it with a `.lock` extension, unless otherwise configured.
+    pub fn new(metadata_path: impl Into<PathBuf>) -> Self {
+        Build {
+            metadata_path: metadata_path.into(),
+            lockfile_path: None,
+            url: None,
+            metadata_version: MetadataVersion::Latest,
+            codegen: CodegenBuilder::new(),
+            out_file: "subxt_interface.rs".to_owned(),
+        }
+    }
+
+    /// The URL of a node to fetch the metadata from, if it has not been fetched yet or
+    /// [`UPDATE_METADATA_ENV_VAR`] is set. Without this, the metadata file must exist.
+    pub fn set_url(&mut self, url: Url) {
+        self.url = Some(url);
+    }
+
+    /// The version of the metadata to fetch from the node. Defaults to the latest stable version.
+    pub fn set_metadata_version(&mut self, version: MetadataVersion) {
+        self.metadata_version = version;
+    }
+
+    /// Set the path to the lockfile, relative to the crate root.
+    pub fn set_lockfile_path(&mut self, lockfile_path: impl Into<PathBuf>) {
+        self.lockfile_path = Some(lockfile_path.into());
+    }
+
+    /// Configure the code that will be generated.
+    pub fn set_codegen(&mut self, codegen: CodegenBuilder) {
+        self.codegen = codegen;
+    }
+
+    /// The name of the file in `OUT_DIR` to write the generated code to.
+    /// Defaults to `subxt_interface.rs`.
+    pub fn set_out_file(&mut self, out_file: impl Into<String>) {
+        self.out_file = out_file.into();
+    }
+
+    /// Check the metadata against the lockfile (fetching the metadata first if needed) and
+    /// generate the interface into `OUT_DIR`, returning the path to the generated file.
+    ///
+    /// This also tells Cargo to re-run the build script only when the metadata file, the
+    /// lockfile or [`UPDATE_METADATA_ENV_VAR`] change.
+    pub fn run(self) -> Result<PathBuf, BuildError> {
+        let out_dir = std::env::var_os("OUT_DIR").ok_or(BuildError::NoOutDir)?;
+        let update = std::env::var(UPDATE_METADATA_ENV_VAR).is_ok_and(|v| v != "0");
+        let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from);
+
+        let build = match manifest_dir {
+            Some(dir) => self.relative_to(&dir),
+            None => self,
+        };
+
+        println!("cargo:rerun-if-changed={}", build.metadata_path.display());
+        println!("cargo:rerun-if-changed={}", build.lockfile_path().display());
+        println!("cargo:rerun-if-env-changed={UPDATE_METADATA_ENV_VAR}");
+
+        build.run_in(Path::new(&out_dir), update)
+    }
+
+    fn relative_to(mut self, dir: &Path) -> Self {
+        self.metadata_path = dir.join(&self.metadata_path);
+        self.lockfile_path = self.lockfile_path.map(|path| dir.join(path));
+        self
+    }
+
+    fn lockfile_path(&self) -> PathBuf {
+        self.lockfile_path
+            .clone()
+            .unwrap_or_else(|| self.metadata_path.with_extension("lock"))
+    }
+
+    fn run_in(self, out_dir: &Path, update: bool) -> Result<PathBuf, BuildError> {
+        let lockfile_path = self.lockfile_path();
+        let metadata_path = &self.metadata_path;
+
+        let metadata = if update || !metadata_path.exists() {
+            let url = self
+                .url
+                .ok_or_else(|| BuildError::MissingMetadata(display(metadata_path)))?;
+            let bytes = fetch_metadata_from_url_blocking(url, self.metadata_version)?;
+            let metadata = decode_metadata(metadata_path, &bytes)?;
+            write(metadata_path, &bytes)?;
+            write(&lockfile_path, MetadataLock::new(&metadata).to_string())?;
+            metadata
+        } else {
+            let bytes = read(metadata_path)?;
+            let metadata = decode_metadata(metadata_path, &bytes)?;
+            let lock = MetadataLock::new(&metadata);
+            if lockfile_path.exists() {
+                let locked = MetadataLock::parse(&String::from_utf8_lossy(&read(&lockfile_path)?))
+                    .map_err(|e| BuildError::InvalidLockfile(display(&lockfile_path), e))?;
+                if locked != lock {
+                    return Err(BuildError::LockfileMismatch {
+                        metadata: display(metadata_path),
+                        lockfile: display(&lockfile_path),
+                        diff: locked.diff(&lock),
+                    });
+                }
+            } else {
+                write(&lockfile_path, lock.to_string())?;
+            }
+            metadata
+        };
+
+        let code = self.codegen.generate(metadata)?;
+        let out_path = out_dir.join(&self.out_file);
+        write(&out_path, code.to_string())?;
+        Ok(out_path)
+    }
+}
+
+/// The contents of a metadata lockfile: the spec version of the runtime (if it could be
+/// found in the metadata), and hashes of the metadata and of each pallet and runtime API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataLock {
+    spec_version: Option<u32>,
+    metadata_hash: String,
+    pallets: BTreeMap<String, String>,
+    runtime_apis: BTreeMap<String, String>,
+}
+
+impl MetadataLock {
+    /// Compute the lock for some metadata.
+    pub fn new(metadata: &Metadata) -> Self {
+        let spec_version = metadata
+            .pallet_by_name("System")
+            .and_then(|pallet| pallet.constant_by_name("Version"))
+            .and_then(|version| {
+                // The spec version is the 4th field of the `RuntimeVersion` struct.
+                <(String, String, u32, u32)>::decode(&mut version.value())
+                    .ok()
+                    .map(|(_spec_name, _impl_name, _authoring_version, spec_version)| spec_version)
+            });
+
+        MetadataLock {
+            spec_version,
+            metadata_hash: to_hex(metadata.hasher().hash()),
+            pallets: metadata
+                .pallets()
+                .map(|p| (p.name().to_owned(), to_hex(p.hash())))
+                .collect(),
+            runtime_apis: metadata
+                .runtime_api_traits()
+                .map(|api| (api.name().to_owned(), to_hex(api.hash())))
+                .collect(),
+        }
+    }
+
+    /// The spec version of the runtime that the metadata came from.
+    pub fn spec_version(&self) -> Option<u32> {
+        self.spec_version
+    }
+
+    /// The `0x` prefixed hex encoded hash of the metadata.
+    pub fn metadata_hash(&self) -> &str {
+        &self.metadata_hash
+    }
+
+    /// Parse a lockfile, as written by the [`std::fmt::Display`] impl.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut spec_version = None;
+        let mut metadata_hash = None;
+        let mut pallets = BTreeMap::new();
+        let mut runtime_apis = BTreeMap::new();
+        let mut section = None;
+
+        for (idx, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = Some(name.to_owned());
+                continue;
+            }
+
+            let err = |msg: &str| format!("line {}: {msg}", idx + 1);
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| err("expected `key = value`"))?;
+            let (key, value) = (key.trim().trim_matches('"'), value.trim());
+            let string_value = || {
+                value
+                    .strip_prefix('"')
+                    .and_then(|v| v.strip_suffix('"'))
+                    .map(ToOwned::to_owned)
+                    .ok_or_else(|| err("expected a quoted string"))
+            };
+
+            match (section.as_deref(), key) {
+                (None, "spec_version") => {
+                    spec_version = Some(value.parse().map_err(|_| err("invalid spec version"))?)
+                }
+                (None, "metadata_hash") => metadata_hash = Some(string_value()?),
+                (Some("pallets"), name) => {
+                    pallets.insert(name.to_owned(), string_value()?);
+                }
+                (Some("runtime_apis"), name) => {
+                    runtime_apis.insert(name.to_owned(), string_value()?);
+                }
+                _ => return Err(err(&format!("unexpected key `{key}`"))),
+            }
+        }
+
+        Ok(MetadataLock {
+            spec_version,
+            metadata_hash: metadata_hash.ok_or("missing `metadata_hash`")?,
+            pallets,
+            runtime_apis,
+        })
+    }
+
+    /// Summarize the differences between this lock and another, one per line.
+    pub fn diff(&self, other: &MetadataLock) -> String {
+        let mut lines = vec![];
+        if self.spec_version != other.spec_version {
+            let fmt = |v: Option<u32>| v.map_or("unknown".to_owned(), |v| v.to_string());
+            lines.push(format!(
+                "  spec version: {} -> {}",
+                fmt(self.spec_version),
+                fmt(other.spec_version)
+            ));
+        }
+        if self.metadata_hash != other.metadata_hash {
+            lines.push(format!(
+                "  metadata hash: {} -> {}",
+                self.metadata_hash, other.metadata_hash
+            ));
+        }
+        diff_hashes(&mut lines, "pallets", &self.pallets, &other.pallets);
+        diff_hashes(
+            &mut lines,
+            "runtime APIs",
+            &self.runtime_apis,
+            &other.runtime_apis,
+        );
+        lines.join("\n")
+    }
+}
+
+impl std::fmt::Display for MetadataLock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "# Generated by subxt_codegen::build. Do not edit by hand."
+        )?;
+        if let Some(spec_version) = self.spec_version {
+            writeln!(f, "spec_version = {spec_version}")?;
+        }
+        writeln!(f, "metadata_hash = \"{}\"", self.metadata_hash)?;
+        writeln!(f, "\n[pallets]")?;
+        for (name, hash) in &self.pallets {
+            writeln!(f, "{name} = \"{hash}\"")?;
+        }
+        writeln!(f, "\n[runtime_apis]")?;
+        for (name, hash) in &self.runtime_apis {
+            writeln!(f, "{name} = \"{hash}\"")?;
+        }
+        Ok(())
+    }
+}
+
+fn diff_hashes(
+    lines: &mut Vec<String>,
+    what: &str,
+    old: &BTreeMap<String, String>,
+    new: &BTreeMap<String, String>,
+) {
+    let added: Vec<_> = new.keys().filter(|k| !old.contains_key(*k)).collect();
+    let removed: Vec<_> = old.keys().filter(|k| !new.contains_key(*k)).collect();
+    let changed: Vec<_> = old
+        .iter()
+        .filter(|(k, hash)| new.get(*k).is_some_and(|h| h != *hash))
+        .map(|(k, _)| k)
+        .collect();
+
+    for (verb, names) in [("added", added), ("removed", removed), ("changed", changed)] {
+        if !names.is_empty() {
+            let names: Vec<_> = names.into_iter().map(String::as_str).collect();
+            lines.push(format!("  {what} {verb}: {}", names.join(", ")));
+        }
+    }
+}
+
+fn to_hex(hash: [u8; 32]) -> String {
+    format!("0x{}", hex::encode(hash))
+}
+
+fn display(path: &Path) -> String {
+    path.to_string_lossy().into()
+}
+
+fn decode_metadata(path: &Path, bytes: &[u8]) -> Result<Metadata, BuildError> {
+    Metadata::decode(&mut &*bytes).map_err(|e| BuildError::Decode(display(path), e))
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, BuildError> {
+    std::fs::read(path).map_err(|e| BuildError::Io(display(path), e))
+}
+
+fn write(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), BuildError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| BuildError::Io(display(parent), e))?;
+    }
+    std::fs::write(path, contents).map_err(|e| BuildError::Io(display(path), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(name: &str) -> Metadata {
+        let bytes = std::fs::read(format!("../artifacts/{name}")).unwrap();
+        Metadata::decode(&mut &*bytes).unwrap()
+    }
+
+    #[test]
+    fn lockfile_roundtrips() {
+        let lock = MetadataLock::new(&metadata("polkadot_metadata_small.scale"));
+        assert!(lock.spec_version().is_some());
+        assert_eq!(MetadataLock::parse(&lock.to_string()).unwrap(), lock);
+    }
+
+    #[test]
+    fn lockfile_diff_summarizes_changes() {
+        let small = MetadataLock::new(&metadata("polkadot_metadata_small.scale"));
+        let tiny = MetadataLock::new(&metadata("polkadot_metadata_tiny.scale"));
+
+        let diff = small.diff(&tiny);
+        assert!(diff.contains("metadata hash:"), "{diff}");
+        assert!(diff.contains("pallets removed:"), "{diff}");
+        assert_eq!(small.diff(&small), "");
+    }
+
+    #[test]
+    fn mismatched_lockfile_fails_the_build() {
+        let dir = std::env::temp_dir().join(format!("subxt-build-{}", std::process::id()));
+        let metadata_path = dir.join("metadata.scale");
+        let out_dir = dir.join("out");
+        write(
+            &metadata_path,
+            std::fs::read("../artifacts/polkadot_metadata_tiny.scale").unwrap(),
+        )
+        .unwrap();
+
+        // No lockfile yet, so one is written and code is generated.
+        let out_file = Build::new(&metadata_path).run_in(&out_dir, false).unwrap();
+        assert!(out_file.exists());
+        assert!(dir.join("metadata.lock").exists());
+
+        // The metadata changes underneath the lockfile, so the build fails.
+        write(
+            &metadata_path,
+            std::fs::read("../artifacts/polkadot_metadata_small.scale").unwrap(),
+        )
+        .unwrap();
+        let err = Build::new(&metadata_path)
+            .run_in(&out_dir, false)
+            .unwrap_err();
+        assert!(matches!(err, BuildError::LockfileMismatch { .. }), "{err}");
+
+        // Without a URL, the metadata can't be updated.
+        let err = Build::new(&metadata_path)
+            .run_in(&out_dir, true)
+            .unwrap_err();
+        assert!(matches!(err, BuildError::MissingMetadata(_)), "{err}");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -57,6 +57,9 @@ pub enum CodegenError {
     /// Cannot generate types.
     #[error("Type Generation failed: {0}")]
     TypeGeneration(#[from] TypegenError),
+    /// The generated code could not be parsed in order to post-process it.
+    #[error("Failed to parse the generated code: {0}")]
+    InvalidGeneratedCode(syn::Error),
     /// Error when generating metadata from Wasm-runtime
     #[error("Failed to generate metadata from wasm file. reason: {0}")]
     Wasm(String),
@@ -106,3 +109,42 @@ pub enum FetchMetadataError {
     #[error("Other error: {0}")]
     Other(String),
 }
+
+/// Error generating code from a build script via [`crate::build`].
+#[cfg(feature = "fetch-metadata")]
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// Cannot fetch the metadata.
+    #[error("Failed to fetch metadata: {0}")]
+    Fetch(#[from] FetchMetadataError),
+    /// Cannot generate code from the metadata.
+    #[error("Failed to generate code: {0}")]
+    Codegen(#[from] CodegenError),
+    /// Cannot decode the metadata.
+    #[error("Could not decode the metadata at {0}: {1}")]
+    Decode(String, codec::Error),
+    /// Failed IO reading or writing a file.
+    #[error("Failed IO for {0}: {1}")]
+    Io(String, std::io::Error),
+    /// The metadata needs fetching (it does not exist yet, or an update was asked for), but
+    /// there is no URL to fetch it from.
+    #[error("The metadata at {0} needs fetching, but no URL was given to fetch it from")]
+    MissingMetadata(String),
+    /// The lockfile could not be parsed.
+    #[error("Could not parse the lockfile at {0}: {1}")]
+    InvalidLockfile(String, String),
+    /// The metadata does not match the lockfile.
+    #[error("The metadata at {metadata} does not match the lockfile at {lockfile}:\n{diff}\nIf the metadata has been updated on purpose, delete the lockfile to accept it, or set SUBXT_UPDATE_METADATA=1 to fetch the metadata again and update both")]
+    LockfileMismatch {
+        /// Path to the metadata file.
+        metadata: String,
+        /// Path to the lockfile.
+        lockfile: String,
+        /// A summary of the differences between the two.
+        diff: String,
+    },
+    /// `OUT_DIR` is not set, which likely means that we're not in a build script.
+    #[error("OUT_DIR is not set; code can only be generated this way from a build script")]
+    NoOutDir,
+}
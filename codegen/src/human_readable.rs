@@ -0,0 +1,250 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! When serde support is enabled, generated types derive `Serialize` and `Deserialize`.
+//! Most fields serialize sensibly as-is (account IDs as SS58 strings, hashes as hex
+//! and so on), but large integers and raw bytes need pointing at the helpers in
+//! `subxt_core::utils::human_readable`. That happens here, as a pass over the
+//! generated items.
+
+use crate::ModuleTree;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use std::collections::HashMap;
+use syn::parse_quote;
+
+/// Add `#[serde(with = "..")]` attributes to the fields of every generated type that
+/// derives `Serialize` and which contains integers or bytes that should be serialized
+/// in a human readable way.
+pub fn add_serde_field_attributes(
+    module_tree: &mut ModuleTree,
+    crate_path: &syn::Path,
+) -> Result<(), syn::Error> {
+    let helpers = Helpers::new(crate_path);
+
+    helpers.apply(&mut module_tree.root_items)?;
+    helpers.apply(&mut module_tree.types_root_items)?;
+    for (_, _, items) in &mut module_tree.pallets {
+        helpers.apply(items)?;
+    }
+    for (_, items) in &mut module_tree.types_children {
+        helpers.apply(items)?;
+    }
+    Ok(())
+}
+
+struct Helpers {
+    int_as_string: syn::Attribute,
+    bytes_as_hex: syn::Attribute,
+}
+
+impl Helpers {
+    fn new(crate_path: &syn::Path) -> Self {
+        let helper = |name: &str| {
+            let name = syn::Ident::new(name, proc_macro2::Span::call_site());
+            let path = quote!(#crate_path::utils::human_readable::#name).to_string();
+            parse_quote!(#[serde(with = #path)])
+        };
+        Helpers {
+            int_as_string: helper("int_as_string"),
+            bytes_as_hex: helper("bytes_as_hex"),
+        }
+    }
+
+    fn apply(&self, tokens: &mut TokenStream2) -> Result<(), syn::Error> {
+        let mut file: syn::File = syn::parse2(std::mem::take(tokens))?;
+        self.annotate_items(&mut file.items);
+        *tokens = quote!(#file);
+        Ok(())
+    }
+
+    fn annotate_items(&self, items: &mut [syn::Item]) {
+        // Calls, events and runtime API calls refer to their field types via type aliases
+        // in a sibling module, ie `pub new: set_max_code_size::New`, so look through those.
+        let mut aliases = HashMap::new();
+        for item in items.iter() {
+            let syn::Item::Mod(syn::ItemMod {
+                ident,
+                content: Some((_, mod_items)),
+                ..
+            }) = item
+            else {
+                continue;
+            };
+            for mod_item in mod_items {
+                if let syn::Item::Type(alias) = mod_item {
+                    aliases.insert((ident.clone(), alias.ident.clone()), (*alias.ty).clone());
+                }
+            }
+        }
+
+        for item in items.iter_mut() {
+            match item {
+                syn::Item::Struct(item) if derives_serialize(&item.attrs) => {
+                    self.annotate_fields(&mut item.fields, &aliases);
+                }
+                syn::Item::Enum(item) if derives_serialize(&item.attrs) => {
+                    for variant in &mut item.variants {
+                        self.annotate_fields(&mut variant.fields, &aliases);
+                    }
+                }
+                syn::Item::Mod(syn::ItemMod {
+                    content: Some((_, mod_items)),
+                    ..
+                }) => self.annotate_items(mod_items),
+                _ => {}
+            }
+        }
+    }
+
+    fn annotate_fields(
+        &self,
+        fields: &mut syn::Fields,
+        aliases: &HashMap<(syn::Ident, syn::Ident), syn::Type>,
+    ) {
+        for field in fields.iter_mut() {
+            let ty = resolve_alias(&field.ty, aliases).unwrap_or(&field.ty);
+            let attr = if is_large_int(ty) {
+                &self.int_as_string
+            } else if is_bytes(ty) {
+                &self.bytes_as_hex
+            } else {
+                continue;
+            };
+            field.attrs.push(attr.clone());
+        }
+    }
+}
+
+fn derives_serialize(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        let syn::Meta::List(list) = &attr.meta else {
+            return false;
+        };
+        list.path.is_ident("derive")
+            && list.tokens.clone().into_iter().any(|token| {
+                matches!(token, proc_macro2::TokenTree::Ident(ident) if ident == "Serialize")
+            })
+    })
+}
+
+fn resolve_alias<'a>(
+    ty: &syn::Type,
+    aliases: &'a HashMap<(syn::Ident, syn::Ident), syn::Type>,
+) -> Option<&'a syn::Type> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let mut segments = type_path.path.segments.iter();
+    let (Some(module), Some(alias), None) = (segments.next(), segments.next(), segments.next())
+    else {
+        return None;
+    };
+    aliases.get(&(module.ident.clone(), alias.ident.clone()))
+}
+
+/// Integers which may not fit into an `f64`, and so may lose precision in eg JSON,
+/// or `Option`s or `Vec`s of these.
+fn is_large_int(ty: &syn::Type) -> bool {
+    if let Some(inner) = option_or_vec_arg(ty) {
+        return is_large_int(inner);
+    }
+    last_segment(ty).is_some_and(|segment| {
+        segment.arguments.is_none()
+            && ["u64", "u128", "i64", "i128"]
+                .iter()
+                .any(|int| segment.ident == int)
+    })
+}
+
+/// `Vec<u8>` or `[u8; N]`, or `Option`s or `Vec`s of these. Serde can't derive
+/// implementations for arrays longer than 32 items, so it's important that these are
+/// found wherever they are nested.
+fn is_bytes(ty: &syn::Type) -> bool {
+    if let syn::Type::Array(array) = ty {
+        return is_u8(&array.elem);
+    }
+    match option_or_vec_arg(ty) {
+        Some(inner) if is_u8(inner) => last_segment(ty).is_some_and(|s| s.ident == "Vec"),
+        Some(inner) => is_bytes(inner),
+        None => false,
+    }
+}
+
+/// The type argument of an `Option<T>` or `Vec<T>`.
+fn option_or_vec_arg(ty: &syn::Type) -> Option<&syn::Type> {
+    let segment = last_segment(ty)?;
+    if segment.ident != "Option" && segment.ident != "Vec" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first() {
+        Some(syn::GenericArgument::Type(ty)) if args.args.len() == 1 => Some(ty),
+        _ => None,
+    }
+}
+
+fn is_u8(ty: &syn::Type) -> bool {
+    last_segment(ty).is_some_and(|segment| segment.arguments.is_none() && segment.ident == "u8")
+}
+
+fn last_segment(ty: &syn::Type) -> Option<&syn::PathSegment> {
+    match ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => type_path.path.segments.last(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn annotates_fields_through_type_aliases() {
+        let helpers = Helpers::new(&parse_quote!(::subxt_core));
+        let mut tokens = quote! {
+            #[derive(Debug, ::subxt_core::ext::serde::Serialize)]
+            pub struct Transfer {
+                pub dest: transfer::Dest,
+                pub value: transfer::Value,
+                pub data: ::std::vec::Vec<::core::primitive::u8>,
+                pub nested: ::core::option::Option<::core::primitive::u128>,
+                pub signatures: ::std::vec::Vec<[::core::primitive::u8; 64usize]>,
+                pub small: ::std::vec::Vec<::core::primitive::u32>,
+            }
+            pub mod transfer {
+                pub type Dest = ::subxt_core::utils::AccountId32;
+                pub type Value = ::core::primitive::u128;
+            }
+            #[derive(Debug)]
+            pub struct NotSerde(pub ::core::primitive::u128);
+        };
+        helpers.apply(&mut tokens).unwrap();
+
+        let expected = quote! {
+            #[derive(Debug, ::subxt_core::ext::serde::Serialize)]
+            pub struct Transfer {
+                pub dest: transfer::Dest,
+                #[serde(with = ":: subxt_core :: utils :: human_readable :: int_as_string")]
+                pub value: transfer::Value,
+                #[serde(with = ":: subxt_core :: utils :: human_readable :: bytes_as_hex")]
+                pub data: ::std::vec::Vec<::core::primitive::u8>,
+                #[serde(with = ":: subxt_core :: utils :: human_readable :: int_as_string")]
+                pub nested: ::core::option::Option<::core::primitive::u128>,
+                #[serde(with = ":: subxt_core :: utils :: human_readable :: bytes_as_hex")]
+                pub signatures: ::std::vec::Vec<[::core::primitive::u8; 64usize]>,
+                pub small: ::std::vec::Vec<::core::primitive::u32>,
+            }
+            pub mod transfer {
+                pub type Dest = ::subxt_core::utils::AccountId32;
+                pub type Value = ::core::primitive::u128;
+            }
+            #[derive(Debug)]
+            pub struct NotSerde(pub ::core::primitive::u128);
+        };
+        assert_eq!(tokens.to_string(), expected.to_string());
+    }
+}
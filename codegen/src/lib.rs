@@ -11,7 +11,9 @@
 
 mod api;
 pub mod error;
+mod human_readable;
 mod ir;
+mod module_tree;
 
 // These should probably be in a separate crate; they are used by the
 // macro and CLI tool, so they only live here because this is a common
@@ -20,6 +22,10 @@ mod ir;
 #[cfg_attr(docsrs, doc(cfg(feature = "fetch-metadata")))]
 pub mod fetch_metadata;
 
+#[cfg(feature = "fetch-metadata")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fetch-metadata")))]
+pub mod build;
+
 #[cfg(feature = "web")]
 use getrandom as _;
 
@@ -35,6 +41,7 @@ use syn::parse_quote;
 
 // Part of the public interface, so expose:
 pub use error::CodegenError;
+pub use module_tree::ModuleTree;
 pub use subxt_metadata::Metadata;
 pub use syn;
 
@@ -65,6 +72,10 @@ pub struct CodegenBuilder {
     use_default_substitutions: bool,
     generate_docs: bool,
     runtime_types_only: bool,
+    client_bindings: bool,
+    client_crate_path: syn::Path,
+    feature_gates: bool,
+    serde: bool,
     item_mod: syn::ItemMod,
     extra_global_derives: Vec<syn::Path>,
     extra_global_attributes: Vec<syn::Attribute>,
@@ -83,6 +94,10 @@ impl Default for CodegenBuilder {
             use_default_substitutions: true,
             generate_docs: true,
             runtime_types_only: false,
+            client_bindings: false,
+            client_crate_path: syn::parse_quote!(::subxt),
+            feature_gates: false,
+            serde: false,
             item_mod: syn::parse_quote!(
                 pub mod api {}
             ),
@@ -137,6 +152,43 @@ impl CodegenBuilder {
         self.runtime_types_only = true;
     }
 
+    /// Additionally generate an interface which is bound to a `subxt` client, so that
+    /// storage entries and runtime APIs can be accessed via for example
+    /// `api::client_api().storage(&client).system().account(id).fetch().await`.
+    ///
+    /// Unlike the rest of the generated interface, this relies on the `subxt` crate
+    /// (rather than just `subxt_core`) being available. By default, we expect it to be at
+    /// `::subxt`; see [`CodegenBuilder::set_client_crate_path()`].
+    pub fn generate_client_bindings(&mut self) {
+        self.client_bindings = true;
+    }
+
+    /// Gate each pallet module behind a `pallet-<name>` feature (for instance `pallet-balances`),
+    /// and each runtime API behind a `runtime-api-<name>` feature (for instance
+    /// `runtime-api-account-nonce-api`), so that crates using the generated code only compile the
+    /// parts of it that they enable. The generated types are not gated.
+    ///
+    /// The features need declaring in the `Cargo.toml` of the crate that the generated code lives
+    /// in; see [`ModuleTree::cargo_features()`].
+    pub fn generate_feature_gates(&mut self) {
+        self.feature_gates = true;
+    }
+
+    /// Derive `serde::Serialize` and `serde::Deserialize` on all of the generated types, so
+    /// that chain data can be converted to and from formats like JSON. Values are represented
+    /// in a human readable way: account IDs as SS58 strings, hashes and byte arrays as `0x`
+    /// prefixed hex strings, and integers that may not fit into an `f64` (`u64`, `u128`, `i64`
+    /// and `i128`) as strings. Integers and bytes are found in fields of these types, and in
+    /// `Option`s and `Vec`s of them, like `Option<u128>` or `Vec<[u8; 64]>`. Those nested any
+    /// other way, for instance in a tuple, are serialized as numbers and arrays.
+    ///
+    /// The derives use the `serde` crate re-exported from `subxt_core`, so there is no need to
+    /// depend on it separately. Any types substituted via [`CodegenBuilder::set_type_substitute()`]
+    /// must implement `serde::Serialize` and `serde::Deserialize` themselves.
+    pub fn enable_serde(&mut self) {
+        self.serde = true;
+    }
+
     /// Set the additional derives that will be applied to all types. By default,
     /// a set of derives required for Subxt are automatically added for all types.
     ///
@@ -237,10 +289,50 @@ impl CodegenBuilder {
         self.crate_path = crate_path;
     }
 
+    /// Set the path to the `subxt` crate that the client bindings make use of. By default,
+    /// we expect it to be at `::subxt`. This is only used if [`CodegenBuilder::generate_client_bindings()`]
+    /// is set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path provided is not an absolute path.
+    pub fn set_client_crate_path(&mut self, client_crate_path: syn::Path) {
+        if absolute_path(client_crate_path.clone()).is_err() {
+            panic!("The provided client crate path must be an absolute path, ie prefixed with '::' or 'crate'");
+        }
+        self.client_crate_path = client_crate_path;
+    }
+
     /// Generate an interface, assuming that the default path to the `subxt` crate is `::subxt::ext::subxt_core`.
     /// If the `subxt` crate is not available as a top level dependency, use `generate` and provide
     /// a valid path to the `subxt¦ crate.
     pub fn generate(self, metadata: Metadata) -> Result<TokenStream2, CodegenError> {
+        self.generate_module_tree(metadata)
+            .map(|module_tree| module_tree.to_token_stream())
+    }
+
+    /// Generate an interface in the same way as [`CodegenBuilder::generate()`], but return it as a
+    /// [`ModuleTree`], which can be written out as one file per pallet (plus a `runtime_types` directory)
+    /// rather than as a single module.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use codec::Decode;
+    /// use subxt_codegen::{ Metadata, CodegenBuilder };
+    ///
+    /// let encoded = std::fs::read("../artifacts/polkadot_metadata_full.scale").unwrap();
+    /// let metadata = Metadata::decode(&mut &*encoded).unwrap();
+    ///
+    /// // Writes `src/polkadot/mod.rs`, `src/polkadot/system.rs` and so on. This
+    /// // can then be used via `mod polkadot;` in `src/lib.rs`.
+    /// CodegenBuilder::new()
+    ///     .generate_module_tree(metadata)
+    ///     .unwrap()
+    ///     .write_to_dir("src/polkadot")
+    ///     .unwrap();
+    /// ```
+    pub fn generate_module_tree(self, metadata: Metadata) -> Result<ModuleTree, CodegenError> {
         let crate_path = self.crate_path;
 
         let mut derives_registry: DerivesRegistry = if self.use_default_derives {
@@ -252,6 +344,16 @@ impl CodegenBuilder {
         derives_registry.add_derives_for_all(self.extra_global_derives);
         derives_registry.add_attributes_for_all(self.extra_global_attributes);
 
+        if self.serde {
+            let serde_crate_path = quote::quote! { #crate_path::ext::serde }.to_string();
+            derives_registry.add_derives_for_all([
+                parse_quote!(#crate_path::ext::serde::Serialize),
+                parse_quote!(#crate_path::ext::serde::Deserialize),
+            ]);
+            derives_registry
+                .add_attributes_for_all([parse_quote!(#[serde(crate = #serde_crate_path)])]);
+        }
+
         for (ty, derives) in self.derives_for_type {
             derives_registry.add_derives_for(ty, derives, false);
         }
@@ -279,26 +381,38 @@ impl CodegenBuilder {
         }
 
         let item_mod = self.item_mod;
-        let generator = RuntimeGenerator::new(metadata);
+        let mut generator = RuntimeGenerator::new(metadata);
+        if self.client_bindings {
+            generator.generate_client_bindings(self.client_crate_path);
+        }
+        if self.feature_gates {
+            generator.generate_feature_gates();
+        }
         let should_gen_docs = self.generate_docs;
 
-        if self.runtime_types_only {
+        let mut module_tree = if self.runtime_types_only {
             generator.generate_runtime_types(
                 item_mod,
                 derives_registry,
                 type_substitutes,
-                crate_path,
+                crate_path.clone(),
                 should_gen_docs,
-            )
+            )?
         } else {
             generator.generate_runtime(
                 item_mod,
                 derives_registry,
                 type_substitutes,
-                crate_path,
+                crate_path.clone(),
                 should_gen_docs,
-            )
+            )?
+        };
+
+        if self.serde {
+            human_readable::add_serde_field_attributes(&mut module_tree, &crate_path)
+                .map_err(CodegenError::InvalidGeneratedCode)?;
         }
+        Ok(module_tree)
     }
 }
 
@@ -0,0 +1,164 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! The generated interface, split into the modules that make it up.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use std::path::{Path, PathBuf};
+
+/// A generated interface, split into its root module, one module per pallet and
+/// a `runtime_types` module containing one module per crate that types were
+/// generated from. This can be turned into a single token stream via
+/// [`ModuleTree::to_token_stream()`], or written out as a tree of files via
+/// [`ModuleTree::write_to_dir()`].
+#[derive(Debug, Clone)]
+pub struct ModuleTree {
+    pub(crate) attrs: Vec<syn::Attribute>,
+    pub(crate) ident: syn::Ident,
+    pub(crate) root_items: TokenStream2,
+    pub(crate) pallets: Vec<(TokenStream2, syn::Ident, TokenStream2)>,
+    pub(crate) types_mod_ident: syn::Ident,
+    pub(crate) types_root_items: TokenStream2,
+    pub(crate) types_children: Vec<(syn::Ident, TokenStream2)>,
+    pub(crate) features: Vec<String>,
+}
+
+impl ModuleTree {
+    /// The name of the root module, ie `api` when generating into `pub mod api {}`.
+    pub fn ident(&self) -> &syn::Ident {
+        &self.ident
+    }
+
+    /// Output the whole interface as a single module.
+    pub fn to_token_stream(&self) -> TokenStream2 {
+        let ModuleTree {
+            attrs,
+            ident,
+            root_items,
+            types_mod_ident,
+            types_root_items,
+            ..
+        } = self;
+
+        let pallets = self
+            .pallets
+            .iter()
+            .map(|(cfg, name, items)| quote!(#cfg pub mod #name { #items }));
+        let types_children = self
+            .types_children
+            .iter()
+            .map(|(name, items)| quote!(pub mod #name { #items }));
+
+        quote! {
+            #( #attrs )*
+            pub mod #ident {
+                #root_items
+
+                #( #pallets )*
+
+                pub mod #types_mod_ident {
+                    use super::#types_mod_ident;
+
+                    #( #types_children )*
+                    #types_root_items
+                }
+            }
+        }
+    }
+
+    /// Return the files that the interface is made up of, as paths relative to
+    /// some output directory alongside their contents. The root module lives in
+    /// `mod.rs`, each pallet in `<pallet_name>.rs`, and the generated types in a
+    /// `runtime_types` directory containing one file per crate.
+    pub fn files(&self) -> Vec<(PathBuf, TokenStream2)> {
+        let ModuleTree {
+            root_items,
+            types_mod_ident,
+            types_root_items,
+            ..
+        } = self;
+
+        // Outer attributes on the module become inner attributes at the top of `mod.rs`.
+        let attrs = self.attrs.iter().map(|attr| {
+            let mut attr = attr.clone();
+            attr.style = syn::AttrStyle::Inner(Default::default());
+            attr
+        });
+        let pallet_cfgs = self.pallets.iter().map(|(cfg, _, _)| cfg);
+        let pallet_names = self.pallets.iter().map(|(_, name, _)| name);
+        let types_children_names = self.types_children.iter().map(|(name, _)| name);
+
+        let mut files = vec![(
+            PathBuf::from("mod.rs"),
+            quote! {
+                #( #attrs )*
+
+                #root_items
+
+                #( #pallet_cfgs pub mod #pallet_names; )*
+
+                pub mod #types_mod_ident;
+            },
+        )];
+
+        for (_, name, items) in &self.pallets {
+            files.push((PathBuf::from(format!("{name}.rs")), items.clone()));
+        }
+
+        let types_dir = PathBuf::from(types_mod_ident.to_string());
+        files.push((
+            types_dir.join("mod.rs"),
+            quote! {
+                use super::#types_mod_ident;
+
+                #( pub mod #types_children_names; )*
+                #types_root_items
+            },
+        ));
+        for (name, items) in &self.types_children {
+            files.push((types_dir.join(format!("{name}.rs")), items.clone()));
+        }
+
+        files
+    }
+
+    /// The features that parts of the generated code are gated behind, if
+    /// [`crate::CodegenBuilder::generate_feature_gates()`] was used. These need declaring
+    /// in the `Cargo.toml` of the crate that the code is used in; see
+    /// [`ModuleTree::cargo_features()`].
+    pub fn features(&self) -> &[String] {
+        &self.features
+    }
+
+    /// A `[features]` table declaring each of the [`ModuleTree::features()`], plus
+    /// an `all` feature which enables every one of them, to be added to the
+    /// `Cargo.toml` of the crate that the code is used in.
+    pub fn cargo_features(&self) -> String {
+        let mut toml = String::from("[features]\n");
+        let all: Vec<_> = self.features.iter().map(|f| format!("\"{f}\"")).collect();
+        toml.push_str(&format!("all = [{}]\n", all.join(", ")));
+        for feature in &self.features {
+            toml.push_str(&format!("{feature} = []\n"));
+        }
+        toml
+    }
+
+    /// Write the files returned from [`ModuleTree::files()`] into the directory given,
+    /// creating it if necessary and overwriting any existing files with the same names.
+    ///
+    /// The output is not formatted; running `rustfmt` on the resulting `mod.rs` will format
+    /// every file in the tree.
+    pub fn write_to_dir(&self, dir: impl AsRef<Path>) -> std::io::Result<()> {
+        let dir = dir.as_ref();
+        for (path, contents) in self.files() {
+            let path = dir.join(path);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, format!("{contents}\n"))?;
+        }
+        Ok(())
+    }
+}
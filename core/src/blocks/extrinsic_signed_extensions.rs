@@ -3,11 +3,14 @@
 // see LICENSE for license details.
 
 use crate::config::signed_extensions::{
-    ChargeAssetTxPayment, ChargeTransactionPayment, CheckNonce,
+    ChargeAssetTxPayment, ChargeTransactionPayment, CheckMetadataHash, CheckMetadataHashMode,
+    CheckNonce, CheckSpecVersion, CheckTxVersion,
 };
 use crate::config::SignedExtension;
 use crate::dynamic::Value;
+use crate::utils::Era;
 use crate::{config::Config, error::Error, Metadata};
+use codec::Decode;
 use scale_decode::DecodeAsType;
 
 /// The signed extensions of an extrinsic.
@@ -114,6 +117,73 @@ impl<'a, T: Config> ExtrinsicSignedExtensions<'a, T> {
     pub fn nonce(&self) -> Option<u64> {
         self.find::<CheckNonce>().ok()?
     }
+
+    /// The era of the extrinsic, extracted from the CheckMortality signed extension. This
+    /// describes how long a mortal extrinsic is valid for; see [`Era::birth()`] and
+    /// [`Era::death()`] to work out the range of blocks that it is valid in.
+    ///
+    /// Returns `None` if the era was not found or decoding failed.
+    pub fn era(&self) -> Option<Era> {
+        // The era is decoded from its compact wire format, rather than from the (rather
+        // unwieldy) shape that it's given in the metadata.
+        let ext = self
+            .iter()
+            .find_map(|ext| ext.ok().filter(|ext| ext.name() == "CheckMortality"))?;
+        Era::decode(&mut ext.bytes()).ok()
+    }
+
+    /// Has the extrinsic expired by the block `block_number`, and so can no longer be included
+    /// in a block?
+    ///
+    /// A mortal extrinsic's era records the length of its lifetime and where its birth block
+    /// falls within a repeating period of that many blocks, but not which of those periods it
+    /// was born in. So `valid_at` must be the number of some block at which the extrinsic is
+    /// known to be valid; its birth block is then [`Era::birth()`] of this, and it expires at
+    /// [`Era::death()`] of this. For an extrinsic in a block, this can be the number of that
+    /// block. For a pending extrinsic, this can be the number of the best block when the
+    /// extrinsic was first seen in the transaction pool. If the extrinsic is not valid at
+    /// `valid_at`, the answer is for a different lifetime and is meaningless.
+    ///
+    /// Immortal extrinsics never expire. Returns `None` if the era was not found or decoding
+    /// failed.
+    pub fn is_expired_at(&self, block_number: u64, valid_at: u64) -> Option<bool> {
+        Some(block_number >= self.era()?.death(valid_at))
+    }
+
+    /// Does the extrinsic have the CheckSpecVersion signed extension? This adds the spec
+    /// version of the runtime to the signer payload rather than to the extrinsic, so the spec
+    /// version that it was signed against is that of a block at which it is valid, such as the
+    /// block that it is in, and can't be decoded from the extrinsic itself.
+    pub fn has_check_spec_version(&self) -> bool {
+        matches!(self.find::<CheckSpecVersion>(), Ok(Some(_)))
+    }
+
+    /// Does the extrinsic have the CheckTxVersion signed extension? Like
+    /// [`ExtrinsicSignedExtensions::has_check_spec_version()`], this adds the transaction
+    /// version of the runtime to the signer payload rather than to the extrinsic.
+    pub fn has_check_tx_version(&self) -> bool {
+        matches!(self.find::<CheckTxVersion>(), Ok(Some(_)))
+    }
+
+    /// Whether the extrinsic asks for the metadata hash to be checked, extracted from the
+    /// CheckMetadataHash signed extension.
+    ///
+    /// Returns `None` if the mode was not found or decoding failed.
+    pub fn metadata_hash_mode(&self) -> Option<CheckMetadataHashMode> {
+        self.find::<CheckMetadataHash>().ok()?
+    }
+
+    /// The ID of the asset that the fee of the extrinsic is paid in, extracted from the
+    /// ChargeAssetTxPayment signed extension.
+    ///
+    /// Returns `None` if the fee is paid in the native chain token, or if the asset ID was not
+    /// found or decoding failed.
+    pub fn asset_id(&self) -> Option<T::AssetId> {
+        self.find::<ChargeAssetTxPayment<T>>()
+            .ok()??
+            .asset_id()
+            .cloned()
+    }
 }
 
 /// A single signed extension
@@ -4,14 +4,18 @@
 
 use crate::blocks::extrinsic_signed_extensions::ExtrinsicSignedExtensions;
 use crate::{
-    config::{Config, Hasher},
-    error::{BlockError, Error, MetadataError},
+    client::ClientState,
+    config::{Config, ExtrinsicParams, ExtrinsicParamsEncoder, Hasher},
+    error::{BlockError, Error, ExtrinsicParamsError, MetadataError},
     Metadata,
 };
 use alloc::sync::Arc;
 use alloc::vec::Vec;
 use codec::{Compact, CompactLen, Decode};
+use derive_where::derive_where;
 use scale_decode::DecodeAsType;
+use scale_info::TypeDef;
+use sp_crypto_hashing::blake2_256;
 use subxt_metadata::PalletMetadata;
 
 pub use crate::blocks::StaticExtrinsic;
@@ -122,6 +126,7 @@ impl<T: Config> Extrinsics<T> {
 }
 
 /// A single extrinsic in a block.
+#[derive_where(Clone)]
 pub struct ExtrinsicDetails<T: Config> {
     /// The index of the extrinsic in the block.
     index: u32,
@@ -140,7 +145,20 @@ pub struct ExtrinsicDetails<T: Config> {
     _marker: core::marker::PhantomData<T>,
 }
 
+// The metadata is left out, since it is large and the same for every extrinsic in a block.
+impl<T: Config> core::fmt::Debug for ExtrinsicDetails<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ExtrinsicDetails")
+            .field("index", &self.index)
+            .field("bytes", &self.bytes)
+            .field("pallet_index", &self.pallet_index)
+            .field("variant_index", &self.variant_index)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Details only available in signed extrinsics.
+#[derive(Clone)]
 pub struct SignedExtrinsicDetails {
     /// start index of the range in `bytes` of `ExtrinsicDetails` that encodes the address.
     address_start_idx: usize,
@@ -418,6 +436,143 @@ where
 
         Ok(decoded)
     }
+
+    /// Decode the address that signed this extrinsic into some type, such as [`Config::Address`].
+    ///
+    /// Returns `None` if the extrinsic is not signed.
+    pub fn address<A: Decode>(&self) -> Result<Option<A>, Error> {
+        let Some(bytes) = self.address_bytes() else {
+            return Ok(None);
+        };
+        Ok(Some(A::decode(&mut &*bytes)?))
+    }
+
+    /// Decode the signature of this extrinsic into some type, such as [`Config::Signature`].
+    ///
+    /// Returns `None` if the extrinsic is not signed.
+    pub fn signature<S: Decode>(&self) -> Result<Option<S>, Error> {
+        let Some(bytes) = self.signature_bytes() else {
+            return Ok(None);
+        };
+        Ok(Some(S::decode(&mut &*bytes)?))
+    }
+
+    /// Return the account ID of the signer of this extrinsic. If the address is a `MultiAddress`,
+    /// this is the account ID in its `Id` variant; other variants (such as account indexes)
+    /// would need looking up on chain, and so lead to an error.
+    ///
+    /// Returns `None` if the extrinsic is not signed.
+    pub fn signer_account_id(&self) -> Result<Option<T::AccountId>, Error>
+    where
+        T::AccountId: Decode,
+    {
+        let Some(bytes) = self.address_bytes() else {
+            return Ok(None);
+        };
+
+        let address_ty = self.metadata.extrinsic().address_ty();
+        let ty = self
+            .metadata
+            .types()
+            .resolve(address_ty)
+            .ok_or(MetadataError::TypeNotFound(address_ty))?;
+
+        // An address which is a `MultiAddress`-like enum; the account ID is in the `Id` variant.
+        // Anything else is assumed to be the account ID itself.
+        let account_id_bytes = match &ty.type_def {
+            TypeDef::Variant(variants) => {
+                let is_id = |index: u8| {
+                    variants
+                        .variants
+                        .iter()
+                        .any(|v| v.index == index && v.name == "Id" && v.fields.len() == 1)
+                };
+                match bytes.split_first() {
+                    Some((index, account_id_bytes)) if is_id(*index) => account_id_bytes,
+                    _ => {
+                        return Err(BlockError::DecodingError(codec::Error::from(
+                            "The address that signed the extrinsic is not an account ID",
+                        ))
+                        .into())
+                    }
+                }
+            }
+            _ => bytes,
+        };
+
+        Ok(Some(T::AccountId::decode(&mut &*account_id_bytes)?))
+    }
+
+    /// Rebuild the payload that was signed to produce the signature of this extrinsic, from its
+    /// call data, its signed extension data, and the "additional" data that each signed extension
+    /// contributes to the payload but which is not included in the extrinsic itself.
+    ///
+    /// The additional data is obtained by constructing the [`Config::ExtrinsicParams`] from the
+    /// given client state and params. These should be the same as those used when the extrinsic
+    /// was signed: the client state should have the genesis hash and runtime version of the chain
+    /// at the time, and for mortal transactions the params should provide the same mortality
+    /// checkpoint (ie the hash of the birth block; see [`crate::utils::Era::birth()`]).
+    ///
+    /// The metadata hash can't be rebuilt, so this returns an error for extrinsics which ask for
+    /// it to be checked. Returns `None` if the extrinsic is not signed.
+    pub fn signer_payload(
+        &self,
+        client_state: &ClientState<T>,
+        params: <T::ExtrinsicParams as ExtrinsicParams<T>>::Params,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let Some(extra_bytes) = self.signed_extensions_bytes() else {
+            return Ok(None);
+        };
+        let metadata_hash_mode = self
+            .signed_extensions()
+            .and_then(|exts| exts.metadata_hash_mode());
+        if metadata_hash_mode.is_some_and(|mode| mode.is_enabled()) {
+            return Err(ExtrinsicParamsError::CannotRebuildAdditional(
+                "the metadata hash is checked",
+            )
+            .into());
+        }
+        let additional_and_extra_params =
+            <T::ExtrinsicParams as ExtrinsicParams<T>>::new(client_state, params)?;
+
+        let mut bytes = self.call_bytes().to_vec();
+        bytes.extend_from_slice(extra_bytes);
+        additional_and_extra_params.encode_additional_to(&mut bytes);
+
+        // As when signing, payloads longer than 256 bytes are hashed first.
+        if bytes.len() > 256 {
+            bytes = blake2_256(&bytes).to_vec();
+        }
+        Ok(Some(bytes))
+    }
+
+    /// Verify the signature of this extrinsic. The signer payload is rebuilt as described in
+    /// [`ExtrinsicDetails::signer_payload()`], and the given `verify` function is handed the
+    /// signature, the signer payload and the account ID of the signer, returning whether the
+    /// signature is valid. For [`crate::utils::MultiSignature`]s, this can be
+    /// `subxt_signer::verify_multi_signature`.
+    ///
+    /// Returns `false` if the extrinsic is not signed.
+    pub fn verify_signature<F>(
+        &self,
+        client_state: &ClientState<T>,
+        params: <T::ExtrinsicParams as ExtrinsicParams<T>>::Params,
+        verify: F,
+    ) -> Result<bool, Error>
+    where
+        T::AccountId: Decode,
+        T::Signature: Decode,
+        F: FnOnce(&T::Signature, &[u8], &T::AccountId) -> bool,
+    {
+        let (Some(account_id), Some(signature), Some(payload)) = (
+            self.signer_account_id()?,
+            self.signature::<T::Signature>()?,
+            self.signer_payload(client_state, params)?,
+        ) else {
+            return Ok(false);
+        };
+        Ok(verify(&signature, &payload, &account_id))
+    }
 }
 
 /// A Static Extrinsic found in a block coupled with it's details.
@@ -469,7 +624,9 @@ impl ExtrinsicPartTypeIds {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::SubstrateConfig;
+    use crate::client::RuntimeVersion;
+    use crate::config::{DefaultExtrinsicParamsBuilder, SubstrateConfig};
+    use crate::utils::{AccountId32, MultiAddress, MultiSignature, H256};
     use assert_matches::assert_matches;
     use codec::{Decode, Encode};
     use frame_metadata::v15::{CustomMetadata, OuterEnums};
@@ -477,7 +634,7 @@ mod tests {
         v15::{ExtrinsicMetadata, PalletCallMetadata, PalletMetadata, RuntimeMetadataV15},
         RuntimeMetadataPrefixed,
     };
-    use scale_info::{meta_type, TypeInfo};
+    use scale_info::{meta_type, MetaType, TypeInfo};
     use scale_value::Value;
 
     // Extrinsic needs to contain at least the generic type parameter "Call"
@@ -556,6 +713,19 @@ mod tests {
 
     /// Build fake metadata consisting the types needed to represent an extrinsic.
     fn metadata() -> Metadata {
+        metadata_with_signer_types(meta_type::<()>(), meta_type::<()>())
+    }
+
+    /// Like [`metadata()`], but with the address and signature types used by Substrate, so that
+    /// signed extrinsics can be decoded.
+    fn signed_metadata() -> Metadata {
+        metadata_with_signer_types(
+            meta_type::<MultiAddress<AccountId32, u32>>(),
+            meta_type::<MultiSignature>(),
+        )
+    }
+
+    fn metadata_with_signer_types(address_ty: MetaType, signature_ty: MetaType) -> Metadata {
         let pallets = vec![PalletMetadata {
             name: "Test",
             storage: None,
@@ -572,9 +742,9 @@ mod tests {
         let extrinsic = ExtrinsicMetadata {
             version: 4,
             signed_extensions: vec![],
-            address_ty: meta_type::<()>(),
+            address_ty,
             call_ty: meta_type::<RuntimeCall>(),
-            signature_ty: meta_type::<()>(),
+            signature_ty,
             extra_ty: meta_type::<()>(),
         };
 
@@ -749,4 +919,94 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn signer_and_signature_are_decoded() {
+        use crate::tx::signer::Signer;
+
+        struct TestSigner;
+        impl Signer<SubstrateConfig> for TestSigner {
+            fn account_id(&self) -> AccountId32 {
+                AccountId32([1; 32])
+            }
+            fn address(&self) -> MultiAddress<AccountId32, u32> {
+                MultiAddress::Id(self.account_id())
+            }
+            fn sign(&self, signer_payload: &[u8]) -> MultiSignature {
+                // Not a real signature, but lets us check what was signed.
+                let mut signature = [0; 64];
+                signature[..32].copy_from_slice(&blake2_256(signer_payload));
+                MultiSignature::Sr25519(signature)
+            }
+        }
+
+        let metadata = signed_metadata();
+        let ids = ExtrinsicPartTypeIds::new(&metadata).unwrap();
+        let client_state = ClientState::<SubstrateConfig> {
+            genesis_hash: H256::repeat_byte(2),
+            runtime_version: RuntimeVersion {
+                spec_version: 1,
+                transaction_version: 1,
+            },
+            metadata: metadata.clone(),
+        };
+
+        let tx = crate::dynamic::tx(
+            "Test",
+            "TestCall",
+            vec![
+                Value::u128(10),
+                Value::bool(true),
+                Value::string("SomeValue"),
+            ],
+        );
+        let partial_tx = crate::tx::create_partial_signed(
+            &tx,
+            &client_state,
+            DefaultExtrinsicParamsBuilder::new().build(),
+        )
+        .expect("Valid dynamic parameters are provided");
+        let tx_encoded = partial_tx.sign(&TestSigner);
+
+        let extrinsic = ExtrinsicDetails::<SubstrateConfig>::decode_from(
+            1,
+            tx_encoded.encoded(),
+            metadata,
+            ids,
+        )
+        .expect("Valid extrinsic");
+
+        assert!(extrinsic.is_signed());
+        assert_eq!(
+            extrinsic
+                .address::<MultiAddress<AccountId32, u32>>()
+                .unwrap(),
+            Some(TestSigner.address())
+        );
+        assert_eq!(
+            extrinsic.signer_account_id().unwrap(),
+            Some(TestSigner.account_id())
+        );
+        assert_eq!(
+            extrinsic.signature::<MultiSignature>().unwrap(),
+            Some(TestSigner.sign(&partial_tx.signer_payload()))
+        );
+
+        let signer_payload = extrinsic
+            .signer_payload(&client_state, DefaultExtrinsicParamsBuilder::new().build())
+            .unwrap();
+        assert_eq!(signer_payload, Some(partial_tx.signer_payload()));
+
+        let verify = |signature: &MultiSignature, payload: &[u8], account_id: &AccountId32| {
+            *signature == TestSigner.sign(payload) && *account_id == TestSigner.account_id()
+        };
+        let is_valid = extrinsic
+            .verify_signature(
+                &client_state,
+                DefaultExtrinsicParamsBuilder::new().build(),
+                verify,
+            )
+            .unwrap();
+        assert!(is_valid);
+    }
 }
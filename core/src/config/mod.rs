@@ -126,6 +126,18 @@ pub trait Header: Sized + Encode + Decode {
     fn hash(&self) -> <Self::Hasher as Hasher>::Output {
         Self::Hasher::hash_of(self)
     }
+
+    /// Return the hash of the parent block.
+    ///
+    /// By default, this decodes the hash from the start of the SCALE encoded header, which is
+    /// where Substrate based headers (including [`substrate::SubstrateHeader`]) keep it. Headers
+    /// which are laid out differently should override this.
+    fn parent_hash(&self) -> Result<<Self::Hasher as Hasher>::Output, codec::Error>
+    where
+        <Self::Hasher as Hasher>::Output: Decode,
+    {
+        Decode::decode(&mut &*self.encode())
+    }
 }
 
 cfg_substrate_compat! {
@@ -143,6 +155,13 @@ cfg_substrate_compat! {
             fn number(&self) -> Self::Number {
                 *self.number()
             }
+
+            fn parent_hash(&self) -> Result<<Self::Hasher as Hasher>::Output, codec::Error>
+            where
+                <Self::Hasher as Hasher>::Output: Decode,
+            {
+                Ok(*sp_runtime::traits::Header::parent_hash(self))
+            }
         }
 
         impl<T: sp_runtime::traits::Hash> Hasher for T {
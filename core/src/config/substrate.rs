@@ -297,6 +297,18 @@ impl From<U256> for NumberOrHex {
 mod test {
     use super::*;
 
+    #[test]
+    fn parent_hash_is_decoded_from_header() {
+        let header = SubstrateHeader::<u32, BlakeTwo256> {
+            parent_hash: H256::repeat_byte(7),
+            number: 10,
+            state_root: H256::repeat_byte(8),
+            extrinsics_root: H256::repeat_byte(9),
+            digest: Digest::default(),
+        };
+        assert_eq!(header.parent_hash(), Ok(H256::repeat_byte(7)));
+    }
+
     // Smoldot returns numeric block numbers in the header at the time of writing;
     // ensure we can deserialize them properly.
     #[test]
@@ -83,3 +83,11 @@ pub fn get<Addr: Address>(address: &Addr, metadata: &Metadata) -> Result<Addr::T
     )?;
     Ok(value)
 }
+
+/// Fetch the SS58 prefix that the chain uses for its addresses, from the `System::SS58Prefix`
+/// constant. This can be passed to [`crate::utils::AccountId32::to_ss58check_with_version`]
+/// to display addresses in the chain's own format.
+pub fn ss58_prefix(metadata: &Metadata) -> Result<u16, Error> {
+    let address = address::DefaultAddress::<u16>::new("System", "SS58Prefix");
+    get(&address, metadata)
+}
@@ -236,6 +236,10 @@ pub enum ExtrinsicParamsError {
     },
     /// A signed extension in use on some chain was not provided.
     UnknownSignedExtension(String),
+    /// The additional data of an extrinsic cannot be rebuilt from the parameters, and so
+    /// neither can the payload that was signed. This is the case when the extrinsic asks
+    /// for the metadata hash to be checked, for instance.
+    CannotRebuildAdditional(&'static str),
     /// Some custom error.
     Custom(Box<dyn CustomError>),
 }
@@ -251,6 +255,10 @@ impl Display for ExtrinsicParamsError {
                 f,
                 "The chain expects a signed extension with the name {e}, but we did not provide one"
             ),
+            ExtrinsicParamsError::CannotRebuildAdditional(e) => write!(
+                f,
+                "Cannot rebuild the additional data that the extrinsic was signed with: {e}"
+            ),
             ExtrinsicParamsError::Custom(e) => {
                 write!(f, "Error constructing extrinsic parameters: {e}")
             }
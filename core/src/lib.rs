@@ -50,6 +50,7 @@ pub mod ext {
     pub use scale_decode;
     pub use scale_encode;
     pub use scale_value;
+    pub use serde;
 
     cfg_substrate_compat! {
         pub use sp_runtime;
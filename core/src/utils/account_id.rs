@@ -15,6 +15,8 @@ use alloc::vec::Vec;
 use codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
+use super::ss58;
+
 /// A 32-byte cryptographic identifier. This is a simplified version of Substrate's
 /// `sp_core::crypto::AccountId32`. To obtain more functionality, convert this into
 /// that type.
@@ -52,15 +54,35 @@ impl From<[u8; 32]> for AccountId32 {
 }
 
 impl AccountId32 {
-    // Return the ss58-check string for this key. Adapted from `sp_core::crypto`. We need this to
-    // serialize our account appropriately but otherwise don't care.
+    // Return the ss58-check string for this key using the default substrate prefix. Adapted from
+    // `sp_core::crypto`.
     fn to_ss58check(&self) -> String {
         // For serializing to a string to obtain the account nonce, we use the default substrate
         // prefix (since we have no way to otherwise pick one). It doesn't really matter, since when
         // it's deserialized back in system_accountNextIndex, we ignore this (so long as it's valid).
-        const SUBSTRATE_SS58_PREFIX: u8 = 42;
-        // prefix <= 63 just take up one byte at the start:
-        let mut v = vec![SUBSTRATE_SS58_PREFIX];
+        self.to_ss58check_with_version(ss58::SUBSTRATE_PREFIX)
+            .expect("the substrate prefix is a valid prefix; qed")
+    }
+
+    /// Return the SS58 encoded string for this account, using the given network prefix. See
+    /// [`ss58::prefix_for_network`] to look up the prefix of a well known network.
+    ///
+    /// Returns an error if the prefix is greater than [`ss58::MAX_PREFIX`], which is the
+    /// highest prefix that can be encoded.
+    pub fn to_ss58check_with_version(&self, prefix: u16) -> Result<String, InvalidSs58Prefix> {
+        if prefix > ss58::MAX_PREFIX {
+            return Err(InvalidSs58Prefix(prefix));
+        }
+        let mut v = match prefix {
+            // prefix <= 63 just take up one byte at the start:
+            0..=63 => vec![prefix as u8],
+            // larger prefixes are split across two bytes:
+            _ => {
+                let first = ((prefix & 0b0000_0000_1111_1100) as u8) >> 2;
+                let second = ((prefix >> 8) as u8) | ((prefix & 0b0000_0000_0000_0011) as u8) << 6;
+                vec![first | 0b0100_0000, second]
+            }
+        };
         // then push the account ID bytes.
         v.extend(self.0);
         // then push a 2 byte checksum of what we have so far.
@@ -68,13 +90,19 @@ impl AccountId32 {
         v.extend(&r[0..2]);
         // then encode to base58.
         use base58::ToBase58;
-        v.to_base58()
+        Ok(v.to_base58())
     }
 
     // This isn't strictly needed, but to give our AccountId32 a little more usefulness, we also
     // implement the logic needed to decode an AccountId32 from an SS58 encoded string. This is exposed
-    // via a `FromStr` impl.
+    // via a `FromStr` impl, which accepts any prefix.
     fn from_ss58check(s: &str) -> Result<Self, FromSs58Error> {
+        Self::from_ss58check_with_version(s).map(|(account, _)| account)
+    }
+
+    /// Decode an SS58 encoded string into an account, returning the account along with
+    /// the network prefix that it was encoded with.
+    pub fn from_ss58check_with_version(s: &str) -> Result<(Self, u16), FromSs58Error> {
         const CHECKSUM_LEN: usize = 2;
         let body_len = 32;
 
@@ -83,9 +111,14 @@ impl AccountId32 {
         if data.len() < 2 {
             return Err(FromSs58Error::BadLength);
         }
-        let prefix_len = match data[0] {
-            0..=63 => 1,
-            64..=127 => 2,
+        let (prefix_len, prefix) = match data[0] {
+            0..=63 => (1, data[0] as u16),
+            64..=127 => {
+                // Undo the two byte encoding done in `to_ss58check_with_version`:
+                let lower = (data[0] << 2) | (data[1] >> 6);
+                let upper = data[1] & 0b0011_1111;
+                (2, (lower as u16) | ((upper as u16) << 8))
+            }
             _ => return Err(FromSs58Error::InvalidPrefix),
         };
         if data.len() != prefix_len + body_len + CHECKSUM_LEN {
@@ -101,18 +134,33 @@ impl AccountId32 {
         let result = data[prefix_len..body_len + prefix_len]
             .try_into()
             .map_err(|_| FromSs58Error::BadLength)?;
-        Ok(AccountId32(result))
+        Ok((AccountId32(result), prefix))
+    }
+
+    /// Decode an SS58 encoded string into an account, returning an error if it was not
+    /// encoded with the expected network prefix.
+    pub fn from_ss58check_with_expected_prefix(
+        s: &str,
+        expected: u16,
+    ) -> Result<Self, FromSs58Error> {
+        let (account, found) = Self::from_ss58check_with_version(s)?;
+        if found != expected {
+            return Err(FromSs58Error::UnexpectedPrefix { expected, found });
+        }
+        Ok(account)
     }
 }
 
 /// An error obtained from trying to interpret an SS58 encoded string into an AccountId32
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
 #[allow(missing_docs)]
+#[non_exhaustive]
 pub enum FromSs58Error {
     BadBase58,
     BadLength,
     InvalidChecksum,
     InvalidPrefix,
+    UnexpectedPrefix { expected: u16, found: u16 },
 }
 
 impl Display for FromSs58Error {
@@ -122,6 +170,10 @@ impl Display for FromSs58Error {
             FromSs58Error::BadLength => write!(f, "Length is bad"),
             FromSs58Error::InvalidChecksum => write!(f, "Invalid checksum"),
             FromSs58Error::InvalidPrefix => write!(f, "Invalid SS58 prefix byte."),
+            FromSs58Error::UnexpectedPrefix { expected, found } => write!(
+                f,
+                "Expected an address with SS58 prefix {expected}, but got {found}"
+            ),
         }
     }
 }
@@ -129,6 +181,25 @@ impl Display for FromSs58Error {
 #[cfg(feature = "std")]
 impl std::error::Error for FromSs58Error {}
 
+/// An error obtained from trying to SS58 encode an AccountId32 with a prefix greater than
+/// [`ss58::MAX_PREFIX`]. This holds the prefix that was given.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct InvalidSs58Prefix(pub u16);
+
+impl Display for InvalidSs58Prefix {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "SS58 prefix {} is greater than the maximum of {}",
+            self.0,
+            ss58::MAX_PREFIX
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidSs58Prefix {}
+
 // We do this just to get a checksum to help verify the validity of the address in to_ss58check
 fn ss58hash(data: &[u8]) -> Vec<u8> {
     use blake2::{Blake2b512, Digest};
@@ -231,4 +302,60 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn ss58_with_version_is_compatible_with_substrate_impl() {
+        use sp_core::crypto::Ss58AddressFormat;
+
+        let substrate_account = AccountKeyring::Alice.to_account_id();
+        let local_account = AccountId32(substrate_account.clone().into());
+
+        // Cover one and two byte prefixes:
+        for prefix in [0, 2, 42, 63, 64, 1284, ss58::MAX_PREFIX] {
+            let substrate_ss58 =
+                substrate_account.to_ss58check_with_version(Ss58AddressFormat::custom(prefix));
+            assert_eq!(
+                substrate_ss58,
+                local_account.to_ss58check_with_version(prefix).unwrap()
+            );
+            assert_eq!(
+                AccountId32::from_ss58check_with_version(&substrate_ss58).unwrap(),
+                (local_account.clone(), prefix)
+            );
+        }
+    }
+
+    #[test]
+    fn ss58_prefixes_out_of_range_are_rejected() {
+        let account = AccountId32([1; 32]);
+        assert!(account.to_ss58check_with_version(ss58::MAX_PREFIX).is_ok());
+        assert_eq!(
+            account.to_ss58check_with_version(ss58::MAX_PREFIX + 1),
+            Err(InvalidSs58Prefix(ss58::MAX_PREFIX + 1))
+        );
+        assert_eq!(
+            account.to_ss58check_with_version(u16::MAX),
+            Err(InvalidSs58Prefix(u16::MAX))
+        );
+    }
+
+    #[test]
+    fn ss58_expected_prefix_is_checked() {
+        let account = AccountId32(AccountKeyring::Bob.to_account_id().into());
+        let polkadot = account.to_ss58check_with_version(0).unwrap();
+
+        assert_eq!(
+            AccountId32::from_ss58check_with_expected_prefix(&polkadot, 0),
+            Ok(account.clone())
+        );
+        assert_eq!(
+            AccountId32::from_ss58check_with_expected_prefix(&polkadot, 2),
+            Err(FromSs58Error::UnexpectedPrefix {
+                expected: 2,
+                found: 0
+            })
+        );
+        // FromStr accepts any prefix:
+        assert_eq!(polkadot.parse::<AccountId32>(), Ok(account));
+    }
 }
@@ -189,6 +189,20 @@ impl<Store, Order> scale_encode::EncodeAsType for DecodedBits<Store, Order> {
     }
 }
 
+/// Serialized as a sequence of booleans, one for each bit.
+impl<Store, Order> serde::Serialize for DecodedBits<Store, Order> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.bits.iter())
+    }
+}
+
+impl<'de, Store, Order> serde::Deserialize<'de> for DecodedBits<Store, Order> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bits = <Vec<bool> as serde::Deserialize>::deserialize(deserializer)?;
+        Ok(bits.into_iter().collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
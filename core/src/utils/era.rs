@@ -70,6 +70,55 @@ impl Era {
             phase: quantized_phase,
         }
     }
+
+    /// Create an "immortal" transaction.
+    pub fn immortal() -> Self {
+        Self::Immortal
+    }
+
+    /// `true` if this is an immortal transaction.
+    pub fn is_immortal(&self) -> bool {
+        matches!(self, Self::Immortal)
+    }
+
+    /// The number of blocks that a mortal transaction is valid for, or `0` if it is immortal.
+    pub fn period(&self) -> u64 {
+        match self {
+            Self::Immortal => 0,
+            Self::Mortal { period, .. } => *period,
+        }
+    }
+
+    /// The phase in the period that a mortal transaction's lifetime begins, or `0` if it is
+    /// immortal.
+    pub fn phase(&self) -> u64 {
+        match self {
+            Self::Immortal => 0,
+            Self::Mortal { phase, .. } => *phase,
+        }
+    }
+
+    /// Get the block number of the start of the era whose properties this object describes
+    /// that `current` belongs to. This is the "birth" block whose hash is signed by a
+    /// transaction that is valid at block `current`.
+    pub fn birth(&self, current: u64) -> u64 {
+        match self {
+            Self::Immortal => 0,
+            Self::Mortal { period, phase } => {
+                (current.max(*phase) - phase) / period * period + phase
+            }
+        }
+    }
+
+    /// Get the block number of the first block at which the era has ended, given some block
+    /// `current` in that era. A transaction that is valid at block `current` is no longer
+    /// valid from this block onwards.
+    pub fn death(&self, current: u64) -> u64 {
+        match self {
+            Self::Immortal => u64::MAX,
+            Self::Mortal { period, .. } => self.birth(current).saturating_add(*period),
+        }
+    }
 }
 
 // Both copied from `sp_runtime::generic::Era`; this is the wire interface and so
@@ -105,3 +154,38 @@ impl codec::Decode for Era {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use codec::{Decode, Encode};
+
+    #[test]
+    fn birth_and_death_of_mortal_era() {
+        let era = Era::mortal(64, 1000);
+        assert_eq!(era, Era::decode(&mut &*era.encode()).unwrap());
+        assert_eq!(era.period(), 64);
+        assert_eq!(era.phase(), 1000 % 64);
+
+        assert_eq!(era.birth(1000), 1000);
+        assert_eq!(era.death(1000), 1064);
+        assert_eq!(era.birth(1063), 1000);
+        assert_eq!(era.death(1063), 1064);
+        assert_eq!(era.birth(1064), 1064);
+    }
+
+    #[test]
+    fn death_of_mortal_era_saturates() {
+        let era = Era::mortal(64, u64::MAX);
+        assert_eq!(era.death(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn immortal_era_never_dies() {
+        let era = Era::immortal();
+        assert!(era.is_immortal());
+        assert_eq!(era.period(), 0);
+        assert_eq!(era.birth(1000), 0);
+        assert_eq!(era.death(1000), u64::MAX);
+    }
+}
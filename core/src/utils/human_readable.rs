@@ -0,0 +1,344 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Helpers for serializing values in a human readable way, for use via
+//! `#[serde(with = "...")]`. These are used by code generated with
+//! serde support enabled, which is where they will typically be seen.
+
+/// Serialize integers as strings, so that they don't lose precision in formats
+/// like JSON, where numbers are often parsed as `f64`. Deserializing accepts
+/// either a string or a number.
+///
+/// This works for `u64`, `u128`, `i64` and `i128`, as well as any `Option`s or
+/// `Vec`s of these, in which case each integer is serialized as a string.
+pub mod int_as_string {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use core::fmt::Display;
+    use core::marker::PhantomData;
+    use core::str::FromStr;
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serialize the integers in the value as strings.
+    pub fn serialize<T: IntAsString, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize_as_string(serializer)
+    }
+
+    /// Deserialize the integers in the value from either strings or numbers.
+    pub fn deserialize<'de, T: IntAsString, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        T::deserialize_from_string(deserializer)
+    }
+
+    /// A value containing integers which can be serialized as strings.
+    pub trait IntAsString: Sized {
+        /// Serialize the value, with any integers in it as strings.
+        fn serialize_as_string<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+        /// Deserialize the value, with any integers in it as either strings or numbers.
+        fn deserialize_from_string<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error>;
+    }
+
+    macro_rules! impl_int_as_string {
+        ($($int:ty),*) => {$(
+            impl IntAsString for $int {
+                fn serialize_as_string<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+                fn deserialize_from_string<'de, D: Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Self, D::Error> {
+                    deserializer.deserialize_any(IntVisitor(PhantomData))
+                }
+            }
+        )*};
+    }
+    impl_int_as_string!(u64, u128, i64, i128);
+
+    impl<T: IntAsString> IntAsString for Option<T> {
+        fn serialize_as_string<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.as_ref().map(AsString).serialize(serializer)
+        }
+        fn deserialize_from_string<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            let value = Option::<AsString<T>>::deserialize(deserializer)?;
+            Ok(value.map(|v| v.0))
+        }
+    }
+
+    impl<T: IntAsString> IntAsString for Vec<T> {
+        fn serialize_as_string<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter().map(AsString))
+        }
+        fn deserialize_from_string<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            let values = Vec::<AsString<T>>::deserialize(deserializer)?;
+            Ok(values.into_iter().map(|v| v.0).collect())
+        }
+    }
+
+    // Serializes and deserializes the value inside it via `IntAsString`.
+    struct AsString<T>(T);
+
+    impl<T: IntAsString> Serialize for AsString<&T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.serialize_as_string(serializer)
+        }
+    }
+
+    impl<'de, T: IntAsString> Deserialize<'de> for AsString<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            T::deserialize_from_string(deserializer).map(AsString)
+        }
+    }
+
+    struct IntVisitor<T>(PhantomData<T>);
+
+    impl<'de, T> de::Visitor<'de> for IntVisitor<T>
+    where
+        T: FromStr + Display + TryFrom<u128> + TryFrom<i128>,
+    {
+        type Value = T;
+
+        fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            f.write_str("an integer, or a string containing an integer")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+            v.parse()
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+        }
+
+        fn visit_string<E: de::Error>(self, v: String) -> Result<T, E> {
+            self.visit_str(&v)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<T, E> {
+            self.visit_u128(v as u128)
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<T, E> {
+            self.visit_i128(v as i128)
+        }
+
+        fn visit_u128<E: de::Error>(self, v: u128) -> Result<T, E> {
+            T::try_from(v).map_err(|_| E::custom("integer out of range"))
+        }
+
+        fn visit_i128<E: de::Error>(self, v: i128) -> Result<T, E> {
+            T::try_from(v).map_err(|_| E::custom("integer out of range"))
+        }
+    }
+}
+
+/// Serialize bytes as a `0x` prefixed hex string. This works for `Vec<u8>` and
+/// fixed size byte arrays of any length, as well as any `Option`s or `Vec`s of
+/// these, in which case each set of bytes is serialized as a hex string.
+pub mod bytes_as_hex {
+    use alloc::vec::Vec;
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serialize the bytes in the value as hex strings.
+    pub fn serialize<T: BytesAsHex, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize_as_hex(serializer)
+    }
+
+    /// Deserialize the bytes in the value from hex strings. If the target is a fixed
+    /// size array, the number of bytes must match its length.
+    pub fn deserialize<'de, T: BytesAsHex, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        T::deserialize_from_hex(deserializer)
+    }
+
+    /// A value containing bytes which can be serialized as hex strings.
+    pub trait BytesAsHex: Sized {
+        /// Serialize the value, with any bytes in it as hex strings.
+        fn serialize_as_hex<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+        /// Deserialize the value, with any bytes in it as hex strings.
+        fn deserialize_from_hex<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error>;
+    }
+
+    impl BytesAsHex for Vec<u8> {
+        fn serialize_as_hex<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            impl_serde::serialize::serialize(self, serializer)
+        }
+        fn deserialize_from_hex<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            impl_serde::serialize::deserialize(deserializer)
+        }
+    }
+
+    impl<const N: usize> BytesAsHex for [u8; N] {
+        fn serialize_as_hex<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            impl_serde::serialize::serialize(self, serializer)
+        }
+        fn deserialize_from_hex<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            let bytes = impl_serde::serialize::deserialize(deserializer)?;
+            let len = bytes.len();
+            bytes
+                .try_into()
+                .map_err(|_| de::Error::invalid_length(len, &"a hex string of the expected length"))
+        }
+    }
+
+    impl<T: BytesAsHex> BytesAsHex for Option<T> {
+        fn serialize_as_hex<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.as_ref().map(AsHex).serialize(serializer)
+        }
+        fn deserialize_from_hex<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            let value = Option::<AsHex<T>>::deserialize(deserializer)?;
+            Ok(value.map(|v| v.0))
+        }
+    }
+
+    impl<T: BytesAsHex> BytesAsHex for Vec<T> {
+        fn serialize_as_hex<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter().map(AsHex))
+        }
+        fn deserialize_from_hex<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            let values = Vec::<AsHex<T>>::deserialize(deserializer)?;
+            Ok(values.into_iter().map(|v| v.0).collect())
+        }
+    }
+
+    // Serializes and deserializes the value inside it via `BytesAsHex`.
+    struct AsHex<T>(T);
+
+    impl<T: BytesAsHex> Serialize for AsHex<&T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.serialize_as_hex(serializer)
+        }
+    }
+
+    impl<'de, T: BytesAsHex> Deserialize<'de> for AsHex<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            T::deserialize_from_hex(deserializer).map(AsHex)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Foo {
+        #[serde(with = "int_as_string")]
+        balance: u128,
+        #[serde(with = "int_as_string")]
+        delta: i64,
+        #[serde(with = "bytes_as_hex")]
+        data: Vec<u8>,
+        #[serde(with = "bytes_as_hex")]
+        signature: [u8; 4],
+    }
+
+    #[test]
+    fn roundtrips_human_readable_values() {
+        let foo = Foo {
+            balance: u128::MAX,
+            delta: -5,
+            data: vec![1, 2, 3],
+            signature: [0xde, 0xad, 0xbe, 0xef],
+        };
+
+        let json = serde_json::to_value(&foo).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "balance": "340282366920938463463374607431768211455",
+                "delta": "-5",
+                "data": "0x010203",
+                "signature": "0xdeadbeef"
+            })
+        );
+        assert_eq!(serde_json::from_value::<Foo>(json).unwrap(), foo);
+    }
+
+    #[test]
+    fn accepts_numbers_and_checks_array_lengths() {
+        let foo: Foo = serde_json::from_value(serde_json::json!({
+            "balance": 100,
+            "delta": -1,
+            "data": "0x",
+            "signature": "0x01020304"
+        }))
+        .unwrap();
+        assert_eq!(foo.balance, 100);
+        assert_eq!(foo.delta, -1);
+
+        let err = serde_json::from_value::<Foo>(serde_json::json!({
+            "balance": "1",
+            "delta": "1",
+            "data": "0x",
+            "signature": "0x0102"
+        }));
+        assert!(err.is_err());
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Nested {
+        #[serde(with = "int_as_string")]
+        tip: Option<u128>,
+        #[serde(with = "int_as_string")]
+        weights: Vec<u64>,
+        #[serde(with = "bytes_as_hex")]
+        signature: Option<[u8; 64]>,
+        #[serde(with = "bytes_as_hex")]
+        remarks: Vec<Vec<u8>>,
+    }
+
+    #[test]
+    fn roundtrips_nested_values() {
+        let nested = Nested {
+            tip: Some(u128::MAX),
+            weights: vec![1, u64::MAX],
+            signature: Some([0xab; 64]),
+            remarks: vec![vec![1], vec![]],
+        };
+
+        let json = serde_json::to_value(&nested).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "tip": "340282366920938463463374607431768211455",
+                "weights": ["1", "18446744073709551615"],
+                "signature": format!("0x{}", "ab".repeat(64)),
+                "remarks": ["0x01", "0x"]
+            })
+        );
+        assert_eq!(serde_json::from_value::<Nested>(json).unwrap(), nested);
+
+        let empty: Nested = serde_json::from_value(serde_json::json!({
+            "tip": null,
+            "weights": [],
+            "signature": null,
+            "remarks": []
+        }))
+        .unwrap();
+        assert_eq!(empty.tip, None);
+        assert_eq!(empty.signature, None);
+    }
+}
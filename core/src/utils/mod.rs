@@ -8,10 +8,13 @@ mod account_id;
 mod account_id20;
 pub mod bits;
 mod era;
+pub mod human_readable;
 mod multi_address;
 mod multi_signature;
+pub mod ss58;
 mod static_type;
 mod unchecked_extrinsic;
+mod unified_address;
 mod wrapper_opaque;
 
 use alloc::borrow::ToOwned;
@@ -21,7 +24,7 @@ use alloc::vec::Vec;
 use codec::{Compact, Decode, Encode};
 use derive_where::derive_where;
 
-pub use account_id::AccountId32;
+pub use account_id::{AccountId32, FromSs58Error, InvalidSs58Prefix};
 pub use account_id20::AccountId20;
 pub use era::Era;
 pub use multi_address::MultiAddress;
@@ -29,6 +32,7 @@ pub use multi_signature::MultiSignature;
 pub use primitive_types::{H160, H256, H512};
 pub use static_type::Static;
 pub use unchecked_extrinsic::UncheckedExtrinsic;
+pub use unified_address::{FromUnifiedAddress, UnifiedAddress, UnifiedAddressError};
 pub use wrapper_opaque::WrapperKeepOpaque;
 
 /// Wraps an already encoded byte vector, prevents being encoded as a raw byte vector as part of
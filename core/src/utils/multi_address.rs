@@ -24,6 +24,8 @@ use codec::{Decode, Encode};
     scale_encode::EncodeAsType,
     scale_decode::DecodeAsType,
     scale_info::TypeInfo,
+    serde::Serialize,
+    serde::Deserialize,
 )]
 pub enum MultiAddress<AccountId, AccountIndex> {
     /// It's an account ID (pubkey).
@@ -31,11 +33,11 @@ pub enum MultiAddress<AccountId, AccountIndex> {
     /// It's an account index.
     Index(#[codec(compact)] AccountIndex),
     /// It's some arbitrary raw bytes.
-    Raw(Vec<u8>),
+    Raw(#[serde(with = "super::human_readable::bytes_as_hex")] Vec<u8>),
     /// It's a 32 byte representation.
-    Address32([u8; 32]),
+    Address32(#[serde(with = "super::human_readable::bytes_as_hex")] [u8; 32]),
     /// Its a 20 byte representation.
-    Address20([u8; 20]),
+    Address20(#[serde(with = "super::human_readable::bytes_as_hex")] [u8; 20]),
 }
 
 impl<AccountId, AccountIndex> From<AccountId> for MultiAddress<AccountId, AccountIndex> {
@@ -0,0 +1,88 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! A registry of well known SS58 address formats, mapping network names to the prefix
+//! that their addresses are encoded with. This is a subset of the
+//! [SS58 registry](https://github.com/paritytech/ss58-registry); chains that are not listed
+//! here can still be used by providing their prefix directly, or by reading it from the
+//! `System::SS58Prefix` constant in their metadata.
+//!
+//! ```rust
+//! use subxt_core::utils::{ss58, AccountId32};
+//!
+//! let prefix = ss58::prefix_for_network("polkadot").unwrap();
+//! assert_eq!(prefix, 0);
+//! assert_eq!(ss58::network_for_prefix(prefix), Some("polkadot"));
+//!
+//! let account = AccountId32([0; 32]);
+//! let address = account.to_ss58check_with_version(prefix).unwrap();
+//! let decoded = AccountId32::from_ss58check_with_expected_prefix(&address, prefix).unwrap();
+//! assert_eq!(account, decoded);
+//! ```
+
+/// The generic Substrate prefix, which is used by default when no other prefix is given.
+pub const SUBSTRATE_PREFIX: u16 = 42;
+
+/// The highest prefix that can be encoded into an SS58 address.
+pub const MAX_PREFIX: u16 = 16_383;
+
+/// Well known network names and their SS58 prefixes.
+pub const KNOWN_NETWORKS: &[(&str, u16)] = &[
+    ("polkadot", 0),
+    ("kusama", 2),
+    ("astar", 5),
+    ("bifrost", 6),
+    ("edgeware", 7),
+    ("karura", 8),
+    ("acala", 10),
+    ("laminar", 11),
+    ("polymesh", 12),
+    ("kulupu", 16),
+    ("darwinia", 18),
+    ("stafi", 20),
+    ("robonomics", 32),
+    ("centrifuge", 36),
+    ("nodle", 37),
+    ("kilt", 38),
+    ("substrate", SUBSTRATE_PREFIX),
+    ("hydradx", 63),
+    ("moonbeam", 1284),
+    ("moonriver", 1285),
+];
+
+/// Look up the SS58 prefix of a network by name (for example `"polkadot"`). Names are
+/// matched case insensitively.
+pub fn prefix_for_network(network: &str) -> Option<u16> {
+    KNOWN_NETWORKS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(network))
+        .map(|(_, prefix)| *prefix)
+}
+
+/// Look up the name of the network that uses the given SS58 prefix.
+pub fn network_for_prefix(prefix: u16) -> Option<&'static str> {
+    KNOWN_NETWORKS
+        .iter()
+        .find(|(_, p)| *p == prefix)
+        .map(|(name, _)| *name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn known_networks_are_unique_and_valid() {
+        for (i, (name, prefix)) in KNOWN_NETWORKS.iter().enumerate() {
+            assert!(*prefix <= MAX_PREFIX);
+            assert_eq!(prefix_for_network(name), Some(*prefix));
+            assert_eq!(network_for_prefix(*prefix), Some(*name));
+            assert!(KNOWN_NETWORKS[i + 1..]
+                .iter()
+                .all(|(n, p)| n != name && p != prefix));
+        }
+        assert_eq!(prefix_for_network("Kusama"), Some(2));
+        assert_eq!(prefix_for_network("unknown"), None);
+    }
+}
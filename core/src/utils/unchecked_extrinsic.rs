@@ -112,6 +112,22 @@ impl<Address, Call, Signature, Extra> IntoVisitor
     }
 }
 
+impl<Address, Call, Signature, Extra> serde::Serialize
+    for UncheckedExtrinsic<Address, Call, Signature, Extra>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        impl_serde::serialize::serialize(self.bytes(), serializer)
+    }
+}
+
+impl<'de, Address, Call, Signature, Extra> serde::Deserialize<'de>
+    for UncheckedExtrinsic<Address, Call, Signature, Extra>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        super::human_readable::bytes_as_hex::deserialize(deserializer).map(Self::new)
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -0,0 +1,342 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! A [`UnifiedAddress`] can be parsed from any of the ways that an account is commonly written
+//! down: an SS58 string, a `0x` prefixed hex encoded 32 byte account ID, or a 20 byte Ethereum
+//! address (as used by Frontier based chains). It can then be checked against the account ID type
+//! of a chain and turned into a [`MultiAddress`] or the chain's [`Config::Address`].
+//!
+//! ```rust
+//! use subxt_core::config::PolkadotConfig;
+//! use subxt_core::utils::{MultiAddress, UnifiedAddress, UnifiedAddressError};
+//!
+//! let address: UnifiedAddress = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+//!     .parse()
+//!     .unwrap();
+//! assert_eq!(address.ss58_prefix(), Some(42));
+//!
+//! // Turn it into an address for a chain using 32 byte account IDs:
+//! let address = address.into_address::<PolkadotConfig>().unwrap();
+//! assert!(matches!(address, MultiAddress::Id(_)));
+//!
+//! // Ethereum style addresses are rejected for such chains:
+//! let address: UnifiedAddress = "0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac"
+//!     .parse()
+//!     .unwrap();
+//! assert_eq!(
+//!     address.into_address::<PolkadotConfig>(),
+//!     Err(UnifiedAddressError::UnexpectedLength { expected: 32, found: 20 })
+//! );
+//! ```
+
+use core::fmt::Display;
+use core::str::FromStr;
+
+use alloc::vec::Vec;
+
+use super::{AccountId20, AccountId32, FromSs58Error, MultiAddress};
+use crate::config::Config;
+
+/// An account, parsed from an SS58 string or from hex.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnifiedAddress {
+    /// A 32 byte account ID which was SS58 encoded with the given prefix.
+    Ss58 {
+        /// The account ID.
+        account_id: AccountId32,
+        /// The SS58 prefix that the address was encoded with. This is never greater than
+        /// [`super::ss58::MAX_PREFIX`] when parsed. If it is, the address can't be SS58
+        /// encoded, and so it is displayed as hex instead.
+        prefix: u16,
+    },
+    /// A 32 byte account ID which was hex encoded.
+    Hex(AccountId32),
+    /// A 20 byte Ethereum address.
+    H160(AccountId20),
+}
+
+impl UnifiedAddress {
+    /// The SS58 prefix that this address was encoded with, if it was SS58 encoded.
+    pub fn ss58_prefix(&self) -> Option<u16> {
+        match self {
+            UnifiedAddress::Ss58 { prefix, .. } => Some(*prefix),
+            _ => None,
+        }
+    }
+
+    /// The raw bytes of the account ID.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            UnifiedAddress::Ss58 { account_id, .. } | UnifiedAddress::Hex(account_id) => {
+                account_id.as_ref()
+            }
+            UnifiedAddress::H160(account_id) => account_id.as_ref(),
+        }
+    }
+
+    /// Convert this into an account ID of the given type, returning an error if it is not
+    /// the right kind of account ID.
+    pub fn into_account_id<AccountId: FromUnifiedAddress>(
+        self,
+    ) -> Result<AccountId, UnifiedAddressError> {
+        AccountId::from_unified_address(self)
+    }
+
+    /// Convert this into a [`MultiAddress::Id`] with the given account ID type, returning an
+    /// error if it is not the right kind of account ID.
+    pub fn into_multi_address<AccountId: FromUnifiedAddress, AccountIndex>(
+        self,
+    ) -> Result<MultiAddress<AccountId, AccountIndex>, UnifiedAddressError> {
+        self.into_account_id().map(MultiAddress::Id)
+    }
+
+    /// Convert this into an address for the chain described by the given [`Config`], returning
+    /// an error if it is not the kind of account ID that the chain uses.
+    pub fn into_address<T: Config>(self) -> Result<T::Address, UnifiedAddressError>
+    where
+        T::AccountId: FromUnifiedAddress,
+    {
+        self.into_account_id::<T::AccountId>().map(Into::into)
+    }
+}
+
+impl FromStr for UnifiedAddress {
+    type Err = UnifiedAddressError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let Some(hex) = s.strip_prefix("0x") else {
+            let (account_id, prefix) =
+                AccountId32::from_ss58check_with_version(s).map_err(UnifiedAddressError::Ss58)?;
+            return Ok(UnifiedAddress::Ss58 { account_id, prefix });
+        };
+
+        let bytes = hex::decode(hex).map_err(|_| UnifiedAddressError::InvalidHex)?;
+        match bytes.len() {
+            32 => Ok(UnifiedAddress::Hex(AccountId32(to_array(bytes)))),
+            20 => {
+                let account_id = AccountId20(to_array(bytes));
+                // Mixed case addresses carry an EIP-55 checksum, which we check. All lower
+                // or upper case addresses have no checksum.
+                let is_mixed_case = hex.chars().any(|c| c.is_ascii_lowercase())
+                    && hex.chars().any(|c| c.is_ascii_uppercase());
+                if is_mixed_case && account_id.checksum()[2..] != *hex {
+                    return Err(UnifiedAddressError::InvalidChecksum);
+                }
+                Ok(UnifiedAddress::H160(account_id))
+            }
+            len => Err(UnifiedAddressError::InvalidHexLength(len)),
+        }
+    }
+}
+
+impl Display for UnifiedAddress {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            UnifiedAddress::Ss58 { account_id, prefix } => {
+                match account_id.to_ss58check_with_version(*prefix) {
+                    Ok(ss58) => write!(f, "{ss58}"),
+                    Err(_) => write!(f, "0x{}", hex::encode(account_id.0)),
+                }
+            }
+            UnifiedAddress::Hex(account_id) => write!(f, "0x{}", hex::encode(account_id.0)),
+            UnifiedAddress::H160(account_id) => write!(f, "{account_id}"),
+        }
+    }
+}
+
+impl From<AccountId32> for UnifiedAddress {
+    fn from(account_id: AccountId32) -> Self {
+        UnifiedAddress::Hex(account_id)
+    }
+}
+
+impl From<AccountId20> for UnifiedAddress {
+    fn from(account_id: AccountId20) -> Self {
+        UnifiedAddress::H160(account_id)
+    }
+}
+
+/// Account ID types which can be obtained from a [`UnifiedAddress`].
+pub trait FromUnifiedAddress: Sized {
+    /// Convert the given address into this account ID type, returning an error if it is
+    /// not the right kind of account ID.
+    fn from_unified_address(address: UnifiedAddress) -> Result<Self, UnifiedAddressError>;
+}
+
+impl FromUnifiedAddress for AccountId32 {
+    fn from_unified_address(address: UnifiedAddress) -> Result<Self, UnifiedAddressError> {
+        match address {
+            UnifiedAddress::Ss58 { account_id, .. } | UnifiedAddress::Hex(account_id) => {
+                Ok(account_id)
+            }
+            UnifiedAddress::H160(_) => Err(UnifiedAddressError::UnexpectedLength {
+                expected: 32,
+                found: 20,
+            }),
+        }
+    }
+}
+
+impl FromUnifiedAddress for AccountId20 {
+    fn from_unified_address(address: UnifiedAddress) -> Result<Self, UnifiedAddressError> {
+        match address {
+            UnifiedAddress::H160(account_id) => Ok(account_id),
+            // SS58 is never used to encode 20 byte accounts, so this is more likely to be
+            // a mistake than the hex case:
+            UnifiedAddress::Ss58 { .. } => Err(UnifiedAddressError::UnexpectedSs58),
+            UnifiedAddress::Hex(_) => Err(UnifiedAddressError::UnexpectedLength {
+                expected: 20,
+                found: 32,
+            }),
+        }
+    }
+}
+
+/// An error obtained from trying to parse a [`UnifiedAddress`], or from trying to convert one
+/// into a specific account ID type.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum UnifiedAddressError {
+    /// The address looked like SS58 (it had no `0x` prefix), but could not be decoded.
+    Ss58(FromSs58Error),
+    /// The address had a `0x` prefix, but was not valid hex.
+    InvalidHex,
+    /// The address was hex, but was neither 32 nor 20 bytes long.
+    InvalidHexLength(usize),
+    /// The address was a mixed case Ethereum address with an invalid EIP-55 checksum.
+    InvalidChecksum,
+    /// The address was for an account ID of a different length than the one expected.
+    UnexpectedLength {
+        /// The expected account ID length.
+        expected: usize,
+        /// The account ID length that was given.
+        found: usize,
+    },
+    /// The address was SS58 encoded, but 20 byte Ethereum addresses were expected.
+    UnexpectedSs58,
+}
+
+impl Display for UnifiedAddressError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            UnifiedAddressError::Ss58(e) => write!(f, "Invalid SS58 address: {e}"),
+            UnifiedAddressError::InvalidHex => write!(f, "Invalid hex encoded address"),
+            UnifiedAddressError::InvalidHexLength(len) => write!(
+                f,
+                "Hex encoded address is {len} bytes long, but expected 32 or 20 bytes"
+            ),
+            UnifiedAddressError::InvalidChecksum => {
+                write!(f, "Invalid EIP-55 checksum in Ethereum address")
+            }
+            UnifiedAddressError::UnexpectedLength { expected, found } => write!(
+                f,
+                "Expected a {expected} byte account ID, but got a {found} byte one"
+            ),
+            UnifiedAddressError::UnexpectedSs58 => write!(
+                f,
+                "Expected a 20 byte Ethereum address, but got an SS58 address"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnifiedAddressError {}
+
+fn to_array<const N: usize>(bytes: Vec<u8>) -> [u8; N] {
+    bytes.try_into().expect("length checked above; qed")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::format;
+    use alloc::string::ToString;
+    use sp_keyring::AccountKeyring;
+
+    #[test]
+    fn parses_ss58_and_hex_accounts() {
+        let alice = AccountId32(AccountKeyring::Alice.to_raw_public());
+
+        let ss58 = alice.to_ss58check_with_version(0).unwrap();
+        let address: UnifiedAddress = ss58.parse().unwrap();
+        assert_eq!(
+            address,
+            UnifiedAddress::Ss58 {
+                account_id: alice.clone(),
+                prefix: 0
+            }
+        );
+        assert_eq!(address.to_string(), ss58);
+
+        let hex = format!("0x{}", hex::encode(alice.0));
+        let address: UnifiedAddress = hex.parse().unwrap();
+        assert_eq!(address, UnifiedAddress::Hex(alice.clone()));
+        assert_eq!(address.to_string(), hex);
+
+        assert_eq!(address.into_account_id::<AccountId32>(), Ok(alice));
+    }
+
+    #[test]
+    fn ss58_addresses_with_invalid_prefixes_are_displayed_as_hex() {
+        let alice = AccountId32(AccountKeyring::Alice.to_raw_public());
+        let address = UnifiedAddress::Ss58 {
+            account_id: alice.clone(),
+            prefix: 20000,
+        };
+        assert_eq!(address.to_string(), format!("0x{}", hex::encode(alice.0)));
+    }
+
+    #[test]
+    fn parses_ethereum_addresses() {
+        let checksummed = "0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac";
+        let address: UnifiedAddress = checksummed.parse().unwrap();
+        assert_eq!(address.to_string(), checksummed);
+
+        // No checksum to validate if all one case:
+        let lower: UnifiedAddress = checksummed.to_ascii_lowercase().parse().unwrap();
+        assert_eq!(lower, address);
+
+        // But a bad checksum is rejected:
+        assert_eq!(
+            "0xF24FF3a9CF04c71Dbc94D0b566f7A27B94566cac".parse::<UnifiedAddress>(),
+            Err(UnifiedAddressError::InvalidChecksum)
+        );
+
+        let account_id = address.into_account_id::<AccountId20>().unwrap();
+        assert_eq!(account_id.checksum(), checksummed);
+    }
+
+    #[test]
+    fn gives_precise_errors() {
+        assert_eq!(
+            "0x1234".parse::<UnifiedAddress>(),
+            Err(UnifiedAddressError::InvalidHexLength(2))
+        );
+        assert_eq!(
+            "0xzz".parse::<UnifiedAddress>(),
+            Err(UnifiedAddressError::InvalidHex)
+        );
+        assert_eq!(
+            "not an address".parse::<UnifiedAddress>(),
+            Err(UnifiedAddressError::Ss58(FromSs58Error::BadBase58))
+        );
+
+        let alice = AccountId32(AccountKeyring::Alice.to_raw_public());
+        assert_eq!(
+            UnifiedAddress::Hex(alice.clone()).into_account_id::<AccountId20>(),
+            Err(UnifiedAddressError::UnexpectedLength {
+                expected: 20,
+                found: 32
+            })
+        );
+        assert_eq!(
+            UnifiedAddress::Ss58 {
+                account_id: alice,
+                prefix: 42
+            }
+            .into_account_id::<AccountId20>(),
+            Err(UnifiedAddressError::UnexpectedSs58)
+        );
+    }
+}
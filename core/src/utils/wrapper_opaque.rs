@@ -152,6 +152,18 @@ impl<T> IntoVisitor for WrapperKeepOpaque<T> {
     }
 }
 
+impl<T> serde::Serialize for WrapperKeepOpaque<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        super::human_readable::bytes_as_hex::serialize(&self.data, serializer)
+    }
+}
+
+impl<'de, T> serde::Deserialize<'de> for WrapperKeepOpaque<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        super::human_readable::bytes_as_hex::deserialize(deserializer).map(Self::from_encoded)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use scale_decode::DecodeAsType;
@@ -58,6 +58,12 @@ struct RuntimeMetadataArgs {
     #[darling(default)]
     runtime_types_only: bool,
     #[darling(default)]
+    client_bindings: bool,
+    #[darling(default)]
+    feature_gates: bool,
+    #[darling(default)]
+    serde: bool,
+    #[darling(default)]
     no_default_derives: bool,
     #[darling(default)]
     no_default_substitutions: bool,
@@ -125,6 +131,15 @@ fn subxt_inner(args: TokenStream, item_mod: syn::ItemMod) -> Result<TokenStream,
     if args.runtime_types_only {
         codegen.runtime_types_only();
     }
+    if args.client_bindings {
+        codegen.generate_client_bindings();
+    }
+    if args.feature_gates {
+        codegen.generate_feature_gates();
+    }
+    if args.serde {
+        codegen.enable_serde();
+    }
     if args.no_default_derives {
         codegen.disable_default_derives();
     }
@@ -0,0 +1,146 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use hmac::{Hmac, Mac};
+use sha2::{Sha256, Sha512};
+use zeroize::Zeroize;
+
+const ED25519_SEED: &[u8] = b"ed25519 seed";
+
+/// An extended private key: 32 bytes of `kL`, 32 bytes of `kR` and a 32 byte chain code.
+pub type ExtendedKey = [u8; 96];
+
+/// Compute the master extended key from a BIP-39 seed in the way that the Ledger
+/// Polkadot app does. This follows BIP32-Ed25519 (as opposed to plain SLIP-10), and is
+/// taken from `ledgerMaster` in `@polkadot/util-crypto`.
+pub fn ledger_master(seed: &[u8; 64]) -> ExtendedKey {
+    let mut chain_code_data = [0u8; 65];
+    chain_code_data[0] = 1;
+    chain_code_data[1..].copy_from_slice(seed);
+    let chain_code = hmac_sha256(ED25519_SEED, &chain_code_data);
+    chain_code_data.zeroize();
+
+    // Keep hashing until the third highest bit of the last byte is clear.
+    let mut private = hmac_sha512(ED25519_SEED, seed);
+    while private[31] & 0b0010_0000 != 0 {
+        private = hmac_sha512(ED25519_SEED, &private);
+    }
+    private[0] &= 0b1111_1000;
+    private[31] &= 0b0111_1111;
+    private[31] |= 0b0100_0000;
+
+    let mut key = [0u8; 96];
+    key[..64].copy_from_slice(&private);
+    key[64..].copy_from_slice(&chain_code);
+    private.zeroize();
+    key
+}
+
+/// Derive a child extended key at the given index, which is expected to be hardened
+/// (ie `>= 2^31`). This is taken from `ledgerDerivePrivate` in `@polkadot/util-crypto`.
+pub fn ledger_derive_private(key: &ExtendedKey, index: u32) -> ExtendedKey {
+    let (kl, rest) = key.split_at(32);
+    let (kr, chain_code) = rest.split_at(32);
+
+    let mut data = [0u8; 69];
+    data[1..33].copy_from_slice(kl);
+    data[33..65].copy_from_slice(kr);
+    data[65..].copy_from_slice(&index.to_le_bytes());
+
+    let mut z = hmac_sha512(chain_code, &data);
+    data[0] = 1;
+    let mut child_chain_code = hmac_sha512(chain_code, &data);
+    data.zeroize();
+
+    // kL' = kL + 8 * zL[0..28] and kR' = kR + zR, both as little endian integers
+    // truncated to 256 bits.
+    let mut zl = [0u8; 32];
+    zl[..28].copy_from_slice(&z[..28]);
+    let mut child = [0u8; 96];
+    child[..32].copy_from_slice(&add_le(kl, &mul8_le(&zl)));
+    child[32..64].copy_from_slice(&add_le(kr, &z[32..]));
+    child[64..].copy_from_slice(&child_chain_code[32..]);
+
+    z.zeroize();
+    zl.zeroize();
+    child_chain_code.zeroize();
+    child
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any size; qed");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC takes keys of any size; qed");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+// Add two 32 byte little endian integers, discarding any overflow.
+fn add_le(a: &[u8], b: &[u8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    let mut carry = 0u16;
+    for ((out, a), b) in out.iter_mut().zip(a).zip(b) {
+        let sum = *a as u16 + *b as u16 + carry;
+        *out = sum as u8;
+        carry = sum >> 8;
+    }
+    out
+}
+
+// Multiply a 32 byte little endian integer by 8, discarding any overflow.
+fn mul8_le(a: &[u8; 32]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    let mut carry = 0u8;
+    for (out, a) in out.iter_mut().zip(a) {
+        *out = (a << 3) | carry;
+        carry = a >> 5;
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn little_endian_arithmetic() {
+        let mut a = [0u8; 32];
+        a[0] = 0xff;
+        a[1] = 0x01;
+        let mut b = [0u8; 32];
+        b[0] = 0x02;
+
+        let mut expected = [0u8; 32];
+        expected[0] = 0x01;
+        expected[1] = 0x02;
+        assert_eq!(add_le(&a, &b), expected);
+
+        let mut expected = [0u8; 32];
+        expected[0] = 0xf8;
+        expected[1] = 0x0f;
+        assert_eq!(mul8_le(&a), expected);
+
+        // Overflow is discarded:
+        let mut expected = [0u8; 32];
+        expected[0] = 0x01;
+        assert_eq!(add_le(&[0xff; 32], &b), expected);
+
+        let mut expected = [0xff; 32];
+        expected[0] = 0xf8;
+        assert_eq!(mul8_le(&[0xff; 32]), expected);
+    }
+
+    #[test]
+    fn master_key_is_clamped() {
+        for i in 0..=255u8 {
+            let key = ledger_master(&[i; 64]);
+            assert_eq!(key[0] & 0b0000_0111, 0);
+            assert_eq!(key[31] & 0b1110_0000, 0b0100_0000);
+        }
+    }
+}
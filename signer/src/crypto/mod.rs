@@ -8,11 +8,19 @@ mod derive_junction;
 mod secret_uri;
 
 // No need for the cfg other than to avoid an unused_imports lint warning.
-#[cfg(any(feature = "sr25519", feature = "ecdsa"))]
+#[cfg(feature = "ed25519")]
+mod ledger_derivation;
+#[cfg(any(feature = "sr25519", feature = "ecdsa", feature = "ed25519"))]
 mod seed_from_entropy;
+#[cfg(any(feature = "sr25519", feature = "ecdsa", feature = "ed25519"))]
+mod wrap_bytes;
 
 pub use derive_junction::DeriveJunction;
 pub use secret_uri::{SecretUri, SecretUriError, DEV_PHRASE};
 
-#[cfg(any(feature = "sr25519", feature = "ecdsa"))]
+#[cfg(feature = "ed25519")]
+pub use ledger_derivation::{ledger_derive_private, ledger_master};
+#[cfg(any(feature = "sr25519", feature = "ecdsa", feature = "ed25519"))]
 pub use seed_from_entropy::seed_from_entropy;
+#[cfg(any(feature = "sr25519", feature = "ecdsa", feature = "ed25519"))]
+pub use wrap_bytes::wrap_bytes;
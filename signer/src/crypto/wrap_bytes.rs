@@ -0,0 +1,45 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use alloc::vec::Vec;
+
+const WRAP_PREFIX: &[u8] = b"<Bytes>";
+const WRAP_POSTFIX: &[u8] = b"</Bytes>";
+
+/// Wrap a message in `<Bytes>..</Bytes>`, which is what the polkadot-js extension does
+/// to messages given to `signRaw` before signing them. Messages which are already
+/// wrapped are returned unchanged. This is taken from `u8aWrapBytes` in polkadot-js.
+#[allow(dead_code)]
+pub fn wrap_bytes(message: &[u8]) -> Vec<u8> {
+    if is_wrapped(message) {
+        return message.to_vec();
+    }
+
+    let mut wrapped = Vec::with_capacity(WRAP_PREFIX.len() + message.len() + WRAP_POSTFIX.len());
+    wrapped.extend_from_slice(WRAP_PREFIX);
+    wrapped.extend_from_slice(message);
+    wrapped.extend_from_slice(WRAP_POSTFIX);
+    wrapped
+}
+
+fn is_wrapped(message: &[u8]) -> bool {
+    message.len() >= WRAP_PREFIX.len() + WRAP_POSTFIX.len()
+        && message.starts_with(WRAP_PREFIX)
+        && message.ends_with(WRAP_POSTFIX)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn wraps_only_once() {
+        let wrapped = wrap_bytes(b"hello");
+        assert_eq!(wrapped, b"<Bytes>hello</Bytes>");
+        assert_eq!(wrap_bytes(&wrapped), wrapped);
+        assert_eq!(wrap_bytes(b""), b"<Bytes></Bytes>");
+        // Overlapping prefix and postfix doesn't count as wrapped:
+        assert_eq!(wrap_bytes(b"<Bytes>"), b"<Bytes><Bytes></Bytes>");
+    }
+}
@@ -5,7 +5,7 @@
 //! An ecdsa keypair implementation.
 use codec::Encode;
 
-use crate::crypto::{seed_from_entropy, DeriveJunction, SecretUri};
+use crate::crypto::{seed_from_entropy, wrap_bytes, DeriveJunction, SecretUri};
 use core::{fmt::Display, str::FromStr};
 use hex::FromHex;
 use secp256k1::{ecdsa::RecoverableSignature, Message, Secp256k1, SecretKey};
@@ -167,6 +167,12 @@ impl Keypair {
         self.sign_prehashed(&sp_crypto_hashing::blake2_256(message))
     }
 
+    /// Sign a message in the same way that the polkadot-js extension's `signRaw` does, by
+    /// first wrapping it in `<Bytes>..</Bytes>`. Use [`verify_message()`] to verify these.
+    pub fn sign_message(&self, message: &[u8]) -> Signature {
+        self.sign(&wrap_bytes(message))
+    }
+
     /// Signs a pre-hashed message.
     pub fn sign_prehashed(&self, message_hash: &[u8; 32]) -> Signature {
         let wrapped = Message::from_digest_slice(message_hash).expect("Message is 32 bytes; qed");
@@ -193,6 +199,45 @@ pub fn verify<M: AsRef<[u8]>>(sig: &Signature, message: M, pubkey: &PublicKey) -
     internal::verify(&sig.0, &wrapped, pubkey)
 }
 
+/// Verify that a signature for a message was created by the owner of the [`PublicKey`] using
+/// [`Keypair::sign_message()`] or the polkadot-js extension's `signRaw`. The message is wrapped
+/// in `<Bytes>..</Bytes>` before being verified, unless it is already wrapped.
+///
+/// ```rust
+/// use subxt_signer::ecdsa;
+///
+/// let keypair = ecdsa::dev::alice();
+/// let message = b"Hello!";
+///
+/// let signature = keypair.sign_message(message);
+/// let public_key = keypair.public_key();
+/// assert!(ecdsa::verify_message(&signature, message, &public_key));
+/// assert!(ecdsa::verify(&signature, b"<Bytes>Hello!</Bytes>", &public_key));
+/// ```
+pub fn verify_message<M: AsRef<[u8]>>(sig: &Signature, message: M, pubkey: &PublicKey) -> bool {
+    verify(sig, wrap_bytes(message.as_ref()), pubkey)
+}
+
+/// Recover the [`PublicKey`] whose owner created some signature for a message, or `None` if the
+/// signature is not valid.
+///
+/// ```rust
+/// use subxt_signer::ecdsa;
+///
+/// let keypair = ecdsa::dev::alice();
+/// let message = b"Hello!";
+///
+/// let signature = keypair.sign(message);
+/// let public_key = ecdsa::recover(&signature, message).unwrap();
+/// assert_eq!(public_key.0, keypair.public_key().0);
+/// ```
+pub fn recover<M: AsRef<[u8]>>(sig: &Signature, message: M) -> Option<PublicKey> {
+    let message_hash = sp_crypto_hashing::blake2_256(message.as_ref());
+    let wrapped = Message::from_digest_slice(&message_hash).expect("Message is 32 bytes; qed");
+
+    internal::recover(&sig.0, &wrapped)
+}
+
 pub(crate) mod internal {
     use super::*;
 
@@ -218,6 +263,15 @@ pub(crate) mod internal {
             .verify_ecdsa(message, &signature, &public)
             .is_ok()
     }
+
+    pub fn recover(sig: &[u8; 65], message: &Message) -> Option<PublicKey> {
+        let recid = secp256k1::ecdsa::RecoveryId::from_i32(sig[64] as i32).ok()?;
+        let signature = RecoverableSignature::from_compact(&sig[..64], recid).ok()?;
+        let public = Secp256k1::verification_only()
+            .recover_ecdsa(message, &signature)
+            .ok()?;
+        Some(PublicKey(public.serialize()))
+    }
 }
 
 /// An error handed back if creating a keypair fails.
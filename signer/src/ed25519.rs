@@ -0,0 +1,700 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! An ed25519 keypair implementation.
+use codec::Encode;
+
+use crate::crypto::{
+    ledger_derive_private, ledger_master, seed_from_entropy, wrap_bytes, DeriveJunction, SecretUri,
+};
+use alloc::vec::Vec;
+use core::{fmt::Display, str::FromStr};
+use ed25519_zebra::{SigningKey, VerificationKey};
+use hex::FromHex;
+use secrecy::ExposeSecret;
+
+const SECRET_KEY_LENGTH: usize = 32;
+
+/// Seed bytes used to generate a key pair.
+pub type SecretKeyBytes = [u8; SECRET_KEY_LENGTH];
+
+/// A signature generated by [`Keypair::sign()`]. These bytes are equivalent
+/// to a Substrate `MultiSignature::Ed25519(bytes)`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Signature(pub [u8; 64]);
+
+impl AsRef<[u8]> for Signature {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// The public key for an [`Keypair`] key pair. This is equivalent to a
+/// Substrate `AccountId32`.
+#[derive(Debug, Clone)]
+pub struct PublicKey(pub [u8; 32]);
+
+impl AsRef<[u8]> for PublicKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// An ed25519 keypair implementation.
+#[derive(Debug, Clone)]
+pub struct Keypair(SigningKey);
+
+impl Keypair {
+    /// Create an ed25519 keypair from a [`SecretUri`]. See the [`SecretUri`] docs for more.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use subxt_signer::{ SecretUri, ed25519::Keypair };
+    /// use std::str::FromStr;
+    ///
+    /// let uri = SecretUri::from_str("//Alice").unwrap();
+    /// let keypair = Keypair::from_uri(&uri).unwrap();
+    ///
+    /// keypair.sign(b"Hello world!");
+    /// ```
+    pub fn from_uri(uri: &SecretUri) -> Result<Self, Error> {
+        let SecretUri {
+            junctions,
+            phrase,
+            password,
+        } = uri;
+
+        // If the phrase is hex, convert bytes directly into a seed, ignoring password.
+        // Else, parse the phrase string taking the password into account. This is
+        // the same approach taken in sp_core::crypto::Pair::from_string_with_seed.
+        let key = if let Some(hex_str) = phrase.expose_secret().strip_prefix("0x") {
+            let seed = SecretKeyBytes::from_hex(hex_str)?;
+            Self::from_secret_key(seed)
+        } else {
+            let phrase = bip39::Mnemonic::from_str(phrase.expose_secret().as_str())?;
+            let pass_str = password.as_ref().map(|p| p.expose_secret().as_str());
+            Self::from_phrase(&phrase, pass_str)?
+        };
+
+        // Now, use any "junctions" to derive a new key from this root key.
+        key.derive(junctions.iter().copied())
+    }
+
+    /// Create an ed25519 keypair from a BIP-39 mnemonic phrase and optional password.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use subxt_signer::{ bip39::Mnemonic, ed25519::Keypair };
+    ///
+    /// let phrase = "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+    /// let mnemonic = Mnemonic::parse(phrase).unwrap();
+    /// let keypair = Keypair::from_phrase(&mnemonic, None).unwrap();
+    ///
+    /// keypair.sign(b"Hello world!");
+    /// ```
+    pub fn from_phrase(mnemonic: &bip39::Mnemonic, password: Option<&str>) -> Result<Self, Error> {
+        let (arr, len) = mnemonic.to_entropy_array();
+        let big_seed =
+            seed_from_entropy(&arr[0..len], password.unwrap_or("")).ok_or(Error::InvalidSeed)?;
+
+        let secret_key_bytes: SecretKeyBytes = big_seed[..SECRET_KEY_LENGTH]
+            .try_into()
+            .expect("should be valid Seed");
+
+        Ok(Self::from_secret_key(secret_key_bytes))
+    }
+
+    /// Create an ed25519 keypair from a BIP-39 mnemonic phrase, optional password and a
+    /// BIP-44 derivation path, in the same way as the Polkadot Ledger app. The same phrase
+    /// and path will produce the same address as the Ledger app does.
+    ///
+    /// **Note:** if the `std` feature is not enabled, we won't attempt to normalize the provided password
+    /// to NFKD first, and so this is your responsibility. This is not a concern if only ASCII
+    /// characters are used in the password.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use subxt_signer::{ bip39::Mnemonic, ed25519::{ Keypair, DerivationPath } };
+    ///
+    /// let phrase = "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+    /// let mnemonic = Mnemonic::parse(phrase).unwrap();
+    ///
+    /// // Equivalent to the path "m/44'/354'/0'/0'/0'":
+    /// let path = DerivationPath::polkadot(0, 0);
+    /// let keypair = Keypair::from_ledger_phrase(&mnemonic, None, &path);
+    ///
+    /// keypair.sign(b"Hello world!");
+    /// ```
+    pub fn from_ledger_phrase(
+        mnemonic: &bip39::Mnemonic,
+        password: Option<&str>,
+        derivation_path: &DerivationPath,
+    ) -> Self {
+        // `to_seed` isn't available unless std is enabled in bip39.
+        #[cfg(feature = "std")]
+        let seed = mnemonic.to_seed(password.unwrap_or(""));
+        #[cfg(not(feature = "std"))]
+        let seed = mnemonic.to_seed_normalized(password.unwrap_or(""));
+
+        let key = derivation_path
+            .indices
+            .iter()
+            .fold(ledger_master(&seed), |key, &index| {
+                ledger_derive_private(&key, index)
+            });
+
+        // As in polkadot-js, the first half of the derived private key is used as the seed.
+        let secret_key: SecretKeyBytes = key[..SECRET_KEY_LENGTH]
+            .try_into()
+            .expect("extended key is 96 bytes; qed");
+        Self::from_secret_key(secret_key)
+    }
+
+    /// Turn a 32 byte seed into a keypair.
+    ///
+    /// # Warning
+    ///
+    /// This will only be secure if the seed is secure!
+    pub fn from_secret_key(secret_key: SecretKeyBytes) -> Self {
+        Self(SigningKey::from(secret_key))
+    }
+
+    /// Derive a child key from this one given a series of junctions. Only
+    /// hard junctions are supported for ed25519 keys.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use subxt_signer::{ bip39::Mnemonic, ed25519::Keypair, DeriveJunction };
+    ///
+    /// let phrase = "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+    /// let mnemonic = Mnemonic::parse(phrase).unwrap();
+    /// let keypair = Keypair::from_phrase(&mnemonic, None).unwrap();
+    ///
+    /// // Equivalent to the URI path '//Alice//stash':
+    /// let new_keypair = keypair.derive([
+    ///     DeriveJunction::hard("Alice"),
+    ///     DeriveJunction::hard("stash")
+    /// ]);
+    /// ```
+    pub fn derive<Js: IntoIterator<Item = DeriveJunction>>(
+        &self,
+        junctions: Js,
+    ) -> Result<Self, Error> {
+        let mut acc = self.secret_key();
+        for junction in junctions {
+            match junction {
+                DeriveJunction::Soft(_) => return Err(Error::SoftJunction),
+                DeriveJunction::Hard(junction_bytes) => {
+                    acc = ("Ed25519HDKD", acc, junction_bytes)
+                        .using_encoded(sp_crypto_hashing::blake2_256)
+                }
+            }
+        }
+        Ok(Self::from_secret_key(acc))
+    }
+
+    /// Obtain the [`PublicKey`] part of this key pair, which can be used in calls to [`verify()`].
+    /// or otherwise converted into an address. The public key bytes are equivalent to a Substrate
+    /// `AccountId32`.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey(VerificationKey::from(&self.0).into())
+    }
+
+    /// Obtain the secret seed of this key pair. This should be kept secret.
+    pub fn secret_key(&self) -> SecretKeyBytes {
+        self.0.into()
+    }
+
+    /// Sign some message. These bytes can be used directly in a Substrate `MultiSignature::Ed25519(..)`.
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        Signature(self.0.sign(message).to_bytes())
+    }
+
+    /// Sign a message in the same way that the polkadot-js extension's `signRaw` does, by
+    /// first wrapping it in `<Bytes>..</Bytes>`. Use [`verify_message()`] to verify these.
+    pub fn sign_message(&self, message: &[u8]) -> Signature {
+        self.sign(&wrap_bytes(message))
+    }
+}
+
+/// A BIP-44 derivation path, for use in [`Keypair::from_ledger_phrase()`]. This can be parsed
+/// from a string like `"m/44'/354'/0'/0'/0'"`, or we can construct one using the helpers
+/// [`DerivationPath::polkadot()`] and [`DerivationPath::kusama()`].
+///
+/// Only hardened derivation is supported for ed25519 keys, so every index in the path is
+/// hardened, whether or not it's written with a trailing `'`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DerivationPath {
+    indices: Vec<u32>,
+}
+
+impl DerivationPath {
+    const HARDENED: u32 = 1 << 31;
+
+    /// The coin type used for Polkadot in BIP-44 paths.
+    pub const POLKADOT_COIN_TYPE: u32 = 354;
+    /// The coin type used for Kusama in BIP-44 paths.
+    pub const KUSAMA_COIN_TYPE: u32 = 434;
+
+    /// A BIP-44 path using the path "m/44'/coin_type'/account'/0'/address_index'". This is
+    /// how the Ledger apps for Substrate based chains derive their keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `coin_type`, `account` or `address_index` provided are >= 2^31.
+    pub fn bip44(coin_type: u32, account: u32, address_index: u32) -> Self {
+        assert!(
+            coin_type < Self::HARDENED,
+            "coin_type must be less than 2^31"
+        );
+        assert!(account < Self::HARDENED, "account must be less than 2^31");
+        assert!(
+            address_index < Self::HARDENED,
+            "address_index must be less than 2^31"
+        );
+
+        let indices = [44, coin_type, account, 0, address_index]
+            .into_iter()
+            .map(|i| i | Self::HARDENED)
+            .collect();
+        DerivationPath { indices }
+    }
+
+    /// A BIP-44 Polkadot path, "m/44'/354'/account'/0'/address_index'".
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `account` or `address_index` provided are >= 2^31.
+    pub fn polkadot(account: u32, address_index: u32) -> Self {
+        Self::bip44(Self::POLKADOT_COIN_TYPE, account, address_index)
+    }
+
+    /// A BIP-44 Kusama path, "m/44'/434'/account'/0'/address_index'".
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `account` or `address_index` provided are >= 2^31.
+    pub fn kusama(account: u32, address_index: u32) -> Self {
+        Self::bip44(Self::KUSAMA_COIN_TYPE, account, address_index)
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('/');
+        if parts.next() != Some("m") {
+            return Err(Error::DerivationPath);
+        }
+
+        let indices = parts
+            .map(|part| {
+                let part = part.strip_suffix('\'').unwrap_or(part);
+                match part.parse::<u32>() {
+                    Ok(index) if index < Self::HARDENED => Ok(index | Self::HARDENED),
+                    _ => Err(Error::DerivationPath),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(DerivationPath { indices })
+    }
+}
+
+/// Verify that some signature for a message was created by the owner of the [`PublicKey`].
+///
+/// ```rust
+/// use subxt_signer::{ bip39::Mnemonic, ed25519 };
+///
+/// let keypair = ed25519::dev::alice();
+/// let message = b"Hello!";
+///
+/// let signature = keypair.sign(message);
+/// let public_key = keypair.public_key();
+/// assert!(ed25519::verify(&signature, message, &public_key));
+/// ```
+pub fn verify<M: AsRef<[u8]>>(sig: &Signature, message: M, pubkey: &PublicKey) -> bool {
+    let Ok(public) = VerificationKey::try_from(pubkey.0) else {
+        return false;
+    };
+    let signature = ed25519_zebra::Signature::from_bytes(&sig.0);
+
+    public.verify(&signature, message.as_ref()).is_ok()
+}
+
+/// Verify that a signature for a message was created by the owner of the [`PublicKey`] using
+/// [`Keypair::sign_message()`] or the polkadot-js extension's `signRaw`. The message is wrapped
+/// in `<Bytes>..</Bytes>` before being verified, unless it is already wrapped.
+///
+/// ```rust
+/// use subxt_signer::ed25519;
+///
+/// let keypair = ed25519::dev::alice();
+/// let message = b"Hello!";
+///
+/// let signature = keypair.sign_message(message);
+/// let public_key = keypair.public_key();
+/// assert!(ed25519::verify_message(&signature, message, &public_key));
+/// assert!(ed25519::verify(&signature, b"<Bytes>Hello!</Bytes>", &public_key));
+/// ```
+pub fn verify_message<M: AsRef<[u8]>>(sig: &Signature, message: M, pubkey: &PublicKey) -> bool {
+    verify(sig, wrap_bytes(message.as_ref()), pubkey)
+}
+
+/// An error handed back if creating a keypair fails.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// Invalid seed.
+    InvalidSeed,
+    /// Soft junctions are not supported.
+    SoftJunction,
+    /// Invalid phrase.
+    Phrase(bip39::Error),
+    /// Invalid hex.
+    Hex(hex::FromHexError),
+    /// Invalid derivation path.
+    DerivationPath,
+}
+impl Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::InvalidSeed => write!(f, "Invalid seed (was it the wrong length?)"),
+            Error::SoftJunction => write!(f, "Invalid seed for ed25519, contained soft junction"),
+            Error::Phrase(e) => write!(f, "Cannot parse phrase: {e}"),
+            Error::Hex(e) => write!(f, "Cannot parse hex string: {e}"),
+            Error::DerivationPath => write!(
+                f,
+                "Invalid derivation path; expected something like \"m/44'/354'/0'/0'/0'\""
+            ),
+        }
+    }
+}
+
+impl_from!(bip39::Error => Error::Phrase);
+impl_from!(hex::FromHexError => Error::Hex);
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Dev accounts, helpful for testing but not to be used in production,
+/// since the secret keys are known.
+pub mod dev {
+    use super::*;
+
+    once_static_cloned! {
+        /// Equivalent to `{DEV_PHRASE}//Alice`.
+        pub fn alice() -> Keypair {
+            Keypair::from_uri(&SecretUri::from_str("//Alice").unwrap()).unwrap()
+        }
+        /// Equivalent to `{DEV_PHRASE}//Bob`.
+        pub fn bob() -> Keypair {
+            Keypair::from_uri(&SecretUri::from_str("//Bob").unwrap()).unwrap()
+        }
+        /// Equivalent to `{DEV_PHRASE}//Charlie`.
+        pub fn charlie() -> Keypair {
+            Keypair::from_uri(&SecretUri::from_str("//Charlie").unwrap()).unwrap()
+        }
+        /// Equivalent to `{DEV_PHRASE}//Dave`.
+        pub fn dave() -> Keypair {
+            Keypair::from_uri(&SecretUri::from_str("//Dave").unwrap()).unwrap()
+        }
+        /// Equivalent to `{DEV_PHRASE}//Eve`.
+        pub fn eve() -> Keypair {
+            Keypair::from_uri(&SecretUri::from_str("//Eve").unwrap()).unwrap()
+        }
+        /// Equivalent to `{DEV_PHRASE}//Ferdie`.
+        pub fn ferdie() -> Keypair {
+            Keypair::from_uri(&SecretUri::from_str("//Ferdie").unwrap()).unwrap()
+        }
+        /// Equivalent to `{DEV_PHRASE}//One`.
+        pub fn one() -> Keypair {
+            Keypair::from_uri(&SecretUri::from_str("//One").unwrap()).unwrap()
+        }
+        /// Equivalent to `{DEV_PHRASE}//Two`.
+        pub fn two() -> Keypair {
+            Keypair::from_uri(&SecretUri::from_str("//Two").unwrap()).unwrap()
+        }
+    }
+}
+
+// Make `Keypair` usable to sign transactions in Subxt. This is optional so that
+// `subxt-signer` can be used entirely independently of Subxt.
+#[cfg(feature = "subxt")]
+mod subxt_compat {
+    use super::*;
+
+    use subxt_core::config::Config;
+    use subxt_core::tx::signer::Signer as SignerT;
+    use subxt_core::utils::{AccountId32, MultiAddress, MultiSignature};
+
+    impl From<Signature> for MultiSignature {
+        fn from(value: Signature) -> Self {
+            MultiSignature::Ed25519(value.0)
+        }
+    }
+
+    impl From<PublicKey> for AccountId32 {
+        fn from(value: PublicKey) -> Self {
+            value.to_account_id()
+        }
+    }
+
+    impl<T> From<PublicKey> for MultiAddress<AccountId32, T> {
+        fn from(value: PublicKey) -> Self {
+            value.to_address()
+        }
+    }
+
+    impl PublicKey {
+        /// A shortcut to obtain an [`AccountId32`] from a [`PublicKey`].
+        /// We often want this type, and using this method avoids any
+        /// ambiguous type resolution issues.
+        pub fn to_account_id(self) -> AccountId32 {
+            AccountId32(self.0)
+        }
+        /// A shortcut to obtain a [`MultiAddress`] from a [`PublicKey`].
+        /// We often want this type, and using this method avoids any
+        /// ambiguous type resolution issues.
+        pub fn to_address<T>(self) -> MultiAddress<AccountId32, T> {
+            MultiAddress::Id(self.to_account_id())
+        }
+    }
+
+    impl<T: Config> SignerT<T> for Keypair
+    where
+        T::AccountId: From<PublicKey>,
+        T::Address: From<PublicKey>,
+        T::Signature: From<Signature>,
+    {
+        fn account_id(&self) -> T::AccountId {
+            self.public_key().into()
+        }
+
+        fn address(&self) -> T::Address {
+            self.public_key().into()
+        }
+
+        fn sign(&self, signer_payload: &[u8]) -> T::Signature {
+            self.sign(signer_payload).into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    use sp_core::crypto::Pair as _;
+    use sp_core::ed25519::Pair as SpPair;
+
+    #[test]
+    fn check_from_phrase_matches() {
+        for _ in 0..20 {
+            let (sp_pair, phrase, _seed) = SpPair::generate_with_phrase(None);
+            let phrase = bip39::Mnemonic::parse(phrase).expect("valid phrase expected");
+            let pair = Keypair::from_phrase(&phrase, None).expect("should be valid");
+
+            assert_eq!(sp_pair.public().0, pair.public_key().0);
+        }
+    }
+
+    #[test]
+    fn check_from_phrase_with_password_matches() {
+        for _ in 0..20 {
+            let (sp_pair, phrase, _seed) = SpPair::generate_with_phrase(Some("Testing"));
+            let phrase = bip39::Mnemonic::parse(phrase).expect("valid phrase expected");
+            let pair = Keypair::from_phrase(&phrase, Some("Testing")).expect("should be valid");
+
+            assert_eq!(sp_pair.public().0, pair.public_key().0);
+        }
+    }
+
+    #[test]
+    fn check_from_secret_uri_matches() {
+        // Some derive junctions to check that the logic there aligns:
+        let uri_paths = ["//bar", "//0001", "//1", "//0001", "//foo//bar//wibble"];
+
+        for i in 0..2 {
+            for path in &uri_paths {
+                // Build an sp_core::Pair that includes a phrase, path and password:
+                let password = format!("Testing{i}");
+                let (_sp_pair, phrase, _seed) = SpPair::generate_with_phrase(Some(&password));
+                let uri = format!("{phrase}{path}///{password}");
+                let sp_pair = SpPair::from_string(&uri, None).expect("should be valid");
+
+                // Now build a local Keypair using the equivalent API:
+                let uri = SecretUri::from_str(&uri).expect("should be valid secret URI");
+                let pair = Keypair::from_uri(&uri).expect("should be valid");
+
+                // They should match:
+                assert_eq!(sp_pair.public().0, pair.public_key().0);
+            }
+        }
+    }
+
+    #[test]
+    fn check_derive_errs_with_soft_junction() {
+        let uri_paths = ["/bar", "/1", "//foo//bar/wibble"];
+        for path in &uri_paths {
+            let (_sp_pair, phrase, _seed) = SpPair::generate_with_phrase(None);
+            let uri = format!("{phrase}{path}");
+            let uri = SecretUri::from_str(&uri).expect("should be valid secret URI");
+            let result = Keypair::from_uri(&uri);
+            assert_eq!(result.err(), Some(Error::SoftJunction));
+        }
+    }
+
+    #[test]
+    fn check_signing_and_verifying_matches() {
+        use sp_core::ed25519::Signature as SpSignature;
+
+        for _ in 0..20 {
+            let (sp_pair, phrase, _seed) = SpPair::generate_with_phrase(Some("Testing"));
+            let phrase = bip39::Mnemonic::parse(phrase).expect("valid phrase expected");
+            let pair = Keypair::from_phrase(&phrase, Some("Testing")).expect("should be valid");
+
+            let message = b"Hello world";
+            let sp_sig = sp_pair.sign(message).0;
+            let sig = pair.sign(message).0;
+
+            // ed25519 signatures are deterministic:
+            assert_eq!(sp_sig, sig);
+            assert!(SpPair::verify(
+                &SpSignature::from_raw(sig),
+                message,
+                &sp_pair.public(),
+            ));
+            assert!(verify(&Signature(sp_sig), message, &pair.public_key()));
+        }
+    }
+
+    #[test]
+    fn check_dev_accounts_match() {
+        use sp_keyring::ed25519::Keyring::*;
+
+        assert_eq!(dev::alice().public_key().0, Alice.public().0);
+        assert_eq!(dev::bob().public_key().0, Bob.public().0);
+        assert_eq!(dev::charlie().public_key().0, Charlie.public().0);
+        assert_eq!(dev::dave().public_key().0, Dave.public().0);
+        assert_eq!(dev::eve().public_key().0, Eve.public().0);
+        assert_eq!(dev::ferdie().public_key().0, Ferdie.public().0);
+        assert_eq!(dev::one().public_key().0, One.public().0);
+        assert_eq!(dev::two().public_key().0, Two.public().0);
+    }
+
+    #[test]
+    fn check_derivation_path_parsing() {
+        let path = DerivationPath::from_str("m/44'/354'/0'/0'/0'").expect("valid path");
+        assert_eq!(path, DerivationPath::polkadot(0, 0));
+
+        // Every index is hardened, so the trailing ' is optional:
+        let path = DerivationPath::from_str("m/44/434/1'/0/7").expect("valid path");
+        assert_eq!(path, DerivationPath::kusama(1, 7));
+
+        for invalid in ["", "44'/354'", "m/foo", "m/2147483648", "m//1", "n/44'"] {
+            assert_eq!(
+                DerivationPath::from_str(invalid),
+                Err(Error::DerivationPath),
+                "{invalid} should not parse"
+            );
+        }
+    }
+
+    #[test]
+    fn check_ledger_derivation() {
+        let phrase = bip39::Mnemonic::parse(crate::DEV_PHRASE).expect("valid phrase");
+
+        let a = Keypair::from_ledger_phrase(&phrase, None, &DerivationPath::polkadot(0, 0));
+        let b = Keypair::from_ledger_phrase(&phrase, None, &DerivationPath::polkadot(0, 1));
+        let c = Keypair::from_ledger_phrase(&phrase, Some("pass"), &DerivationPath::polkadot(0, 0));
+        let d = Keypair::from_ledger_phrase(&phrase, None, &DerivationPath::kusama(0, 0));
+
+        // Derivation is deterministic, and each input leads to a different key:
+        let a2 = Keypair::from_ledger_phrase(&phrase, None, &DerivationPath::polkadot(0, 0));
+        assert_eq!(a.public_key().0, a2.public_key().0);
+        let keys = [a, b, c, d].map(|k| k.public_key().0);
+        for (i, x) in keys.iter().enumerate() {
+            for y in &keys[i + 1..] {
+                assert_ne!(x, y);
+            }
+        }
+
+        // And isn't the same as Substrate style derivation:
+        let substrate = Keypair::from_phrase(&phrase, None).expect("valid phrase");
+        assert_ne!(substrate.public_key().0, keys[0]);
+    }
+
+    #[test]
+    fn check_ledger_derivation_known_answers() {
+        use sp_core::crypto::{Ss58AddressFormat, Ss58Codec};
+
+        let phrase = bip39::Mnemonic::parse(crate::DEV_PHRASE).expect("valid phrase");
+
+        // (password, path, public key, SS58 address), computed separately from this
+        // implementation by following `hdLedger` in `@polkadot/util-crypto` (which takes
+        // the password as a 25th word).
+        let cases = [
+            (
+                None,
+                "m/44'/354'/0'/0'/0'",
+                "7e94759318da12ef3678a2ccf851094eda08c738c164bf6d2729462db81100fe",
+                "13ry8qSb5ANj1mPQm4RaiMsdcLVtP7ps74UUFk3okUpUmFPy",
+            ),
+            (
+                None,
+                "m/44'/354'/0'/0'/1'",
+                "282ae66e30b4be2baf01182d59ad89530e60908af656707edf88a13839976e3a",
+                "1uffFB1WtEnFEUvpug6EGKMKBZneoSRcAh3m6wFx7ine6tM",
+            ),
+            (
+                None,
+                "m/44'/354'/1'/0'/0'",
+                "21f10729a0c1daee7dbaa4bf7007c308569026837654a5bad4afe52346ff2a3b",
+                "1mWBU53iViS7XQALGMczp1tWpcGsVt4mxTLw95QDJm4fboS",
+            ),
+            (
+                Some("pass"),
+                "m/44'/354'/0'/0'/0'",
+                "4fab2d89d968cf294d180a10a77940a7d73b8147cf0e5e5dbb1aaee8c7eca2b0",
+                "12oTdjaCNydvgEJH9zPySrrTvBmZyQXc4HyYsMWJuQvCEqMN",
+            ),
+            (
+                None,
+                "m/44'/434'/0'/0'/0'",
+                "b792448b07a03a465d6f0a7feb76d50f5c185702f88fd2629eb2da7dbcdde5be",
+                "Gj1jNHjE6cxUYxwZ15ho37Q4PurMmK9VuYtRYFNqXS6oWqh",
+            ),
+        ];
+
+        for (password, path, public_key, address) in cases {
+            let derivation_path = DerivationPath::from_str(path).expect("valid path");
+            let pair = Keypair::from_ledger_phrase(&phrase, password, &derivation_path);
+            assert_eq!(hex::encode(pair.public_key().0), public_key, "{path}");
+
+            // Polkadot paths use prefix 0 and Kusama paths prefix 2:
+            let prefix = if path.contains("/354'/") { 0 } else { 2 };
+            let ss58 = sp_core::ed25519::Public::from_raw(pair.public_key().0)
+                .to_ss58check_with_version(Ss58AddressFormat::custom(prefix));
+            assert_eq!(ss58, address, "{path}");
+        }
+    }
+
+    #[test]
+    fn check_hex_uris() {
+        // Hex URIs seem to ignore the password on sp_core and here. Check that this is consistent.
+        let uri_str =
+            "0x1122334455667788112233445566778811223344556677881122334455667788///SomePassword";
+
+        let uri = SecretUri::from_str(uri_str).expect("should be valid");
+        let pair = Keypair::from_uri(&uri).expect("should be valid");
+        let sp_pair = SpPair::from_string(uri_str, None).expect("should be valid");
+
+        assert_eq!(pair.public_key().0, sp_pair.public().0);
+    }
+}
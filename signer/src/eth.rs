@@ -6,6 +6,7 @@
 
 use crate::ecdsa;
 use alloc::format;
+use alloc::vec::Vec;
 use core::fmt::{Display, Formatter};
 use core::str::FromStr;
 use keccak_hash::keccak;
@@ -117,6 +118,23 @@ impl Keypair {
     pub fn sign_prehashed(&self, message_hash: &[u8; 32]) -> Signature {
         Signature(self.0.sign_prehashed(message_hash).0)
     }
+
+    /// Sign a message in the same way as Ethereum's `personal_sign`, by prefixing it as
+    /// described in EIP-191. Use [`verify_message()`] to verify these.
+    ///
+    /// Note that the last byte of the signature is the recovery ID as 0 or 1, and not 27 or 28
+    /// as some Ethereum tooling expects.
+    pub fn sign_message(&self, message: &[u8]) -> Signature {
+        self.sign(&eip191_message(message))
+    }
+}
+
+// Prefix a message in the EIP-191 `personal_sign` format, ie
+// `"\x19Ethereum Signed Message:\n" + len(message) + message`.
+fn eip191_message(message: &[u8]) -> Vec<u8> {
+    let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    prefixed.extend_from_slice(message);
+    prefixed
 }
 /// A derivation path. This can be parsed from a valid derivation path string like
 /// `"m/44'/60'/0'/0/0"`, or we can construct one using the helpers [`DerivationPath::empty()`]
@@ -200,6 +218,24 @@ pub fn verify<M: AsRef<[u8]>>(sig: &Signature, message: M, pubkey: &PublicKey) -
         .is_ok()
 }
 
+/// Verify that a signature for a message was created by the owner of the [`PublicKey`] using
+/// [`Keypair::sign_message()`] or Ethereum's `personal_sign`. The message is prefixed as
+/// described in EIP-191 before being verified.
+///
+/// ```rust
+/// use subxt_signer::eth;
+///
+/// let keypair = eth::dev::alith();
+/// let message = b"Hello!";
+///
+/// let signature = keypair.sign_message(message);
+/// let public_key = keypair.public_key();
+/// assert!(eth::verify_message(&signature, message, &public_key));
+/// ```
+pub fn verify_message<M: AsRef<[u8]>>(sig: &Signature, message: M, pubkey: &PublicKey) -> bool {
+    verify(sig, eip191_message(message.as_ref()), pubkey)
+}
+
 /// An error handed back if creating a keypair fails.
 #[derive(Debug, PartialEq)]
 pub enum Error {
@@ -485,6 +521,24 @@ mod test {
         }
     }
 
+    #[test]
+    fn check_eip191_message_signing() {
+        // The hash that ethers.js `hashMessage("Hello World")` produces:
+        let expected_hash =
+            hex_literal::hex!("a1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2");
+        assert_eq!(keccak(eip191_message(b"Hello World")).0, expected_hash);
+
+        let keypair = dev::alith();
+        let signature = keypair.sign_message(b"Hello World");
+        assert_eq!(signature, keypair.sign_prehashed(&expected_hash));
+        assert!(verify_message(
+            &signature,
+            b"Hello World",
+            &keypair.public_key()
+        ));
+        assert!(!verify(&signature, b"Hello World", &keypair.public_key()));
+    }
+
     // This is a part of the test set linked in BIP39 and copied from https://github.com/trezor/python-mnemonic/blob/f5a975ab10c035596d65d854d21164266ffed284/vectors.json.
     // The passphrase is always TREZOR. We check that keys generated with the mnemonic (and no derivation path) line up with the seeds given.
     #[test]
@@ -0,0 +1,437 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! A filesystem keystore which is compatible with the layout used by Substrate nodes.
+//!
+//! Each key is stored in its own file. The file name is the hex encoded key type,
+//! followed by the hex encoded public key, and the file contents are the JSON encoded
+//! secret URI that the key was derived from. Pointing a [`Keystore`] at a node's keystore
+//! directory (typically `<base-path>/chains/<chain>/keystore`) allows keys to be read
+//! from or written to it.
+
+use core::fmt::Display;
+use core::str::FromStr;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use secrecy::SecretString;
+
+use crate::{SecretUri, SecretUriError};
+
+/// A four byte identifier for the type of a key, for instance `b"aura"` or `b"gran"`.
+/// This is equivalent to Substrate's `KeyTypeId`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KeyTypeId(pub [u8; 4]);
+
+impl KeyTypeId {
+    /// Key type for Babe.
+    pub const BABE: KeyTypeId = KeyTypeId(*b"babe");
+    /// Key type for Grandpa.
+    pub const GRANDPA: KeyTypeId = KeyTypeId(*b"gran");
+    /// Key type for Aura.
+    pub const AURA: KeyTypeId = KeyTypeId(*b"aura");
+    /// Key type for ImOnline.
+    pub const IM_ONLINE: KeyTypeId = KeyTypeId(*b"imon");
+    /// Key type for the authority discovery module.
+    pub const AUTHORITY_DISCOVERY: KeyTypeId = KeyTypeId(*b"audi");
+    /// Key type for parachain validators.
+    pub const PARA_VALIDATOR: KeyTypeId = KeyTypeId(*b"para");
+    /// Key type for parachain assignment.
+    pub const ASSIGNMENT: KeyTypeId = KeyTypeId(*b"asgn");
+    /// Key type for BEEFY.
+    pub const BEEFY: KeyTypeId = KeyTypeId(*b"beef");
+    /// Key type for account keys.
+    pub const ACCOUNT: KeyTypeId = KeyTypeId(*b"acco");
+}
+
+impl From<[u8; 4]> for KeyTypeId {
+    fn from(value: [u8; 4]) -> Self {
+        KeyTypeId(value)
+    }
+}
+
+/// A keypair which can be stored in and loaded from a [`Keystore`].
+///
+/// This is implemented for each of the keypair types in this crate, and cannot be
+/// implemented outside of it.
+pub trait KeystoreKeypair: sealed::Sealed + Sized {
+    /// Construct the keypair from a secret URI.
+    #[doc(hidden)]
+    fn from_keystore_uri(uri: &SecretUri) -> Result<Self, Error>;
+    /// The public key bytes, which make up part of the file name.
+    #[doc(hidden)]
+    fn public_key_bytes(&self) -> Vec<u8>;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+#[cfg(feature = "sr25519")]
+impl sealed::Sealed for crate::sr25519::Keypair {}
+#[cfg(feature = "sr25519")]
+impl KeystoreKeypair for crate::sr25519::Keypair {
+    fn from_keystore_uri(uri: &SecretUri) -> Result<Self, Error> {
+        Ok(Self::from_uri(uri)?)
+    }
+    fn public_key_bytes(&self) -> Vec<u8> {
+        self.public_key().0.to_vec()
+    }
+}
+
+#[cfg(feature = "ed25519")]
+impl sealed::Sealed for crate::ed25519::Keypair {}
+#[cfg(feature = "ed25519")]
+impl KeystoreKeypair for crate::ed25519::Keypair {
+    fn from_keystore_uri(uri: &SecretUri) -> Result<Self, Error> {
+        Ok(Self::from_uri(uri)?)
+    }
+    fn public_key_bytes(&self) -> Vec<u8> {
+        self.public_key().0.to_vec()
+    }
+}
+
+#[cfg(feature = "ecdsa")]
+impl sealed::Sealed for crate::ecdsa::Keypair {}
+#[cfg(feature = "ecdsa")]
+impl KeystoreKeypair for crate::ecdsa::Keypair {
+    fn from_keystore_uri(uri: &SecretUri) -> Result<Self, Error> {
+        Ok(Self::from_uri(uri)?)
+    }
+    fn public_key_bytes(&self) -> Vec<u8> {
+        self.public_key().0.to_vec()
+    }
+}
+
+/// A key found in a [`Keystore`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeystoreEntry {
+    /// The type of the key.
+    pub key_type: KeyTypeId,
+    /// The public key bytes. The scheme of the key is not recorded in the keystore,
+    /// so this is 32 bytes for sr25519 and ed25519 keys and 33 bytes for ecdsa keys.
+    pub public_key: Vec<u8>,
+}
+
+/// A keystore backed by a directory on the filesystem, using the same layout as Substrate.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use subxt_signer::keystore::{Keystore, KeyTypeId};
+/// use subxt_signer::sr25519;
+///
+/// let keystore = Keystore::open("/tmp/node/chains/dev/keystore", None).unwrap();
+///
+/// // Generate a new aura key and store it:
+/// let aura: sr25519::Keypair = keystore.generate(KeyTypeId::AURA).unwrap();
+///
+/// // Load it again later:
+/// let loaded: Option<sr25519::Keypair> = keystore
+///     .load(KeyTypeId::AURA, &aura.public_key().0)
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct Keystore {
+    path: PathBuf,
+    password: Option<SecretString>,
+}
+
+impl Keystore {
+    /// Open the keystore at the given directory, creating it if it doesn't exist.
+    ///
+    /// If a password is given, it's used in place of any password in the stored
+    /// secret URIs, mirroring how a Substrate node started with `--password` treats
+    /// its keystore. Keys must be loaded with the same password that they were
+    /// generated or inserted with.
+    pub fn open(path: impl Into<PathBuf>, password: Option<SecretString>) -> Result<Self, Error> {
+        let path = path.into();
+        fs::create_dir_all(&path)?;
+        Ok(Keystore { path, password })
+    }
+
+    /// The directory that this keystore reads from and writes to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// List the keys in the keystore, optionally only returning those of a given
+    /// key type. Files which don't look like keys are ignored.
+    pub fn keys(&self, key_type: Option<KeyTypeId>) -> Result<Vec<KeystoreEntry>, Error> {
+        let mut entries = Vec::new();
+        for file in fs::read_dir(&self.path)? {
+            let file = file?;
+            if !file.file_type()?.is_file() {
+                continue;
+            }
+            let Some(entry) = file.file_name().to_str().and_then(parse_file_name) else {
+                continue;
+            };
+            if key_type.map_or(true, |k| k == entry.key_type) {
+                entries.push(entry);
+            }
+        }
+        entries.sort_by(|a, b| (a.key_type, &a.public_key).cmp(&(b.key_type, &b.public_key)));
+        Ok(entries)
+    }
+
+    /// Does the keystore contain a key with the given type and public key?
+    pub fn contains(&self, key_type: KeyTypeId, public_key: &[u8]) -> bool {
+        self.key_file_path(key_type, public_key).is_file()
+    }
+
+    /// Load a keypair from the keystore, returning `None` if no key with the given type
+    /// and public key exists.
+    ///
+    /// An error is returned if the stored secret URI doesn't produce the expected public
+    /// key, which will be the case if the wrong password or key scheme is used.
+    pub fn load<K: KeystoreKeypair>(
+        &self,
+        key_type: KeyTypeId,
+        public_key: &[u8],
+    ) -> Result<Option<K>, Error> {
+        let file_path = self.key_file_path(key_type, public_key);
+        let contents = match fs::read_to_string(file_path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let suri: String = serde_json::from_str(&contents)?;
+        let keypair: K = self.keypair_from_suri(&suri)?;
+        if keypair.public_key_bytes() != public_key {
+            return Err(Error::PublicKeyMismatch);
+        }
+        Ok(Some(keypair))
+    }
+
+    /// Generate a new keypair from a random 12 word BIP-39 phrase, and store it in the
+    /// keystore under the given key type.
+    pub fn generate<K: KeystoreKeypair>(&self, key_type: KeyTypeId) -> Result<K, Error> {
+        let mut entropy = [0u8; 16];
+        getrandom::getrandom(&mut entropy).map_err(|_| Error::Randomness)?;
+        let phrase = bip39::Mnemonic::from_entropy(&entropy)?;
+
+        self.insert(key_type, &phrase.to_string())
+    }
+
+    /// Insert the keypair described by the given secret URI into the keystore under
+    /// the given key type, returning it. Any existing key with the same key type and
+    /// public key is overwritten.
+    pub fn insert<K: KeystoreKeypair>(&self, key_type: KeyTypeId, suri: &str) -> Result<K, Error> {
+        let keypair: K = self.keypair_from_suri(suri)?;
+        let file_path = self.key_file_path(key_type, &keypair.public_key_bytes());
+        write_key_file(&file_path, &serde_json::to_string(suri)?)?;
+        Ok(keypair)
+    }
+
+    /// Remove a key from the keystore, returning whether it existed.
+    pub fn remove(&self, key_type: KeyTypeId, public_key: &[u8]) -> Result<bool, Error> {
+        match fs::remove_file(self.key_file_path(key_type, public_key)) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn keypair_from_suri<K: KeystoreKeypair>(&self, suri: &str) -> Result<K, Error> {
+        let mut uri = SecretUri::from_str(suri)?;
+        if let Some(password) = &self.password {
+            uri.password = Some(password.clone());
+        }
+        K::from_keystore_uri(&uri)
+    }
+
+    fn key_file_path(&self, key_type: KeyTypeId, public_key: &[u8]) -> PathBuf {
+        let mut file_name = hex::encode(key_type.0);
+        file_name.push_str(&hex::encode(public_key));
+        self.path.join(file_name)
+    }
+}
+
+// Parse a keystore file name into the key type and public key it represents.
+fn parse_file_name(name: &str) -> Option<KeystoreEntry> {
+    let bytes = hex::decode(name).ok()?;
+    if bytes.len() <= 4 {
+        return None;
+    }
+    let (key_type, public_key) = bytes.split_at(4);
+    Some(KeystoreEntry {
+        key_type: KeyTypeId(key_type.try_into().ok()?),
+        public_key: public_key.to_vec(),
+    })
+}
+
+// Write a key file, making sure that only the current user can read it on unix.
+fn write_key_file(path: &Path, contents: &str) -> Result<(), io::Error> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    io::Write::write_all(&mut options.open(path)?, contents.as_bytes())
+}
+
+/// An error handed back when interacting with a [`Keystore`].
+#[derive(Debug)]
+pub enum Error {
+    /// Filesystem error.
+    Io(io::Error),
+    /// A key file did not contain a JSON encoded secret URI.
+    Json(serde_json::Error),
+    /// The secret URI could not be parsed.
+    SecretUri(SecretUriError),
+    /// A new phrase could not be generated.
+    Phrase(bip39::Error),
+    /// Could not obtain random bytes to generate a new key.
+    Randomness,
+    /// The stored key does not match the public key it is filed under.
+    PublicKeyMismatch,
+    /// sr25519 keypair error.
+    #[cfg(feature = "sr25519")]
+    Sr25519(crate::sr25519::Error),
+    /// ed25519 keypair error.
+    #[cfg(feature = "ed25519")]
+    Ed25519(crate::ed25519::Error),
+    /// ecdsa keypair error.
+    #[cfg(feature = "ecdsa")]
+    Ecdsa(crate::ecdsa::Error),
+}
+
+impl_from!(io::Error => Error::Io);
+impl_from!(serde_json::Error => Error::Json);
+impl_from!(SecretUriError => Error::SecretUri);
+impl_from!(bip39::Error => Error::Phrase);
+#[cfg(feature = "sr25519")]
+impl_from!(crate::sr25519::Error => Error::Sr25519);
+#[cfg(feature = "ed25519")]
+impl_from!(crate::ed25519::Error => Error::Ed25519);
+#[cfg(feature = "ecdsa")]
+impl_from!(crate::ecdsa::Error => Error::Ecdsa);
+
+impl Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "Keystore IO error: {e}"),
+            Error::Json(e) => write!(f, "Invalid key file: {e}"),
+            Error::SecretUri(e) => write!(f, "Invalid secret URI: {e}"),
+            Error::Phrase(e) => write!(f, "Cannot generate phrase: {e}"),
+            Error::Randomness => write!(f, "Could not obtain random bytes."),
+            Error::PublicKeyMismatch => write!(
+                f,
+                "The stored key does not match its public key (is the password or key scheme wrong?)"
+            ),
+            #[cfg(feature = "sr25519")]
+            Error::Sr25519(e) => write!(f, "{e}"),
+            #[cfg(feature = "ed25519")]
+            Error::Ed25519(e) => write!(f, "{e}"),
+            #[cfg(feature = "ecdsa")]
+            Error::Ecdsa(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(all(test, feature = "sr25519"))]
+mod test {
+    use super::*;
+    use crate::sr25519;
+
+    // A fresh, empty directory to use as a keystore.
+    fn temp_keystore(name: &str, password: Option<&str>) -> Keystore {
+        let path = std::env::temp_dir().join(format!(
+            "subxt-signer-keystore-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&path);
+        Keystore::open(path, password.map(|p| SecretString::new(p.to_owned()))).unwrap()
+    }
+
+    #[test]
+    fn insert_and_load_matches_substrate_layout() {
+        let keystore = temp_keystore("layout", None);
+        let alice: sr25519::Keypair = keystore.insert(KeyTypeId::AURA, "//Alice").unwrap();
+
+        // File name is hex key type + hex public key, and contents are the JSON encoded SURI:
+        let file_name = format!("61757261{}", hex::encode(alice.public_key().0));
+        let contents = fs::read_to_string(keystore.path().join(file_name)).unwrap();
+        assert_eq!(contents, "\"//Alice\"");
+
+        let loaded: sr25519::Keypair = keystore
+            .load(KeyTypeId::AURA, &alice.public_key().0)
+            .unwrap()
+            .expect("key should exist");
+        assert_eq!(loaded.public_key().0, sr25519::dev::alice().public_key().0);
+
+        let missing: Option<sr25519::Keypair> = keystore
+            .load(KeyTypeId::GRANDPA, &alice.public_key().0)
+            .unwrap();
+        assert!(missing.is_none());
+
+        fs::remove_dir_all(keystore.path()).unwrap();
+    }
+
+    #[cfg(all(feature = "ed25519", feature = "ecdsa"))]
+    #[test]
+    fn keys_can_be_listed_and_filtered() {
+        use crate::{ecdsa, ed25519};
+
+        let keystore = temp_keystore("list", None);
+        let aura: sr25519::Keypair = keystore.generate(KeyTypeId::AURA).unwrap();
+        let gran: ed25519::Keypair = keystore.generate(KeyTypeId::GRANDPA).unwrap();
+        let beef: ecdsa::Keypair = keystore.generate(KeyTypeId::BEEFY).unwrap();
+
+        // Files that don't look like keys are ignored.
+        fs::write(keystore.path().join("not-a-key"), "hello").unwrap();
+
+        assert_eq!(keystore.keys(None).unwrap().len(), 3);
+        assert_eq!(
+            keystore.keys(Some(KeyTypeId::GRANDPA)).unwrap(),
+            vec![KeystoreEntry {
+                key_type: KeyTypeId::GRANDPA,
+                public_key: gran.public_key().0.to_vec()
+            }]
+        );
+        assert!(keystore.contains(KeyTypeId::AURA, &aura.public_key().0));
+        assert!(keystore.contains(KeyTypeId::BEEFY, &beef.public_key().0));
+
+        let loaded: ecdsa::Keypair = keystore
+            .load(KeyTypeId::BEEFY, &beef.public_key().0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded, beef);
+
+        assert!(keystore
+            .remove(KeyTypeId::AURA, &aura.public_key().0)
+            .unwrap());
+        assert!(!keystore
+            .remove(KeyTypeId::AURA, &aura.public_key().0)
+            .unwrap());
+        assert!(keystore.keys(Some(KeyTypeId::AURA)).unwrap().is_empty());
+
+        fs::remove_dir_all(keystore.path()).unwrap();
+    }
+
+    #[test]
+    fn password_is_applied_to_keys() {
+        let keystore = temp_keystore("password", Some("secret"));
+        let key: sr25519::Keypair = keystore.insert(KeyTypeId::BABE, "//Alice").unwrap();
+
+        // The password changes the key that the SURI produces:
+        assert_ne!(key.public_key().0, sr25519::dev::alice().public_key().0);
+        let expected =
+            sr25519::Keypair::from_uri(&SecretUri::from_str("//Alice///secret").unwrap()).unwrap();
+        assert_eq!(key.public_key().0, expected.public_key().0);
+
+        // Loading without the password fails:
+        let no_password = Keystore::open(keystore.path(), None).unwrap();
+        let res = no_password.load::<sr25519::Keypair>(KeyTypeId::BABE, &key.public_key().0);
+        assert!(matches!(res, Err(Error::PublicKeyMismatch)));
+
+        fs::remove_dir_all(keystore.path()).unwrap();
+    }
+}
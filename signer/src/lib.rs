@@ -11,7 +11,9 @@
 //! and can support compilation to WASM with the `web` feature.
 //!
 //! Enable the `subxt` feature to enable use of this [`sr25519::Keypair`] in signing
-//! subxt transactions for chains supporting sr25519 signatures.
+//! subxt transactions for chains supporting sr25519 signatures. The [`ecdsa::Keypair`]
+//! and [`ed25519::Keypair`] types offer the same interface for the other
+//! `MultiSignature` schemes.
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -32,16 +34,26 @@ pub mod sr25519;
 #[cfg_attr(docsrs, doc(cfg(feature = "ecdsa")))]
 pub mod ecdsa;
 
+// An ed25519 key pair implementation.
+#[cfg(feature = "ed25519")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ed25519")))]
+pub mod ed25519;
+
 // An ethereum signer implementation.
 #[cfg(feature = "unstable-eth")]
 #[cfg_attr(docsrs, doc(cfg(feature = "unstable-eth")))]
 pub mod eth;
 
-/// A polkadot-js account json loader.
+/// A polkadot-js account json loader and exporter.
 #[cfg(feature = "polkadot-js-compat")]
 #[cfg_attr(docsrs, doc(cfg(feature = "polkadot-js-compat")))]
 pub mod polkadot_js_compat;
 
+/// A filesystem keystore compatible with Substrate's keystore layout.
+#[cfg(feature = "keystore")]
+#[cfg_attr(docsrs, doc(cfg(feature = "keystore")))]
+pub mod keystore;
+
 // Re-export useful bits and pieces for generating a Pair from a phrase,
 // namely the Mnemonic struct.
 pub use bip39;
@@ -53,3 +65,11 @@ pub use secrecy::{ExposeSecret, SecretString};
 // SecretUri's can be parsed from strings and used to generate key pairs.
 // DeriveJunctions are the "path" part of these SecretUris.
 pub use crypto::{DeriveJunction, SecretUri, SecretUriError, DEV_PHRASE};
+
+// Verify `MultiSignature`s, such as those found on extrinsics in Subxt.
+#[cfg(feature = "subxt")]
+mod multi_signature;
+
+#[cfg(feature = "subxt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "subxt")))]
+pub use multi_signature::verify_multi_signature;
@@ -0,0 +1,87 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use subxt_core::utils::{AccountId32, MultiSignature};
+
+/// Verify that a [`MultiSignature`] for some message was created by the owner of the given
+/// account. For sr25519 and ed25519 signatures the account ID is the public key, and for ecdsa
+/// signatures it is the blake2_256 hash of the (compressed) public key, which is recovered from
+/// the signature.
+///
+/// If support for the signature's scheme is not enabled via this crate's features, this
+/// returns `false`.
+///
+/// This has the shape expected by `ExtrinsicDetails::verify_signature()` in Subxt, and so can
+/// be used to verify the signatures of extrinsics found in blocks.
+///
+/// ```rust
+/// use subxt_core::utils::MultiSignature;
+/// use subxt_signer::{sr25519, verify_multi_signature};
+///
+/// let keypair = sr25519::dev::alice();
+/// let message = b"Hello!";
+///
+/// let signature = MultiSignature::from(keypair.sign(message));
+/// let account_id = keypair.public_key().to_account_id();
+/// assert!(verify_multi_signature(&signature, message, &account_id));
+/// ```
+pub fn verify_multi_signature(
+    signature: &MultiSignature,
+    message: &[u8],
+    account_id: &AccountId32,
+) -> bool {
+    match signature {
+        #[cfg(feature = "sr25519")]
+        MultiSignature::Sr25519(sig) => crate::sr25519::verify(
+            &crate::sr25519::Signature(*sig),
+            message,
+            &crate::sr25519::PublicKey(account_id.0),
+        ),
+        #[cfg(feature = "ed25519")]
+        MultiSignature::Ed25519(sig) => crate::ed25519::verify(
+            &crate::ed25519::Signature(*sig),
+            message,
+            &crate::ed25519::PublicKey(account_id.0),
+        ),
+        #[cfg(feature = "ecdsa")]
+        MultiSignature::Ecdsa(sig) => {
+            crate::ecdsa::recover(&crate::ecdsa::Signature(*sig), message)
+                .is_some_and(|public_key| public_key.to_account_id() == *account_id)
+        }
+        #[allow(unreachable_patterns)]
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(all(feature = "sr25519", feature = "ed25519", feature = "ecdsa"))]
+    fn verifies_each_signature_scheme() {
+        let message = b"Hello!";
+        let bob = crate::sr25519::dev::bob().public_key().to_account_id();
+
+        let sr25519 = crate::sr25519::dev::alice();
+        let signature = sr25519.sign(message).into();
+        let account_id = sr25519.public_key().to_account_id();
+        assert!(verify_multi_signature(&signature, message, &account_id));
+        assert!(!verify_multi_signature(&signature, b"Bye!", &account_id));
+        assert!(!verify_multi_signature(&signature, message, &bob));
+
+        let ed25519 = crate::ed25519::dev::alice();
+        let signature = ed25519.sign(message).into();
+        let account_id = ed25519.public_key().to_account_id();
+        assert!(verify_multi_signature(&signature, message, &account_id));
+        assert!(!verify_multi_signature(&signature, message, &bob));
+
+        let ecdsa = crate::ecdsa::dev::alice();
+        let signature = ecdsa.sign(message).into();
+        let account_id = ecdsa.public_key().to_account_id();
+        assert!(verify_multi_signature(&signature, message, &account_id));
+        assert!(!verify_multi_signature(&signature, b"Bye!", &account_id));
+        assert!(!verify_multi_signature(&signature, message, &bob));
+    }
+}
@@ -2,7 +2,7 @@
 // This file is dual-licensed as Apache-2.0 or GPL-3.0.
 // see LICENSE for license details.
 
-//! A Polkadot-JS account loader.
+//! A Polkadot-JS account loader and exporter.
 
 use base64::Engine;
 use core::fmt::Display;
@@ -10,15 +10,187 @@ use crypto_secretbox::{
     aead::{Aead, KeyInit},
     Key, Nonce, XSalsa20Poly1305,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use subxt_core::utils::AccountId32;
 
 use crate::sr25519;
 
+// https://github.com/polkadot-js/common/blob/master/packages/keyring/src/pair/defaults.ts
+const PKCS8_HEADER: [u8; 16] = [48, 83, 2, 1, 1, 48, 5, 6, 3, 43, 101, 112, 4, 34, 4, 32];
+const PKCS8_DIVIDER: [u8; 5] = [161, 35, 3, 33, 0];
+
+// The scrypt parameters used by Polkadot-JS; log2(N) = 15, r = 8, p = 1.
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_N: u32 = 1 << SCRYPT_LOG_N;
+const SCRYPT_P: u32 = 1;
+const SCRYPT_R: u32 = 8;
+
+const SALT_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 24;
+const PARAMS_LENGTH: usize = SALT_LENGTH + 12 + NONCE_LENGTH;
+
 /// Given a JSON keypair as exported from Polkadot-JS, this returns an [`sr25519::Keypair`]
 pub fn decrypt_json(json: &str, password: &str) -> Result<sr25519::Keypair, Error> {
+    decrypt_json_as(json, password)
+}
+
+/// Given a JSON keypair as exported from Polkadot-JS, this returns a keypair of the type
+/// given, which can be any keypair that [`encrypt_json()`] accepts. The key type recorded
+/// in the JSON must match.
+///
+/// # Example
+///
+/// ```rust
+/// use subxt_signer::{ sr25519, polkadot_js_compat };
+///
+/// let keypair = sr25519::dev::alice();
+/// let json = polkadot_js_compat::encrypt_json(&keypair, "password", serde_json::Value::Null).unwrap();
+///
+/// let decrypted: sr25519::Keypair = polkadot_js_compat::decrypt_json_as(&json, "password").unwrap();
+/// assert_eq!(decrypted.public_key().0, keypair.public_key().0);
+/// ```
+pub fn decrypt_json_as<K: PolkadotJsKeypair>(json: &str, password: &str) -> Result<K, Error> {
     let pair_json: KeyringPairJson = serde_json::from_str(json)?;
-    Ok(pair_json.decrypt(password)?)
+    pair_json.decrypt(password)
+}
+
+/// Encrypt a keypair into the JSON format used by Polkadot-JS, such that it can be
+/// imported into the Polkadot-JS extension or apps UI using the given password.
+///
+/// The `meta` value is stored in the resulting JSON. Polkadot-JS expects this to be an
+/// object, and will typically look for fields like `name`, `genesisHash` and `whenCreated`
+/// in it. [`serde_json::Value::Null`] is stored as an empty object, and any other value
+/// which is not an object is rejected with [`Error::InvalidMeta`].
+///
+/// # Example
+///
+/// ```rust
+/// use subxt_signer::{ sr25519, polkadot_js_compat };
+///
+/// let keypair = sr25519::dev::alice();
+/// let meta = serde_json::json!({ "name": "Alice" });
+/// let json = polkadot_js_compat::encrypt_json(&keypair, "password", meta).unwrap();
+///
+/// let decrypted = polkadot_js_compat::decrypt_json(&json, "password").unwrap();
+/// assert_eq!(decrypted.public_key().0, keypair.public_key().0);
+/// ```
+pub fn encrypt_json<K: PolkadotJsKeypair>(
+    keypair: &K,
+    password: &str,
+    meta: serde_json::Value,
+) -> Result<String, Error> {
+    let meta = match meta {
+        serde_json::Value::Null => serde_json::Value::Object(Default::default()),
+        serde_json::Value::Object(_) => meta,
+        _ => return Err(Error::InvalidMeta),
+    };
+
+    let mut salt = [0u8; SALT_LENGTH];
+    let mut nonce = [0u8; NONCE_LENGTH];
+    getrandom::getrandom(&mut salt).map_err(|_| Error::Randomness)?;
+    getrandom::getrandom(&mut nonce).map_err(|_| Error::Randomness)?;
+
+    let pair_json = KeyringPairJson::encrypt(keypair, password, meta, salt, nonce)?;
+    Ok(serde_json::to_string(&pair_json)?)
+}
+
+/// A keypair which can be exported to Polkadot-JS compatible JSON via [`encrypt_json()`],
+/// and imported from it via [`decrypt_json_as()`].
+///
+/// This is implemented for each of the keypair types in this crate, and cannot be
+/// implemented outside of it.
+pub trait PolkadotJsKeypair: sealed::Sealed + Sized {
+    /// The key type, as Polkadot-JS names it (eg `"sr25519"`).
+    #[doc(hidden)]
+    const KEY_TYPE: &'static str;
+    /// The length of the secret key bytes, in the form Polkadot-JS stores them.
+    #[doc(hidden)]
+    const SECRET_KEY_LENGTH: usize;
+    /// The secret key bytes, in the form Polkadot-JS stores them.
+    #[doc(hidden)]
+    fn secret_key_bytes(&self) -> Vec<u8>;
+    /// Create the keypair from the secret key bytes, in the form Polkadot-JS stores them.
+    #[doc(hidden)]
+    fn from_secret_key_bytes(bytes: &[u8]) -> Result<Self, Error>;
+    /// The public key bytes.
+    #[doc(hidden)]
+    fn public_key_bytes(&self) -> Vec<u8>;
+    /// The account ID that Polkadot-JS will display for this keypair.
+    #[doc(hidden)]
+    fn account_id(&self) -> AccountId32;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+impl sealed::Sealed for sr25519::Keypair {}
+impl PolkadotJsKeypair for sr25519::Keypair {
+    const KEY_TYPE: &'static str = "sr25519";
+    const SECRET_KEY_LENGTH: usize = 64;
+    fn secret_key_bytes(&self) -> Vec<u8> {
+        self.to_ed25519_bytes().to_vec()
+    }
+    fn from_secret_key_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(sr25519::Keypair::from_ed25519_bytes(bytes)?)
+    }
+    fn public_key_bytes(&self) -> Vec<u8> {
+        self.public_key().0.to_vec()
+    }
+    fn account_id(&self) -> AccountId32 {
+        self.public_key().to_account_id()
+    }
+}
+
+#[cfg(feature = "ed25519")]
+impl sealed::Sealed for crate::ed25519::Keypair {}
+#[cfg(feature = "ed25519")]
+impl PolkadotJsKeypair for crate::ed25519::Keypair {
+    const KEY_TYPE: &'static str = "ed25519";
+    const SECRET_KEY_LENGTH: usize = 64;
+    fn secret_key_bytes(&self) -> Vec<u8> {
+        // Polkadot-JS stores ed25519 secret keys as the seed followed by the public key.
+        let mut bytes = self.secret_key().to_vec();
+        bytes.extend_from_slice(&self.public_key().0);
+        bytes
+    }
+    fn from_secret_key_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let (seed, public_key) = bytes.split_at(32);
+        let keypair = crate::ed25519::Keypair::from_secret_key(
+            seed.try_into().map_err(|_| Error::InvalidKeys)?,
+        );
+        if keypair.public_key().0 != public_key {
+            return Err(Error::InvalidKeys);
+        }
+        Ok(keypair)
+    }
+    fn public_key_bytes(&self) -> Vec<u8> {
+        self.public_key().0.to_vec()
+    }
+    fn account_id(&self) -> AccountId32 {
+        self.public_key().to_account_id()
+    }
+}
+
+#[cfg(feature = "ecdsa")]
+impl sealed::Sealed for crate::ecdsa::Keypair {}
+#[cfg(feature = "ecdsa")]
+impl PolkadotJsKeypair for crate::ecdsa::Keypair {
+    const KEY_TYPE: &'static str = "ecdsa";
+    const SECRET_KEY_LENGTH: usize = 32;
+    fn secret_key_bytes(&self) -> Vec<u8> {
+        self.secret_key().to_vec()
+    }
+    fn from_secret_key_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let secret_key = bytes.try_into().map_err(|_| Error::InvalidKeys)?;
+        crate::ecdsa::Keypair::from_secret_key(secret_key).map_err(|_| Error::InvalidKeys)
+    }
+    fn public_key_bytes(&self) -> Vec<u8> {
+        self.public_key().0.to_vec()
+    }
+    fn account_id(&self) -> AccountId32 {
+        self.public_key().to_account_id()
+    }
 }
 
 /// Error
@@ -45,6 +217,10 @@ pub enum Error {
     Sr25519(sr25519::Error),
     /// The decrypted keys are not valid.
     InvalidKeys,
+    /// Could not obtain random bytes for the salt and nonce.
+    Randomness,
+    /// The metadata to store alongside the keypair is not a JSON object.
+    InvalidMeta,
 }
 
 impl_from!(serde_json::Error => Error::Json);
@@ -64,6 +240,8 @@ impl Display for Error {
             Error::Secretbox(e) => write!(f, "Decryption error: {e}"),
             Error::Sr25519(e) => write!(f, "{e}"),
             Error::InvalidKeys => write!(f, "The decrypted keys are not valid."),
+            Error::Randomness => write!(f, "Could not obtain random bytes."),
+            Error::InvalidMeta => write!(f, "The keypair metadata must be a JSON object."),
         }
     }
 }
@@ -71,7 +249,7 @@ impl Display for Error {
 #[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 struct EncryptionMetadata {
     /// Descriptor for the content
     content: Vec<String>,
@@ -82,7 +260,7 @@ struct EncryptionMetadata {
 }
 
 /// https://github.com/polkadot-js/common/blob/37fa211fdb141d4f6eb32e8f377a4651ed2d9068/packages/keyring/src/types.ts#L67
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 struct KeyringPairJson {
     /// The encoded string
     encoded: String,
@@ -90,6 +268,9 @@ struct KeyringPairJson {
     encoding: EncryptionMetadata,
     /// The ss58 encoded address or the hex-encoded version (the latter is for ETH-compat chains)
     address: AccountId32,
+    /// Arbitrary metadata associated with the account.
+    #[serde(default)]
+    meta: serde_json::Value,
 }
 
 // This can be removed once split_array is stabilized.
@@ -97,14 +278,65 @@ fn slice_to_u32(slice: &[u8]) -> u32 {
     u32::from_le_bytes(slice.try_into().expect("Slice should be 4 bytes."))
 }
 
+// Hash the password into a symmetric key using the parameters Polkadot-JS expects.
+fn scrypt_key(password: &str, salt: &[u8]) -> Key {
+    let scrypt_params = scrypt::Params::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P, 32)
+        .expect("Provided parameters should be valid.");
+    let mut key = Key::default();
+    scrypt::scrypt(password.as_bytes(), salt, &scrypt_params, &mut key)
+        .expect("Key should be 32 bytes.");
+    key
+}
+
 impl KeyringPairJson {
-    /// Decrypt JSON keypair.
-    fn decrypt(self, password: &str) -> Result<sr25519::Keypair, Error> {
+    /// Encrypt a keypair into JSON, given a salt and nonce.
+    fn encrypt<K: PolkadotJsKeypair>(
+        keypair: &K,
+        password: &str,
+        meta: serde_json::Value,
+        salt: [u8; SALT_LENGTH],
+        nonce: [u8; NONCE_LENGTH],
+    ) -> Result<Self, Error> {
+        // https://github.com/polkadot-js/common/blob/master/packages/keyring/src/pair/encode.ts
+        let mut plaintext = PKCS8_HEADER.to_vec();
+        plaintext.extend_from_slice(&keypair.secret_key_bytes());
+        plaintext.extend_from_slice(&PKCS8_DIVIDER);
+        plaintext.extend_from_slice(&keypair.public_key_bytes());
+
+        // Encrypt keys.
+        // https://github.com/polkadot-js/common/blob/master/packages/util-crypto/src/json/encryptFormat.ts
+        let key = scrypt_key(password, &salt);
+        let cipher = XSalsa20Poly1305::new(&key);
+        let ciphertext = cipher.encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())?;
+
+        // https://github.com/polkadot-js/common/blob/master/packages/util-crypto/src/scrypt/toU8a.ts
+        let mut encoded = Vec::with_capacity(PARAMS_LENGTH + ciphertext.len());
+        encoded.extend_from_slice(&salt);
+        encoded.extend_from_slice(&SCRYPT_N.to_le_bytes());
+        encoded.extend_from_slice(&SCRYPT_P.to_le_bytes());
+        encoded.extend_from_slice(&SCRYPT_R.to_le_bytes());
+        encoded.extend_from_slice(&nonce);
+        encoded.extend_from_slice(&ciphertext);
+
+        Ok(KeyringPairJson {
+            encoded: base64::engine::general_purpose::STANDARD.encode(encoded),
+            encoding: EncryptionMetadata {
+                content: vec!["pkcs8".to_owned(), K::KEY_TYPE.to_owned()],
+                r#type: vec!["scrypt".to_owned(), "xsalsa20-poly1305".to_owned()],
+                version: "3".to_owned(),
+            },
+            address: keypair.account_id(),
+            meta,
+        })
+    }
+
+    /// Decrypt the PKCS8 encoded keys, returning the plaintext bytes.
+    fn decrypt_pkcs8(&self, password: &str, key_type: &str) -> Result<Vec<u8>, Error> {
         // Check encoding.
         // https://github.com/polkadot-js/common/blob/37fa211fdb141d4f6eb32e8f377a4651ed2d9068/packages/keyring/src/keyring.ts#L166
         if self.encoding.version != "3"
             || !self.encoding.content.contains(&"pkcs8".to_owned())
-            || !self.encoding.content.contains(&"sr25519".to_owned())
+            || !self.encoding.content.contains(&key_type.to_owned())
             || !self.encoding.r#type.contains(&"scrypt".to_owned())
             || !self
                 .encoding
@@ -115,10 +347,11 @@ impl KeyringPairJson {
         }
 
         // Decode from Base64.
-        let decoded = base64::engine::general_purpose::STANDARD.decode(self.encoded)?;
-        let params: [u8; 68] = decoded[..68]
-            .try_into()
-            .map_err(|_| Error::UnsupportedEncoding)?;
+        let decoded = base64::engine::general_purpose::STANDARD.decode(&self.encoded)?;
+        let params: [u8; PARAMS_LENGTH] = decoded
+            .get(..PARAMS_LENGTH)
+            .and_then(|p| p.try_into().ok())
+            .ok_or(Error::UnsupportedEncoding)?;
 
         // Extract scrypt parameters.
         // https://github.com/polkadot-js/common/blob/master/packages/util-crypto/src/scrypt/fromU8a.ts
@@ -132,47 +365,46 @@ impl KeyringPairJson {
         // protection against carefully-crafted params that can eat up CPU since these are user
         // inputs. So we need to get very clever here, but atm we only allow the defaults
         // and if no match, bail out.
-        if n != 32768 || p != 1 || r != 8 {
+        if n != SCRYPT_N || p != SCRYPT_P || r != SCRYPT_R {
             return Err(Error::UnsupportedScryptParameters { n, p, r });
         }
 
         // Hash password.
-        let scrypt_params =
-            scrypt::Params::new(15, 8, 1, 32).expect("Provided parameters should be valid.");
-        let mut key = Key::default();
-        scrypt::scrypt(password.as_bytes(), salt, &scrypt_params, &mut key)
-            .expect("Key should be 32 bytes.");
+        let key = scrypt_key(password, salt);
 
         // Decrypt keys.
         // https://github.com/polkadot-js/common/blob/master/packages/util-crypto/src/json/decryptData.ts
         let cipher = XSalsa20Poly1305::new(&key);
-        let nonce = Nonce::from_slice(&params[44..68]);
-        let ciphertext = &decoded[68..];
-        let plaintext = cipher.decrypt(nonce, ciphertext)?;
+        let nonce = Nonce::from_slice(&params[44..PARAMS_LENGTH]);
+        let ciphertext = &decoded[PARAMS_LENGTH..];
+        Ok(cipher.decrypt(nonce, ciphertext)?)
+    }
+
+    /// Decrypt JSON keypair.
+    fn decrypt<K: PolkadotJsKeypair>(self, password: &str) -> Result<K, Error> {
+        let plaintext = self.decrypt_pkcs8(password, K::KEY_TYPE)?;
 
         // https://github.com/polkadot-js/common/blob/master/packages/keyring/src/pair/decode.ts
-        if plaintext.len() != 117 {
+        let divider_start = PKCS8_HEADER.len() + K::SECRET_KEY_LENGTH;
+        let public_key_start = divider_start + PKCS8_DIVIDER.len();
+        if plaintext.len() <= public_key_start {
             return Err(Error::InvalidKeys);
         }
 
-        let header = &plaintext[0..16];
-        let secret_key = &plaintext[16..80];
-        let div = &plaintext[80..85];
-        let public_key = &plaintext[85..117];
+        let header = &plaintext[..PKCS8_HEADER.len()];
+        let secret_key = &plaintext[PKCS8_HEADER.len()..divider_start];
+        let div = &plaintext[divider_start..public_key_start];
+        let public_key = &plaintext[public_key_start..];
 
-        if header != [48, 83, 2, 1, 1, 48, 5, 6, 3, 43, 101, 112, 4, 34, 4, 32]
-            || div != [161, 35, 3, 33, 0]
-        {
+        if header != PKCS8_HEADER || div != PKCS8_DIVIDER {
             return Err(Error::InvalidKeys);
         }
 
         // Generate keypair.
-        let keypair = sr25519::Keypair::from_ed25519_bytes(secret_key)?;
+        let keypair = K::from_secret_key_bytes(secret_key)?;
 
         // Ensure keys are correct.
-        if keypair.public_key().0 != public_key
-            || keypair.public_key().to_account_id() != self.address
-        {
+        if keypair.public_key_bytes() != public_key || keypair.account_id() != self.address {
             return Err(Error::InvalidKeys);
         }
 
@@ -210,4 +442,142 @@ mod test {
         "#;
         decrypt_json(json, "whoisalice").unwrap();
     }
+
+    #[test]
+    fn test_encrypt_decrypt_sr25519() {
+        let keypair = sr25519::dev::alice();
+        let meta = serde_json::json!({ "name": "Alice" });
+        let json = encrypt_json(&keypair, "whoisalice", meta.clone()).unwrap();
+
+        let pair_json: KeyringPairJson = serde_json::from_str(&json).unwrap();
+        assert_eq!(pair_json.meta, meta);
+        assert_eq!(
+            pair_json.address.to_string(),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+
+        let decrypted = decrypt_json(&json, "whoisalice").unwrap();
+        assert_eq!(decrypted.public_key().0, keypair.public_key().0);
+        assert!(decrypt_json(&json, "wrong").is_err());
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_encrypt_ed25519() {
+        let keypair = crate::ed25519::dev::bob();
+        let json = encrypt_json(&keypair, "whoisbob", serde_json::Value::Null).unwrap();
+
+        let pair_json: KeyringPairJson = serde_json::from_str(&json).unwrap();
+        assert_eq!(pair_json.address, keypair.public_key().to_account_id());
+        assert!(matches!(
+            pair_json.decrypt_pkcs8("whoisbob", "sr25519"),
+            Err(Error::UnsupportedEncoding)
+        ));
+
+        let plaintext = pair_json.decrypt_pkcs8("whoisbob", "ed25519").unwrap();
+        assert_eq!(plaintext.len(), 117);
+        assert_eq!(&plaintext[16..48], &keypair.secret_key());
+        assert_eq!(&plaintext[48..80], &keypair.public_key().0);
+        assert_eq!(&plaintext[85..117], &keypair.public_key().0);
+    }
+
+    #[cfg(feature = "ecdsa")]
+    #[test]
+    fn test_encrypt_ecdsa() {
+        let keypair = crate::ecdsa::dev::charlie();
+        let json = encrypt_json(&keypair, "whoischarlie", serde_json::Value::Null).unwrap();
+
+        let pair_json: KeyringPairJson = serde_json::from_str(&json).unwrap();
+        assert_eq!(pair_json.address, keypair.public_key().to_account_id());
+
+        let plaintext = pair_json.decrypt_pkcs8("whoischarlie", "ecdsa").unwrap();
+        assert_eq!(plaintext.len(), 86);
+        assert_eq!(&plaintext[..16], &PKCS8_HEADER);
+        assert_eq!(&plaintext[16..48], &keypair.secret_key());
+        assert_eq!(&plaintext[48..53], &PKCS8_DIVIDER);
+        assert_eq!(&plaintext[53..86], &keypair.public_key().0);
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_get_keypair_ed25519() {
+        // The ed25519 keypair for `//Bob`, in the export format used by Polkadot-JS.
+        let json = r#"
+            {
+              "encoded": "pSs3pkUeT5o6mZtX3tuZ2/YEso868BQKRrrlL7H2m4sAgAAAAQAAAAgAAAA8Iw0546awMXj525kBnYACuy6EvMZrr9EXPzOkjT1u/E46P9d9XV5UvAOI/JeoLZI3sUTzZL6ZKxz4h3XWcjKKHE8FfUKLl+T9bQ5kJoBiRuwN6XpcqHFHM35nG0VvfQHgbrqa0Q7ZerfXPG9/JHcRDfL/pJhPGyjc7heAYsbWNUhcbYm2HVLvFkMqSdPW99DnyVhviXA7zUdK/V6W",
+              "encoding": {
+                "content": [
+                  "pkcs8",
+                  "ed25519"
+                ],
+                "type": [
+                  "scrypt",
+                  "xsalsa20-poly1305"
+                ],
+                "version": "3"
+              },
+              "address": "5GoNkf6WdbxCFnPdAnYYQyCjAKPJgLNxXwPjwTh6DGg6gN3E",
+              "meta": {
+                "genesisHash": "",
+                "name": "Bob",
+                "whenCreated": 1718265838755
+              }
+            }
+        "#;
+        let keypair: crate::ed25519::Keypair = decrypt_json_as(json, "whoisbob").unwrap();
+        assert_eq!(
+            keypair.public_key().0,
+            crate::ed25519::dev::bob().public_key().0
+        );
+        assert!(matches!(
+            decrypt_json(json, "whoisbob"),
+            Err(Error::UnsupportedEncoding)
+        ));
+    }
+
+    #[cfg(feature = "ecdsa")]
+    #[test]
+    fn test_get_keypair_ecdsa() {
+        // The ecdsa keypair for `//Charlie`, in the export format used by Polkadot-JS.
+        let json = r#"
+            {
+              "encoded": "HMrsvwHzKmz+ZEtL6sK+9ubbhv/sWw5epTfy+EBsUH4AgAAAAQAAAAgAAACKK8P2jYUBkicp5CVJzcGU2pdgJppKBxeaNuDKJM5Bnsz4DQFuyM9woG1Juw/85n8294gtaSzWu6fJibgD3ws946Fjumc+Q+EDJhAg31MV4GPHI8TwxjFF2dsEq5oZ7vt2VTml8n0rTYs9F1gch179d181YMg4lSUZbAmBey4=",
+              "encoding": {
+                "content": [
+                  "pkcs8",
+                  "ecdsa"
+                ],
+                "type": [
+                  "scrypt",
+                  "xsalsa20-poly1305"
+                ],
+                "version": "3"
+              },
+              "address": "5EP2cMaCxLzhfD3aFAqqgu3kfXH7GcwweEv6JXZRP6ysRHkQ",
+              "meta": {
+                "genesisHash": "",
+                "name": "Charlie",
+                "whenCreated": 1718265838755
+              }
+            }
+        "#;
+        let keypair: crate::ecdsa::Keypair = decrypt_json_as(json, "whoischarlie").unwrap();
+        assert_eq!(
+            keypair.public_key().0,
+            crate::ecdsa::dev::charlie().public_key().0
+        );
+        assert!(decrypt_json_as::<crate::ecdsa::Keypair>(json, "wrong").is_err());
+    }
+
+    #[test]
+    fn test_encrypt_meta_must_be_an_object() {
+        let keypair = sr25519::dev::alice();
+
+        let json = encrypt_json(&keypair, "whoisalice", serde_json::Value::Null).unwrap();
+        let pair_json: KeyringPairJson = serde_json::from_str(&json).unwrap();
+        assert_eq!(pair_json.meta, serde_json::json!({}));
+
+        let res = encrypt_json(&keypair, "whoisalice", serde_json::json!("Alice"));
+        assert!(matches!(res, Err(Error::InvalidMeta)));
+    }
 }
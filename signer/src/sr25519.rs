@@ -6,7 +6,7 @@
 
 use core::{fmt::Display, str::FromStr};
 
-use crate::crypto::{seed_from_entropy, DeriveJunction, SecretUri};
+use crate::crypto::{seed_from_entropy, wrap_bytes, DeriveJunction, SecretUri};
 
 use hex::FromHex;
 use schnorrkel::{
@@ -134,6 +134,13 @@ impl Keypair {
         }))
     }
 
+    /// Obtain the Ed25519 expanded secret key bytes of this keypair; the
+    /// inverse of [`Keypair::from_ed25519_bytes()`].
+    #[cfg(feature = "polkadot-js-compat")]
+    pub(crate) fn to_ed25519_bytes(&self) -> [u8; 64] {
+        self.0.secret.to_ed25519_bytes()
+    }
+
     /// Derive a child key from this one given a series of junctions.
     ///
     /// # Example
@@ -176,6 +183,12 @@ impl Keypair {
         let signature = self.0.sign(context.bytes(message));
         Signature(signature.to_bytes())
     }
+
+    /// Sign a message in the same way that the polkadot-js extension's `signRaw` does, by
+    /// first wrapping it in `<Bytes>..</Bytes>`. Use [`verify_message()`] to verify these.
+    pub fn sign_message(&self, message: &[u8]) -> Signature {
+        self.sign(&wrap_bytes(message))
+    }
 }
 
 /// Verify that some signature for a message was created by the owner of the [`PublicKey`].
@@ -202,6 +215,25 @@ pub fn verify<M: AsRef<[u8]>>(sig: &Signature, message: M, pubkey: &PublicKey) -
         .is_ok()
 }
 
+/// Verify that a signature for a message was created by the owner of the [`PublicKey`] using
+/// [`Keypair::sign_message()`] or the polkadot-js extension's `signRaw`. The message is wrapped
+/// in `<Bytes>..</Bytes>` before being verified, unless it is already wrapped.
+///
+/// ```rust
+/// use subxt_signer::sr25519;
+///
+/// let keypair = sr25519::dev::alice();
+/// let message = b"Hello!";
+///
+/// let signature = keypair.sign_message(message);
+/// let public_key = keypair.public_key();
+/// assert!(sr25519::verify_message(&signature, message, &public_key));
+/// assert!(sr25519::verify(&signature, b"<Bytes>Hello!</Bytes>", &public_key));
+/// ```
+pub fn verify_message<M: AsRef<[u8]>>(sig: &Signature, message: M, pubkey: &PublicKey) -> bool {
+    verify(sig, wrap_bytes(message.as_ref()), pubkey)
+}
+
 /// An error handed back if creating a keypair fails.
 #[derive(Debug)]
 pub enum Error {
@@ -401,6 +433,34 @@ mod test {
         }
     }
 
+    #[test]
+    fn check_sign_message_matches_polkadot_js_wrapping() {
+        use sp_core::sr25519::Signature as SpSignature;
+
+        let pair = dev::alice();
+        let sp_pair = sp_keyring::sr25519::Keyring::Alice.pair();
+
+        let signature = pair.sign_message(b"Hello world");
+        assert!(SpPair::verify(
+            &SpSignature::from_raw(signature.0),
+            b"<Bytes>Hello world</Bytes>",
+            &sp_pair.public(),
+        ));
+
+        // Messages which are already wrapped aren't wrapped again:
+        assert!(verify_message(
+            &signature,
+            b"<Bytes>Hello world</Bytes>",
+            &pair.public_key()
+        ));
+        assert!(verify_message(
+            &signature,
+            b"Hello world",
+            &pair.public_key()
+        ));
+        assert!(!verify(&signature, b"Hello world", &pair.public_key()));
+    }
+
     #[test]
     fn check_dev_accounts_match() {
         use sp_keyring::sr25519::Keyring::*;
@@ -180,6 +180,29 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
         })))
     }
 
+    async fn storage_fetch_descendant_values_from(
+        &self,
+        key: Vec<u8>,
+        start_key: Vec<u8>,
+        at: T::Hash,
+    ) -> Result<StreamOfResults<StorageResponse>, Error> {
+        let keys_stream = StorageFetchDescendantKeysStream {
+            at,
+            key,
+            storage_page_size: self.storage_page_size,
+            methods: self.methods.clone(),
+            done: Default::default(),
+            keys_fut: Default::default(),
+            pagination_start_key: Some(start_key),
+        };
+
+        Ok(StreamOf(Box::pin(StorageFetchDescendantValuesStream {
+            keys: keys_stream,
+            results_fut: None,
+            results: Default::default(),
+        })))
+    }
+
     async fn genesis_hash(&self) -> Result<T::Hash, Error> {
         retry(|| self.methods.genesis_hash()).await
     }
@@ -188,6 +211,14 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
         retry(|| self.methods.chain_get_header(Some(at))).await
     }
 
+    async fn block_hash_at_number(&self, number: u64) -> Result<Option<T::Hash>, Error> {
+        retry(|| {
+            self.methods
+                .chain_get_block_hash(Some(rpc_methods::NumberOrHex::Number(number)))
+        })
+        .await
+    }
+
     async fn block_body(&self, at: T::Hash) -> Result<Option<Vec<Vec<u8>>>, Error> {
         retry(|| async {
             let Some(details) = self.methods.chain_get_block(Some(at)).await? else {
@@ -336,6 +367,10 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
         Ok(retry_sub)
     }
 
+    async fn pending_extrinsics(&self) -> Result<Vec<Vec<u8>>, Error> {
+        retry(|| self.methods.author_pending_extrinsics()).await
+    }
+
     async fn submit_transaction(
         &self,
         extrinsic: &[u8],
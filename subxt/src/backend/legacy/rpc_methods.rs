@@ -324,6 +324,16 @@ impl<T: Config> LegacyRpcMethods<T> {
         Ok(subscription)
     }
 
+    /// Return the extrinsics currently in the transaction pool, which are yet to be included
+    /// in a block. Each is SCALE encoded in the same way as the extrinsics in a block body.
+    pub async fn author_pending_extrinsics(&self) -> Result<Vec<Vec<u8>>, Error> {
+        let extrinsics: Vec<Bytes> = self
+            .client
+            .request("author_pendingExtrinsics", rpc_params![])
+            .await?;
+        Ok(extrinsics.into_iter().map(|b| b.0).collect())
+    }
+
     /// Insert a key into the keystore.
     pub async fn author_insert_key(
         &self,
@@ -8,9 +8,13 @@
 
 pub mod legacy;
 pub mod rpc;
+pub mod snapshot;
 pub mod unstable;
 pub mod utils;
 
+#[cfg(test)]
+pub(crate) mod test_utils;
+
 use subxt_core::client::RuntimeVersion;
 
 use crate::error::Error;
@@ -53,12 +57,27 @@ pub trait Backend<T: Config>: sealed::Sealed + Send + Sync + 'static {
         at: T::Hash,
     ) -> Result<StreamOfResults<StorageResponse>, Error>;
 
+    /// Fetch values underneath the given key from storage, beginning with the first key that
+    /// comes after `start_key`. This allows an iteration over some storage to be resumed from
+    /// the last key that it handed back.
+    async fn storage_fetch_descendant_values_from(
+        &self,
+        key: Vec<u8>,
+        start_key: Vec<u8>,
+        at: T::Hash,
+    ) -> Result<StreamOfResults<StorageResponse>, Error>;
+
     /// Fetch the genesis hash
     async fn genesis_hash(&self) -> Result<T::Hash, Error>;
 
     /// Get a block header
     async fn block_header(&self, at: T::Hash) -> Result<Option<T::Header>, Error>;
 
+    /// Get the hash of the block with the given number. This is expected to be called for
+    /// finalized block numbers, which have exactly one block. For higher numbers, backends
+    /// may return the block on the best chain, or `None`.
+    async fn block_hash_at_number(&self, number: u64) -> Result<Option<T::Hash>, Error>;
+
     /// Return the extrinsics found in the block. Each extrinsic is represented
     /// by a vector of bytes which has _not_ been SCALE decoded (in other words, the
     /// first bytes in the vector will decode to the compact encoded length of the extrinsic)
@@ -89,12 +108,37 @@ pub trait Backend<T: Config>: sealed::Sealed + Send + Sync + 'static {
         &self,
     ) -> Result<StreamOfResults<(T::Header, BlockRef<T::Hash>)>, Error>;
 
+    /// A stream of changes to the best and finalized blocks.
+    ///
+    /// By default, this combines [`Backend::stream_best_block_headers()`] and
+    /// [`Backend::stream_finalized_block_headers()`], and so can't report which blocks have
+    /// been pruned.
+    async fn stream_chain_updates(&self) -> Result<StreamOfResults<ChainUpdate<T>>, Error> {
+        let best = self.stream_best_block_headers().await?.map(|res| {
+            res.map(|(header, block_ref)| ChainUpdate::NewBestBlock { header, block_ref })
+        });
+        let finalized = self.stream_finalized_block_headers().await?.map(|res| {
+            res.map(|(header, block_ref)| ChainUpdate::Finalized {
+                header,
+                block_ref,
+                pruned_block_hashes: Vec::new(),
+            })
+        });
+        Ok(StreamOf::new(Box::pin(futures::stream::select(
+            best, finalized,
+        ))))
+    }
+
     /// Submit a transaction. This will return a stream of events about it.
     async fn submit_transaction(
         &self,
         bytes: &[u8],
     ) -> Result<StreamOfResults<TransactionStatus<T::Hash>>, Error>;
 
+    /// Return the extrinsics which are waiting in the transaction pool to be included in a
+    /// block. Each is represented in the same way as in [`Backend::block_body()`].
+    async fn pending_extrinsics(&self) -> Result<Vec<Vec<u8>>, Error>;
+
     /// Make a call to some runtime API.
     async fn call(
         &self,
@@ -156,6 +200,43 @@ pub trait BackendExt<T: Config>: Backend<T> {
         let metadata: Metadata = Decode::decode(&mut &opaque.0[..])?;
         Ok(metadata)
     }
+
+    /// Return the version of the runtime at some block, from the `Core_version` call.
+    async fn runtime_version_at(&self, at: T::Hash) -> Result<RuntimeVersion, Error> {
+        let version: CoreVersion = self.call_decoding("Core_version", None, at).await?;
+        Ok(RuntimeVersion {
+            spec_version: version.spec_version,
+            transaction_version: version.transaction_version,
+        })
+    }
+}
+
+/// The start of the `RuntimeVersion` returned from the `Core_version` runtime API. Fields
+/// after `transaction_version` are ignored when decoding.
+#[derive(Encode, Decode)]
+pub(crate) struct CoreVersion {
+    pub(crate) spec_name: String,
+    pub(crate) impl_name: String,
+    pub(crate) authoring_version: u32,
+    pub(crate) spec_version: u32,
+    pub(crate) impl_version: u32,
+    pub(crate) apis: Vec<([u8; 8], u32)>,
+    pub(crate) transaction_version: u32,
+}
+
+impl CoreVersion {
+    /// A version with the spec and transaction versions given, and every other field empty.
+    pub(crate) fn new(runtime_version: RuntimeVersion) -> Self {
+        CoreVersion {
+            spec_name: String::new(),
+            impl_name: String::new(),
+            authoring_version: 0,
+            spec_version: runtime_version.spec_version,
+            impl_version: 0,
+            apis: Vec::new(),
+            transaction_version: runtime_version.transaction_version,
+        }
+    }
 }
 
 #[async_trait]
@@ -323,6 +404,27 @@ pub enum TransactionStatus<Hash> {
     },
 }
 
+/// A change to the best or finalized block, as reported by [`Backend::stream_chain_updates()`].
+pub enum ChainUpdate<T: Config> {
+    /// The best block has changed.
+    NewBestBlock {
+        /// The header of the new best block.
+        header: T::Header,
+        /// A reference to the new best block.
+        block_ref: BlockRef<T::Hash>,
+    },
+    /// A new block has been finalized, and with it all of its ancestors.
+    Finalized {
+        /// The header of the newly finalized block.
+        header: T::Header,
+        /// A reference to the newly finalized block.
+        block_ref: BlockRef<T::Hash>,
+        /// Blocks which are not descendants of the finalized block, and so will never be
+        /// finalized. This may be empty if the backend doesn't know about them.
+        pruned_block_hashes: Vec<T::Hash>,
+    },
+}
+
 /// A response from calls like [`Backend::storage_fetch_values`] or
 /// [`Backend::storage_fetch_descendant_values`].
 #[cfg_attr(test, derive(serde::Serialize, Clone, PartialEq, Debug))]
@@ -632,7 +734,9 @@ mod test {
         /// This test should cover the logic of the following methods:
         /// - `genesis_hash`
         /// - `block_header`
+        /// - `block_hash_at_number`
         /// - `block_body`
+        /// - `pending_extrinsics`
         /// - `latest_finalized_block`
         /// - `current_runtime_version`
         /// - `current_runtime_version`
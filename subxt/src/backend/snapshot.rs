@@ -0,0 +1,494 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! This module exposes [`Snapshot`], a portable copy of some of the state of a chain at
+//! a single block, and [`SnapshotBackend`], which serves requests from a [`Snapshot`]
+//! without a connection to a node.
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use subxt::backend::{legacy::LegacyBackend, snapshot::{Snapshot, SnapshotBackend}};
+//! use subxt::{OnlineClient, PolkadotConfig};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! // Export the state of the System and Balances pallets at the latest finalized block:
+//! let rpc = subxt::backend::rpc::RpcClient::from_url("ws://localhost:9944").await?;
+//! let backend = LegacyBackend::<PolkadotConfig>::builder().build(rpc);
+//! let pallets = ["System".to_owned(), "Balances".to_owned()];
+//! let snapshot = Snapshot::export::<PolkadotConfig>(&backend, None, Some(&pallets[..])).await?;
+//! std::fs::write("state.snapshot", snapshot.to_bytes())?;
+//!
+//! // Later, query it without a node:
+//! let snapshot = Snapshot::from_bytes(&std::fs::read("state.snapshot")?)?;
+//! let backend = SnapshotBackend::<PolkadotConfig>::new(snapshot);
+//! let api = OnlineClient::from_backend(Arc::new(backend)).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::backend::{
+    Backend, BackendExt, BlockRef, CoreVersion, RuntimeVersion, StorageResponse, StreamOf,
+    StreamOfResults, TransactionStatus,
+};
+use crate::error::BlockError;
+use crate::{
+    config::{BlockHash, Header},
+    Config, Error,
+};
+use async_trait::async_trait;
+use codec::{Decode, Encode};
+use derive_where::derive_where;
+use futures::stream;
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+/// The bytes that every encoded [`Snapshot`] begins with.
+const MAGIC: &[u8; 8] = b"subxtsnp";
+/// The version of the snapshot format, which follows [`MAGIC`].
+const FORMAT_VERSION: u8 = 0;
+/// The metadata version that a [`Snapshot`] stores its metadata as.
+const METADATA_VERSION: u32 = 15;
+
+/// The raw key value pairs found in some or all of the storage of a chain at a single
+/// block, along with the header of that block and the metadata needed to make sense of it.
+///
+/// Use [`Snapshot::export()`] to create one, [`Snapshot::to_bytes()`] and
+/// [`Snapshot::from_bytes()`] to save and load it, and [`SnapshotBackend`] to query it.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct Snapshot<Hash> {
+    /// The genesis hash of the chain.
+    pub genesis_hash: Hash,
+    /// The hash of the block that the snapshot was taken at.
+    pub block_hash: Hash,
+    /// The SCALE encoded header of the block that the snapshot was taken at.
+    pub header: Vec<u8>,
+    /// The spec version of the runtime at the block.
+    pub spec_version: u32,
+    /// The transaction version of the runtime at the block.
+    pub transaction_version: u32,
+    /// The SCALE encoded metadata of the runtime at the block. This decodes into
+    /// [`crate::Metadata`].
+    pub metadata: Vec<u8>,
+    /// The raw storage keys and values that were exported.
+    pub storage: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl<Hash: BlockHash> Snapshot<Hash> {
+    /// Take a snapshot of the storage of the given pallets (or of all storage if `None` is
+    /// given) at the block given (or at the latest finalized block if `None` is given).
+    pub async fn export<T: Config<Hash = Hash>>(
+        backend: &dyn Backend<T>,
+        at: Option<T::Hash>,
+        pallets: Option<&[String]>,
+    ) -> Result<Self, Error> {
+        let block_hash = match at {
+            Some(hash) => hash,
+            None => backend.latest_finalized_block_ref().await?.hash(),
+        };
+        let header = backend
+            .block_header(block_hash)
+            .await?
+            .ok_or_else(|| BlockError::not_found(block_hash))?;
+
+        let metadata = match backend
+            .metadata_at_version(METADATA_VERSION, block_hash)
+            .await
+        {
+            Ok(metadata) => metadata,
+            Err(_) => backend.legacy_metadata(block_hash).await?,
+        };
+        let version = backend.runtime_version_at(block_hash).await?;
+
+        // Each pallet's storage lives below the hash of its storage prefix.
+        let prefixes = match pallets {
+            None => vec![Vec::new()],
+            Some(pallets) => pallets
+                .iter()
+                .map(|name| {
+                    let pallet = metadata.pallet_by_name_err(name)?;
+                    let prefix = pallet.storage().map_or(pallet.name(), |s| s.prefix());
+                    Ok(sp_crypto_hashing::twox_128(prefix.as_bytes()).to_vec())
+                })
+                .collect::<Result<Vec<_>, Error>>()?,
+        };
+
+        let mut storage = BTreeMap::new();
+        for prefix in prefixes {
+            let mut values = backend
+                .storage_fetch_descendant_values(prefix, block_hash)
+                .await?;
+            while let Some(kv) = values.next().await {
+                let kv = kv?;
+                storage.insert(kv.key, kv.value);
+            }
+        }
+
+        Ok(Snapshot {
+            genesis_hash: backend.genesis_hash().await?,
+            block_hash,
+            header: header.encode(),
+            spec_version: version.spec_version,
+            transaction_version: version.transaction_version,
+            metadata: metadata.encode(),
+            storage,
+        })
+    }
+
+    /// Encode this snapshot into bytes which can be written to a file, and loaded
+    /// back with [`Snapshot::from_bytes()`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(FORMAT_VERSION);
+        self.encode_to(&mut bytes);
+        bytes
+    }
+
+    /// Decode a snapshot from bytes produced by [`Snapshot::to_bytes()`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let Some(bytes) = bytes.strip_prefix(MAGIC) else {
+            return Err(Error::Other(
+                "Not a snapshot: unexpected leading bytes".into(),
+            ));
+        };
+        match bytes.split_first() {
+            Some((&FORMAT_VERSION, mut bytes)) => Ok(Snapshot::decode(&mut bytes)?),
+            Some((version, _)) => Err(Error::Other(format!(
+                "Unsupported snapshot format version {version}"
+            ))),
+            None => Err(Error::Other("Not a snapshot: no format version".into())),
+        }
+    }
+}
+
+/// A backend which serves storage, the block header and metadata from a [`Snapshot`],
+/// without connecting to a node.
+///
+/// The block that the snapshot was taken at is the only block that it knows about, and so
+/// it is the latest finalized block, and block subscriptions hand it back and then end.
+/// Storage which wasn't exported into the snapshot is treated as empty. Submitting
+/// transactions and calling runtime APIs (besides those used to fetch metadata and the
+/// runtime version) is not supported.
+#[derive_where(Debug, Clone)]
+pub struct SnapshotBackend<T: Config> {
+    snapshot: Snapshot<T::Hash>,
+}
+
+impl<T: Config> SnapshotBackend<T> {
+    /// Create a new [`SnapshotBackend`], which serves requests from the snapshot given.
+    pub fn new(snapshot: Snapshot<T::Hash>) -> Self {
+        SnapshotBackend { snapshot }
+    }
+
+    /// The snapshot that requests are served from.
+    pub fn snapshot(&self) -> &Snapshot<T::Hash> {
+        &self.snapshot
+    }
+
+    fn header(&self) -> Result<T::Header, Error> {
+        Ok(T::Header::decode(&mut &*self.snapshot.header)?)
+    }
+
+    fn runtime_version(&self) -> RuntimeVersion {
+        RuntimeVersion {
+            spec_version: self.snapshot.spec_version,
+            transaction_version: self.snapshot.transaction_version,
+        }
+    }
+
+    // Storage can only be fetched at the block that the snapshot was taken at.
+    fn storage_at(&self, at: T::Hash) -> Result<&BTreeMap<Vec<u8>, Vec<u8>>, Error> {
+        if at == self.snapshot.block_hash {
+            Ok(&self.snapshot.storage)
+        } else {
+            Err(BlockError::not_found(at).into())
+        }
+    }
+
+    // The storage entries whose keys begin with `key` and come after `start`.
+    fn descendants(
+        &self,
+        key: Vec<u8>,
+        start: Bound<Vec<u8>>,
+        at: T::Hash,
+    ) -> Result<Vec<StorageResponse>, Error> {
+        let storage = self.storage_at(at)?;
+        // No key below `key` has the prefix, so begin from `key` if we're asked to start
+        // before it.
+        let start = match start {
+            Bound::Excluded(start) if start >= key => Bound::Excluded(start),
+            _ => Bound::Included(key.clone()),
+        };
+        let values = storage
+            .range((start, Bound::Unbounded))
+            .take_while(|(k, _)| k.starts_with(&key))
+            .map(|(k, v)| StorageResponse {
+                key: k.clone(),
+                value: v.clone(),
+            })
+            .collect();
+        Ok(values)
+    }
+
+    fn header_stream(&self) -> Result<StreamOfResults<(T::Header, BlockRef<T::Hash>)>, Error> {
+        let block = (
+            self.header()?,
+            BlockRef::from_hash(self.snapshot.block_hash),
+        );
+        Ok(StreamOf(Box::pin(stream::iter([Ok(block)]))))
+    }
+}
+
+impl<T: Config> super::sealed::Sealed for SnapshotBackend<T> {}
+
+#[async_trait]
+impl<T: Config + Send + Sync + 'static> Backend<T> for SnapshotBackend<T> {
+    async fn storage_fetch_values(
+        &self,
+        keys: Vec<Vec<u8>>,
+        at: T::Hash,
+    ) -> Result<StreamOfResults<StorageResponse>, Error> {
+        let storage = self.storage_at(at)?;
+        let values: Vec<_> = keys
+            .into_iter()
+            .filter_map(|key| {
+                let value = storage.get(&key)?.clone();
+                Some(Ok(StorageResponse { key, value }))
+            })
+            .collect();
+        Ok(StreamOf(Box::pin(stream::iter(values))))
+    }
+
+    async fn storage_fetch_descendant_keys(
+        &self,
+        key: Vec<u8>,
+        at: T::Hash,
+    ) -> Result<StreamOfResults<Vec<u8>>, Error> {
+        let keys = self.descendants(key, Bound::Unbounded, at)?;
+        let keys = keys.into_iter().map(|kv| Ok(kv.key));
+        Ok(StreamOf(Box::pin(stream::iter(keys))))
+    }
+
+    async fn storage_fetch_descendant_values(
+        &self,
+        key: Vec<u8>,
+        at: T::Hash,
+    ) -> Result<StreamOfResults<StorageResponse>, Error> {
+        let values = self.descendants(key, Bound::Unbounded, at)?;
+        Ok(StreamOf(Box::pin(stream::iter(values.into_iter().map(Ok)))))
+    }
+
+    async fn storage_fetch_descendant_values_from(
+        &self,
+        key: Vec<u8>,
+        start_key: Vec<u8>,
+        at: T::Hash,
+    ) -> Result<StreamOfResults<StorageResponse>, Error> {
+        let values = self.descendants(key, Bound::Excluded(start_key), at)?;
+        Ok(StreamOf(Box::pin(stream::iter(values.into_iter().map(Ok)))))
+    }
+
+    async fn genesis_hash(&self) -> Result<T::Hash, Error> {
+        Ok(self.snapshot.genesis_hash)
+    }
+
+    async fn block_header(&self, at: T::Hash) -> Result<Option<T::Header>, Error> {
+        if at == self.snapshot.block_hash {
+            Ok(Some(self.header()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn block_hash_at_number(&self, number: u64) -> Result<Option<T::Hash>, Error> {
+        if self.header()?.number().into() == number {
+            Ok(Some(self.snapshot.block_hash))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn block_body(&self, _at: T::Hash) -> Result<Option<Vec<Vec<u8>>>, Error> {
+        // Block bodies aren't exported into snapshots.
+        Ok(None)
+    }
+
+    async fn latest_finalized_block_ref(&self) -> Result<BlockRef<T::Hash>, Error> {
+        Ok(BlockRef::from_hash(self.snapshot.block_hash))
+    }
+
+    async fn current_runtime_version(&self) -> Result<RuntimeVersion, Error> {
+        Ok(self.runtime_version())
+    }
+
+    async fn stream_runtime_version(&self) -> Result<StreamOfResults<RuntimeVersion>, Error> {
+        let versions = stream::iter([Ok(self.runtime_version())]);
+        Ok(StreamOf(Box::pin(versions)))
+    }
+
+    async fn stream_all_block_headers(
+        &self,
+    ) -> Result<StreamOfResults<(T::Header, BlockRef<T::Hash>)>, Error> {
+        self.header_stream()
+    }
+
+    async fn stream_best_block_headers(
+        &self,
+    ) -> Result<StreamOfResults<(T::Header, BlockRef<T::Hash>)>, Error> {
+        self.header_stream()
+    }
+
+    async fn stream_finalized_block_headers(
+        &self,
+    ) -> Result<StreamOfResults<(T::Header, BlockRef<T::Hash>)>, Error> {
+        self.header_stream()
+    }
+
+    async fn submit_transaction(
+        &self,
+        _bytes: &[u8],
+    ) -> Result<StreamOfResults<TransactionStatus<T::Hash>>, Error> {
+        Err(Error::UnsupportedMethod("submit_transaction".into()))
+    }
+
+    async fn pending_extrinsics(&self) -> Result<Vec<Vec<u8>>, Error> {
+        Err(Error::UnsupportedMethod("pending_extrinsics".into()))
+    }
+
+    async fn call(
+        &self,
+        method: &str,
+        call_parameters: Option<&[u8]>,
+        at: T::Hash,
+    ) -> Result<Vec<u8>, Error> {
+        self.storage_at(at)?;
+        let metadata = frame_metadata::OpaqueMetadata(self.snapshot.metadata.clone());
+
+        // Only the calls needed to fetch metadata and the runtime version are supported.
+        match method {
+            "Core_version" => {
+                let version = RuntimeVersion {
+                    spec_version: self.snapshot.spec_version,
+                    transaction_version: self.snapshot.transaction_version,
+                };
+                Ok(CoreVersion::new(version).encode())
+            }
+            "Metadata_metadata_versions" => Ok(vec![METADATA_VERSION].encode()),
+            "Metadata_metadata_at_version" => {
+                let version = u32::decode(&mut call_parameters.unwrap_or_default())?;
+                let metadata = (version == METADATA_VERSION).then_some(metadata);
+                Ok(metadata.encode())
+            }
+            "Metadata_metadata" => Ok(metadata.encode()),
+            _ => Err(Error::UnsupportedMethod(method.to_owned())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::substrate::{BlakeTwo256, Digest, SubstrateHeader, H256};
+    use crate::SubstrateConfig;
+    use futures::StreamExt;
+
+    fn snapshot() -> Snapshot<H256> {
+        let header = SubstrateHeader::<u32, BlakeTwo256> {
+            parent_hash: H256::zero(),
+            number: 10,
+            state_root: H256::zero(),
+            extrinsics_root: H256::zero(),
+            digest: Digest::default(),
+        };
+        let storage = [
+            (vec![1, 1], vec![10]),
+            (vec![2, 1], vec![20]),
+            (vec![2, 2], vec![21]),
+            (vec![2, 3], vec![22]),
+            (vec![3], vec![30]),
+        ];
+        Snapshot {
+            genesis_hash: H256::repeat_byte(1),
+            block_hash: H256::repeat_byte(2),
+            header: header.encode(),
+            spec_version: 1,
+            transaction_version: 2,
+            metadata: Vec::new(),
+            storage: storage.into_iter().collect(),
+        }
+    }
+
+    async fn keys(values: StreamOfResults<StorageResponse>) -> Vec<Vec<u8>> {
+        values.map(|kv| kv.unwrap().key).collect::<Vec<_>>().await
+    }
+
+    #[test]
+    fn snapshot_bytes_roundtrip() {
+        let snapshot = snapshot();
+        let bytes = snapshot.to_bytes();
+        assert_eq!(Snapshot::<H256>::from_bytes(&bytes).unwrap(), snapshot);
+        assert!(Snapshot::<H256>::from_bytes(&bytes[1..]).is_err());
+    }
+
+    #[tokio::test]
+    async fn storage_is_served_from_snapshot() {
+        let backend = SnapshotBackend::<SubstrateConfig>::new(snapshot());
+        let at = H256::repeat_byte(2);
+
+        let values = backend
+            .storage_fetch_values(vec![vec![3], vec![4]], at)
+            .await
+            .unwrap();
+        assert_eq!(keys(values).await, vec![vec![3]]);
+
+        let values = backend
+            .storage_fetch_descendant_values(vec![2], at)
+            .await
+            .unwrap();
+        assert_eq!(keys(values).await, vec![vec![2, 1], vec![2, 2], vec![2, 3]]);
+
+        let values = backend
+            .storage_fetch_descendant_values_from(vec![2], vec![2, 1], at)
+            .await
+            .unwrap();
+        assert_eq!(keys(values).await, vec![vec![2, 2], vec![2, 3]]);
+
+        let values = backend
+            .storage_fetch_descendant_values_from(vec![2], vec![1, 1], at)
+            .await
+            .unwrap();
+        assert_eq!(keys(values).await, vec![vec![2, 1], vec![2, 2], vec![2, 3]]);
+
+        assert!(backend
+            .storage_fetch_descendant_values(vec![2], H256::zero())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn block_is_served_from_snapshot() {
+        let backend = SnapshotBackend::<SubstrateConfig>::new(snapshot());
+        let hash = H256::repeat_byte(2);
+
+        assert_eq!(backend.block_hash_at_number(10).await.unwrap(), Some(hash));
+        assert_eq!(backend.block_hash_at_number(11).await.unwrap(), None);
+        assert_eq!(
+            backend.block_header(hash).await.unwrap().map(|h| h.number),
+            Some(10)
+        );
+        assert!(backend.block_header(H256::zero()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn unsupported_methods_are_reported_as_such() {
+        let backend = SnapshotBackend::<SubstrateConfig>::new(snapshot());
+        let hash = H256::repeat_byte(2);
+
+        let err = backend.pending_extrinsics().await.unwrap_err();
+        assert!(matches!(err, Error::UnsupportedMethod(m) if m == "pending_extrinsics"));
+        let err = backend.call("Foo_bar", None, hash).await.unwrap_err();
+        assert!(matches!(err, Error::UnsupportedMethod(m) if m == "Foo_bar"));
+        assert!(backend.call("Core_version", None, hash).await.is_ok());
+    }
+}
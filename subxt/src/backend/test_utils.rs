@@ -0,0 +1,375 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! A [`Backend`] which serves a tree of blocks from memory, for use in tests.
+
+use super::{
+    sealed::Sealed, Backend, BlockRef, CoreVersion, RuntimeVersion, StorageResponse,
+    StreamOfResults, TransactionStatus,
+};
+use crate::config::substrate::{BlakeTwo256, Digest, SubstrateHeader, H256};
+use crate::config::{Config, Header};
+use crate::error::Error;
+use crate::events::Phase;
+use crate::{Metadata, OnlineClient};
+use async_trait::async_trait;
+use codec::{Compact, Decode, Encode};
+use futures::stream;
+use scale_encode::EncodeAsType;
+use scale_value::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+/// The header type that [`MockBackend`] works with.
+pub type MockHeader = SubstrateHeader<u32, BlakeTwo256>;
+
+/// A backend holding a tree of blocks, starting with a genesis block. Blocks are added with
+/// [`MockBackend::add_block()`], and the chain of finalized blocks is chosen with
+/// [`MockBackend::finalize()`].
+pub struct MockBackend<T> {
+    state: Mutex<MockState>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+struct MockState {
+    headers: HashMap<H256, MockHeader>,
+    storage: HashMap<H256, BTreeMap<Vec<u8>, Vec<u8>>>,
+    bodies: HashMap<H256, Vec<Vec<u8>>>,
+    runtime_versions: HashMap<H256, RuntimeVersion>,
+    pending: Vec<Vec<u8>>,
+    // The finalized chain, indexed by block number.
+    finalized: Vec<H256>,
+}
+
+impl<T> MockBackend<T>
+where
+    T: Config<Hash = H256, Header = MockHeader>,
+{
+    /// A backend containing only a finalized genesis block.
+    pub fn new() -> Self {
+        let genesis = header(0, H256::zero(), 0);
+        let genesis_hash = genesis.hash();
+        MockBackend {
+            state: Mutex::new(MockState {
+                headers: HashMap::from([(genesis_hash, genesis)]),
+                storage: HashMap::new(),
+                bodies: HashMap::new(),
+                runtime_versions: HashMap::new(),
+                pending: Vec::new(),
+                finalized: vec![genesis_hash],
+            }),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The hash of the genesis block.
+    pub fn genesis(&self) -> H256 {
+        self.state.lock().unwrap().finalized[0]
+    }
+
+    /// Add a block on top of the given parent, returning its header. Children of the same
+    /// parent must be given a different `fork` to tell them apart.
+    pub fn add_block(&self, parent: H256, fork: u64) -> MockHeader {
+        let mut state = self.state.lock().unwrap();
+        let number = state.headers[&parent].number + 1;
+        let header = header(number, parent, fork);
+        state.headers.insert(header.hash(), header.clone());
+        header
+    }
+
+    /// Add a chain of `len` blocks on top of the given parent, returning their headers.
+    pub fn add_chain(&self, parent: H256, len: usize, fork: u64) -> Vec<MockHeader> {
+        let mut headers: Vec<MockHeader> = Vec::with_capacity(len);
+        for _ in 0..len {
+            let parent = headers.last().map_or(parent, |h| h.hash());
+            headers.push(self.add_block(parent, fork));
+        }
+        headers
+    }
+
+    /// Finalize the given block, and with it all of its ancestors.
+    pub fn finalize(&self, hash: H256) {
+        let mut state = self.state.lock().unwrap();
+        let mut finalized = vec![hash];
+        while let Some(parent) = state.headers.get(finalized.last().unwrap()) {
+            if parent.number == 0 {
+                break;
+            }
+            finalized.push(parent.parent_hash);
+        }
+        finalized.reverse();
+        state.finalized = finalized;
+    }
+
+    /// Set the value of a storage entry at the given block.
+    pub fn set_storage(&self, at: H256, key: Vec<u8>, value: Vec<u8>) {
+        let mut state = self.state.lock().unwrap();
+        state.storage.entry(at).or_default().insert(key, value);
+    }
+
+    /// Set the events emitted in the given block, as `(phase, event, topics)`. Each event is a
+    /// value of the outer event enum of the metadata, ie the pallet variant wrapping the event
+    /// variant.
+    pub fn set_events(
+        &self,
+        at: H256,
+        metadata: &Metadata,
+        events: Vec<(Phase, Value, Vec<H256>)>,
+    ) {
+        let mut key = sp_crypto_hashing::twox_128(b"System").to_vec();
+        key.extend(sp_crypto_hashing::twox_128(b"Events"));
+        self.set_storage(at, key, encode_events(metadata, events));
+    }
+
+    /// Set the runtime version of the given block and the blocks built on top of it, which is
+    /// otherwise that of its parent. The genesis block has spec and transaction version 1.
+    pub fn set_runtime_version(&self, at: H256, runtime_version: RuntimeVersion) {
+        let mut state = self.state.lock().unwrap();
+        state.runtime_versions.insert(at, runtime_version);
+    }
+
+    // The runtime version of the given block.
+    fn runtime_version(&self, at: H256) -> Result<RuntimeVersion, Error> {
+        let state = self.state.lock().unwrap();
+        let mut hash = at;
+        loop {
+            if let Some(version) = state.runtime_versions.get(&hash) {
+                return Ok(*version);
+            }
+            match state.headers.get(&hash) {
+                Some(header) if header.number > 0 => hash = header.parent_hash,
+                Some(_) => return Ok(GENESIS_RUNTIME_VERSION),
+                None => {
+                    return Err(Error::Other(format!(
+                        "Block {at:?} is not known to the MockBackend"
+                    )))
+                }
+            }
+        }
+    }
+
+    /// Set the extrinsics in the body of the given block. Bodies are otherwise empty.
+    pub fn set_body(&self, at: H256, extrinsics: Vec<Vec<u8>>) {
+        self.state.lock().unwrap().bodies.insert(at, extrinsics);
+    }
+
+    /// Set the extrinsics in the transaction pool.
+    pub fn set_pending_extrinsics(&self, extrinsics: Vec<Vec<u8>>) {
+        self.state.lock().unwrap().pending = extrinsics;
+    }
+
+    // The storage entries at the given block which satisfy the predicate, in key order.
+    fn storage_entries(
+        &self,
+        at: H256,
+        mut f: impl FnMut(&[u8]) -> bool,
+    ) -> Result<Vec<StorageResponse>, Error> {
+        let state = self.state.lock().unwrap();
+        if !state.headers.contains_key(&at) {
+            return Err(Error::Other(format!(
+                "Block {at:?} is not known to the MockBackend"
+            )));
+        }
+        let entries = state.storage.get(&at).into_iter().flatten();
+        Ok(entries
+            .filter(|(key, _)| f(key))
+            .map(|(key, value)| StorageResponse {
+                key: key.clone(),
+                value: value.clone(),
+            })
+            .collect())
+    }
+
+    /// An [`OnlineClient`] using this backend, with the metadata given and the runtime version
+    /// of the latest finalized block.
+    pub fn client(self: &Arc<Self>, metadata: Metadata) -> OnlineClient<T> {
+        let finalized = *self.state.lock().unwrap().finalized.last().unwrap();
+        let runtime_version = self.runtime_version(finalized).unwrap();
+        OnlineClient::from_backend_with(self.genesis(), runtime_version, metadata, self.clone())
+            .expect("building a client from a backend is infallible")
+    }
+}
+
+const GENESIS_RUNTIME_VERSION: RuntimeVersion = RuntimeVersion {
+    spec_version: 1,
+    transaction_version: 1,
+};
+
+/// Build a header. The `fork` is used as the state root, so that blocks with the same parent
+/// and number can be told apart.
+fn header(number: u32, parent_hash: H256, fork: u64) -> MockHeader {
+    SubstrateHeader {
+        parent_hash,
+        number,
+        state_root: H256::from_low_u64_be(fork),
+        extrinsics_root: H256::zero(),
+        digest: Digest::default(),
+    }
+}
+
+/// Load some metadata to use in tests.
+pub fn metadata() -> Metadata {
+    load_metadata("../artifacts/polkadot_metadata_small.scale")
+}
+
+/// Load the metadata of every pallet in the Polkadot runtime, for tests which need pallets
+/// that [`metadata()`] leaves out.
+pub fn full_metadata() -> Metadata {
+    load_metadata("../artifacts/polkadot_metadata_full.scale")
+}
+
+fn load_metadata(path: &str) -> Metadata {
+    let bytes = std::fs::read(path).unwrap();
+    Metadata::decode(&mut &*bytes).unwrap()
+}
+
+// Encode events in the format that they are stored in `System::Events`.
+fn encode_events(metadata: &Metadata, events: Vec<(Phase, Value, Vec<H256>)>) -> Vec<u8> {
+    let event_ty = metadata.outer_enums().event_enum_ty();
+    let mut bytes = Compact(events.len() as u32).encode();
+    for (phase, event, topics) in events {
+        phase.encode_to(&mut bytes);
+        event
+            .encode_as_type_to(event_ty, metadata.types(), &mut bytes)
+            .expect("event should be valid for the metadata");
+        topics.encode_to(&mut bytes);
+    }
+    bytes
+}
+
+fn unsupported<R>(method: &str) -> Result<R, Error> {
+    Err(Error::UnsupportedMethod(method.to_owned()))
+}
+
+impl<T> Sealed for MockBackend<T> {}
+
+#[async_trait]
+impl<T> Backend<T> for MockBackend<T>
+where
+    T: Config<Hash = H256, Header = MockHeader>,
+{
+    async fn storage_fetch_values(
+        &self,
+        keys: Vec<Vec<u8>>,
+        at: T::Hash,
+    ) -> Result<StreamOfResults<StorageResponse>, Error> {
+        let values = self.storage_entries(at, |key| keys.iter().any(|k| k == key))?;
+        Ok(StreamOfResults::new(Box::pin(stream::iter(
+            values.into_iter().map(Ok),
+        ))))
+    }
+
+    async fn storage_fetch_descendant_keys(
+        &self,
+        key: Vec<u8>,
+        at: T::Hash,
+    ) -> Result<StreamOfResults<Vec<u8>>, Error> {
+        let values = self.storage_entries(at, |k| k.starts_with(&key))?;
+        Ok(StreamOfResults::new(Box::pin(stream::iter(
+            values.into_iter().map(|kv| Ok(kv.key)),
+        ))))
+    }
+
+    async fn storage_fetch_descendant_values(
+        &self,
+        key: Vec<u8>,
+        at: T::Hash,
+    ) -> Result<StreamOfResults<StorageResponse>, Error> {
+        let values = self.storage_entries(at, |k| k.starts_with(&key))?;
+        Ok(StreamOfResults::new(Box::pin(stream::iter(
+            values.into_iter().map(Ok),
+        ))))
+    }
+
+    async fn storage_fetch_descendant_values_from(
+        &self,
+        key: Vec<u8>,
+        start_key: Vec<u8>,
+        at: T::Hash,
+    ) -> Result<StreamOfResults<StorageResponse>, Error> {
+        let values = self.storage_entries(at, |k| k.starts_with(&key) && *k > *start_key)?;
+        Ok(StreamOfResults::new(Box::pin(stream::iter(
+            values.into_iter().map(Ok),
+        ))))
+    }
+
+    async fn genesis_hash(&self) -> Result<T::Hash, Error> {
+        Ok(self.genesis())
+    }
+
+    async fn block_header(&self, at: T::Hash) -> Result<Option<T::Header>, Error> {
+        Ok(self.state.lock().unwrap().headers.get(&at).cloned())
+    }
+
+    async fn block_hash_at_number(&self, number: u64) -> Result<Option<T::Hash>, Error> {
+        let state = self.state.lock().unwrap();
+        Ok(usize::try_from(number)
+            .ok()
+            .and_then(|n| state.finalized.get(n))
+            .copied())
+    }
+
+    async fn block_body(&self, at: T::Hash) -> Result<Option<Vec<Vec<u8>>>, Error> {
+        let state = self.state.lock().unwrap();
+        if !state.headers.contains_key(&at) {
+            return Ok(None);
+        }
+        Ok(Some(state.bodies.get(&at).cloned().unwrap_or_default()))
+    }
+
+    async fn latest_finalized_block_ref(&self) -> Result<BlockRef<T::Hash>, Error> {
+        let state = self.state.lock().unwrap();
+        Ok(BlockRef::from_hash(*state.finalized.last().unwrap()))
+    }
+
+    async fn current_runtime_version(&self) -> Result<RuntimeVersion, Error> {
+        unsupported("current_runtime_version")
+    }
+
+    async fn stream_runtime_version(&self) -> Result<StreamOfResults<RuntimeVersion>, Error> {
+        unsupported("stream_runtime_version")
+    }
+
+    async fn stream_all_block_headers(
+        &self,
+    ) -> Result<StreamOfResults<(T::Header, BlockRef<T::Hash>)>, Error> {
+        unsupported("stream_all_block_headers")
+    }
+
+    async fn stream_best_block_headers(
+        &self,
+    ) -> Result<StreamOfResults<(T::Header, BlockRef<T::Hash>)>, Error> {
+        unsupported("stream_best_block_headers")
+    }
+
+    async fn stream_finalized_block_headers(
+        &self,
+    ) -> Result<StreamOfResults<(T::Header, BlockRef<T::Hash>)>, Error> {
+        unsupported("stream_finalized_block_headers")
+    }
+
+    async fn submit_transaction(
+        &self,
+        _bytes: &[u8],
+    ) -> Result<StreamOfResults<TransactionStatus<T::Hash>>, Error> {
+        unsupported("submit_transaction")
+    }
+
+    async fn pending_extrinsics(&self) -> Result<Vec<Vec<u8>>, Error> {
+        Ok(self.state.lock().unwrap().pending.clone())
+    }
+
+    async fn call(
+        &self,
+        method: &str,
+        _call_parameters: Option<&[u8]>,
+        at: T::Hash,
+    ) -> Result<Vec<u8>, Error> {
+        match method {
+            "Core_version" => Ok(CoreVersion::new(self.runtime_version(at)?).encode()),
+            _ => unsupported(method),
+        }
+    }
+}
@@ -393,10 +393,10 @@ pub struct FollowStreamFinalizedHeads<Hash: BlockHash, F> {
 
 impl<Hash: BlockHash, F> Unpin for FollowStreamFinalizedHeads<Hash, F> {}
 
-impl<Hash, F> FollowStreamFinalizedHeads<Hash, F>
+impl<Hash, F, Item> FollowStreamFinalizedHeads<Hash, F>
 where
     Hash: BlockHash,
-    F: Fn(FollowEvent<BlockRef<Hash>>) -> Vec<BlockRef<Hash>>,
+    F: Fn(FollowEvent<BlockRef<Hash>>) -> Vec<Item>,
 {
     pub fn new(stream: FollowStreamDriverSubscription<Hash>, f: F) -> Self {
         Self {
@@ -409,12 +409,12 @@ where
     }
 }
 
-impl<Hash, F> Stream for FollowStreamFinalizedHeads<Hash, F>
+impl<Hash, F, Item> Stream for FollowStreamFinalizedHeads<Hash, F>
 where
     Hash: BlockHash,
-    F: Fn(FollowEvent<BlockRef<Hash>>) -> Vec<BlockRef<Hash>>,
+    F: Fn(FollowEvent<BlockRef<Hash>>) -> Vec<Item>,
 {
-    type Item = Result<(String, Vec<BlockRef<Hash>>), Error>;
+    type Item = Result<(String, Vec<Item>), Error>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         if self.is_done {
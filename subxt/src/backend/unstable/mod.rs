@@ -23,8 +23,8 @@ use self::rpc_methods::{
     FollowEvent, MethodResponse, RuntimeEvent, StorageQuery, StorageQueryType, StorageResultType,
 };
 use crate::backend::{
-    rpc::RpcClient, utils::retry, Backend, BlockRef, BlockRefT, RuntimeVersion, StorageResponse,
-    StreamOf, StreamOfResults, TransactionStatus,
+    rpc::RpcClient, utils::retry, Backend, BlockRef, BlockRefT, ChainUpdate, RuntimeVersion,
+    StorageResponse, StreamOf, StreamOfResults, TransactionStatus,
 };
 use crate::config::BlockHash;
 use crate::error::{Error, RpcError};
@@ -297,6 +297,28 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for UnstableBackend<T> {
         .await
     }
 
+    async fn storage_fetch_descendant_values_from(
+        &self,
+        key: Vec<u8>,
+        start_key: Vec<u8>,
+        at: T::Hash,
+    ) -> Result<StreamOfResults<StorageResponse>, Error> {
+        // The `chainHead_v1_storage` method can't start from a given key, so we fetch every
+        // value and skip over those that we don't want.
+        let values = self
+            .storage_fetch_descendant_values(key, at)
+            .await?
+            .filter(move |val| {
+                let keep = match val {
+                    Ok(val) => val.key > start_key,
+                    Err(_) => true,
+                };
+                std::future::ready(keep)
+            });
+
+        Ok(StreamOf(Box::pin(values)))
+    }
+
     async fn genesis_hash(&self) -> Result<T::Hash, Error> {
         retry(|| self.methods.chainspec_v1_genesis_hash()).await
     }
@@ -304,7 +326,26 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for UnstableBackend<T> {
     async fn block_header(&self, at: T::Hash) -> Result<Option<T::Header>, Error> {
         retry(|| async {
             let sub_id = get_subscription_id(&self.follow_handle).await?;
-            self.methods.chainhead_v1_header(&sub_id, at).await
+            match self.methods.chainhead_v1_header(&sub_id, at).await {
+                Err(e) if can_fall_back_to_archive(&e) => self
+                    .methods
+                    .archive_unstable_header(at)
+                    .await
+                    .map_err(|_| e),
+                res => res,
+            }
+        })
+        .await
+    }
+
+    async fn block_hash_at_number(&self, number: u64) -> Result<Option<T::Hash>, Error> {
+        retry(|| async {
+            let hashes = self.methods.archive_unstable_hash_by_height(number).await?;
+            // Several hashes means that the height isn't finalized yet and there are forks.
+            match &*hashes {
+                [hash] => Ok(Some(*hash)),
+                _ => Ok(None),
+            }
         })
         .await
     }
@@ -315,7 +356,18 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for UnstableBackend<T> {
 
             // Subscribe to the body response and get our operationId back.
             let follow_events = self.follow_handle.subscribe().events();
-            let status = self.methods.chainhead_v1_body(&sub_id, at).await?;
+            let status = match self.methods.chainhead_v1_body(&sub_id, at).await {
+                Ok(status) => status,
+                Err(e) if can_fall_back_to_archive(&e) => {
+                    let body = self
+                        .methods
+                        .archive_unstable_body(at)
+                        .await
+                        .map_err(|_| e)?;
+                    return Ok(body.map(|exts| exts.into_iter().map(|ext| ext.0).collect()));
+                }
+                Err(e) => return Err(e),
+            };
             let operation_id = match status {
                 MethodResponse::LimitReached => {
                     return Err(RpcError::request_rejected("limit reached").into())
@@ -492,6 +544,88 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for UnstableBackend<T> {
         .await
     }
 
+    async fn stream_chain_updates(&self) -> Result<StreamOfResults<ChainUpdate<T>>, Error> {
+        // A block to fetch the header of, and whether it's a new best or finalized block.
+        enum Update<Ref, Hash> {
+            NewBestBlock(Ref),
+            Finalized(Ref, Vec<Hash>),
+        }
+
+        let methods = self.methods.clone();
+        let follow_events =
+            FollowStreamFinalizedHeads::new(self.follow_handle.subscribe(), |ev| match ev {
+                // The latest finalized block is where we start from, and is also the best block
+                // until we're told otherwise.
+                FollowEvent::Initialized(init) => match init.finalized_block_hashes.last() {
+                    Some(block_ref) => vec![
+                        Update::NewBestBlock(block_ref.clone()),
+                        Update::Finalized(block_ref.clone(), Vec::new()),
+                    ],
+                    None => vec![],
+                },
+                FollowEvent::BestBlockChanged(ev) => vec![Update::NewBestBlock(ev.best_block_hash)],
+                FollowEvent::Finalized(ev) => {
+                    let pruned = ev.pruned_block_hashes.iter().map(|b| b.hash()).collect();
+                    match ev.finalized_block_hashes.last() {
+                        Some(block_ref) => vec![Update::Finalized(block_ref.clone(), pruned)],
+                        None => vec![],
+                    }
+                }
+                _ => vec![],
+            });
+
+        let updates = follow_events.flat_map(move |r| {
+            let methods = methods.clone();
+
+            let (sub_id, updates) = match r {
+                Ok(ev) => ev,
+                Err(e) => return Either::Left(futures::stream::once(async { Err(e) })),
+            };
+
+            Either::Right(futures::stream::iter(updates).filter_map(move |update| {
+                let methods = methods.clone();
+                let sub_id = sub_id.clone();
+
+                async move {
+                    let block_ref = match &update {
+                        Update::NewBestBlock(block_ref) | Update::Finalized(block_ref, _) => {
+                            block_ref.clone()
+                        }
+                    };
+                    let header = match methods
+                        .chainhead_v1_header(&sub_id, block_ref.hash())
+                        .await
+                        .transpose()?
+                    {
+                        Ok(header) => header,
+                        Err(e) => return Some(Err(e)),
+                    };
+
+                    Some(Ok(match update {
+                        Update::NewBestBlock(block_ref) => ChainUpdate::NewBestBlock {
+                            header,
+                            block_ref: block_ref.into(),
+                        },
+                        Update::Finalized(block_ref, pruned_block_hashes) => {
+                            ChainUpdate::Finalized {
+                                header,
+                                block_ref: block_ref.into(),
+                                pruned_block_hashes,
+                            }
+                        }
+                    }))
+                }
+            }))
+        });
+
+        Ok(StreamOf(Box::pin(updates)))
+    }
+
+    async fn pending_extrinsics(&self) -> Result<Vec<Vec<u8>>, Error> {
+        // The `transaction` methods in the new RPC spec only allow submitting transactions.
+        Err(Error::UnsupportedMethod("pending_extrinsics".into()))
+    }
+
     async fn submit_transaction(
         &self,
         extrinsic: &[u8],
@@ -716,3 +850,71 @@ async fn get_subscription_id<Hash: BlockHash>(
 
     Ok(sub_id)
 }
+
+/// The JSON-RPC error code returned when a method doesn't exist or isn't available.
+const METHOD_NOT_FOUND_CODE: i32 = -32601;
+/// The JSON-RPC error code returned by the `chainHead` methods when the block hash given
+/// isn't pinned by the subscription.
+const INVALID_BLOCK_HASH_CODE: i32 = -32801;
+
+/// Blocks which aren't pinned by our `chainHead_v1_follow` subscription (for instance older
+/// blocks) can't be accessed using the `chainHead` methods. If the node tells us that the
+/// block hash is invalid, or that the `chainHead` method isn't supported, we try the `archive`
+/// methods instead, returning the original error if they aren't available either. Any other
+/// error is returned as is.
+fn can_fall_back_to_archive(e: &Error) -> bool {
+    matches!(
+        json_rpc_error_code(e),
+        Some(METHOD_NOT_FOUND_CODE | INVALID_BLOCK_HASH_CODE)
+    )
+}
+
+/// The code of the JSON-RPC error that the node responded with, if this is one.
+#[cfg(feature = "jsonrpsee")]
+fn json_rpc_error_code(e: &Error) -> Option<i32> {
+    let Error::Rpc(RpcError::ClientError(e)) = e else {
+        return None;
+    };
+    match e.downcast_ref::<jsonrpsee::core::client::Error>()? {
+        jsonrpsee::core::client::Error::Call(e) => Some(e.code()),
+        _ => None,
+    }
+}
+
+/// The code of the JSON-RPC error that the node responded with, if this is one.
+#[cfg(not(feature = "jsonrpsee"))]
+fn json_rpc_error_code(_e: &Error) -> Option<i32> {
+    None
+}
+
+#[cfg(all(test, feature = "jsonrpsee"))]
+mod test {
+    use super::*;
+    use jsonrpsee::types::ErrorObjectOwned;
+
+    fn call_error(code: i32) -> Error {
+        let e = jsonrpsee::core::client::Error::Call(ErrorObjectOwned::owned(
+            code, "error", None::<()>,
+        ));
+        RpcError::ClientError(Box::new(e)).into()
+    }
+
+    #[test]
+    fn only_falls_back_to_archive_for_unsupported_calls() {
+        assert!(can_fall_back_to_archive(&call_error(METHOD_NOT_FOUND_CODE)));
+        assert!(can_fall_back_to_archive(&call_error(
+            INVALID_BLOCK_HASH_CODE
+        )));
+
+        // Other JSON-RPC errors, such as invalid params, are returned as is:
+        assert!(!can_fall_back_to_archive(&call_error(-32602)));
+        assert!(!can_fall_back_to_archive(&call_error(-32603)));
+        assert!(!can_fall_back_to_archive(
+            &RpcError::request_rejected("limit reached").into()
+        ));
+        assert!(!can_fall_back_to_archive(
+            &RpcError::DisconnectedWillReconnect("reconnecting".into()).into()
+        ));
+        assert!(!can_fall_back_to_archive(&Error::Other("oops".into())));
+    }
+}
@@ -294,6 +294,44 @@ impl<T: Config> UnstableRpcMethods<T> {
             .request("transaction_v1_stop", rpc_params![operation_id])
             .await
     }
+
+    /// Return the hashes of all of the blocks at the given height, using the
+    /// `archive_unstable_hashByHeight` method. Heights at or below the latest finalized block
+    /// have exactly one hash, while higher ones may have several (one per fork) or none.
+    ///
+    /// Unlike the `chainHead` methods, this works for any block that the node still has,
+    /// but it is only available on nodes that expose the `archive` methods.
+    pub async fn archive_unstable_hash_by_height(
+        &self,
+        height: u64,
+    ) -> Result<Vec<T::Hash>, Error> {
+        self.client
+            .request("archive_unstable_hashByHeight", rpc_params![height])
+            .await
+    }
+
+    /// Get the header of any block that the node still has, using the
+    /// `archive_unstable_header` method. This doesn't require the block to be pinned.
+    pub async fn archive_unstable_header(&self, hash: T::Hash) -> Result<Option<T::Header>, Error> {
+        // header returned as hex encoded SCALE encoded bytes.
+        let header: Option<Bytes> = self
+            .client
+            .request("archive_unstable_header", rpc_params![hash])
+            .await?;
+
+        let header = header
+            .map(|h| codec::Decode::decode(&mut &*h.0))
+            .transpose()?;
+        Ok(header)
+    }
+
+    /// Get the extrinsics in the body of any block that the node still has, using the
+    /// `archive_unstable_body` method. This doesn't require the block to be pinned.
+    pub async fn archive_unstable_body(&self, hash: T::Hash) -> Result<Option<Vec<Bytes>>, Error> {
+        self.client
+            .request("archive_unstable_body", rpc_params![hash])
+            .await
+    }
 }
 
 /// This represents events generated by the `follow` method.
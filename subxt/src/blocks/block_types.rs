@@ -4,7 +4,7 @@
 
 use crate::{
     backend::BlockRef,
-    blocks::Extrinsics,
+    blocks::{DecodedExtrinsic, Extrinsics},
     client::{OfflineClientT, OnlineClientT},
     config::{Config, Header},
     error::{BlockError, DecodeError, Error},
@@ -92,6 +92,23 @@ where
         )?)
     }
 
+    /// Fetch and decode the extrinsics in the block body, joining each with its call, signer,
+    /// nonce, tip, dispatch info, result and the fee that it paid. This fetches the events for
+    /// the block from the node, too, if they haven't already been fetched.
+    pub async fn decoded(&self) -> Result<Vec<DecodedExtrinsic<T, C>>, Error>
+    where
+        T::Address: Decode,
+    {
+        let metadata = self.client.metadata();
+        let extrinsics = self.extrinsics().await?;
+
+        let mut decoded = Vec::with_capacity(extrinsics.len());
+        for details in extrinsics.iter() {
+            decoded.push(DecodedExtrinsic::new(details?, &metadata).await?);
+        }
+        Ok(decoded)
+    }
+
     /// Work with storage.
     pub fn storage(&self) -> Storage<T, C> {
         Storage::new(self.client.clone(), self.block_ref.clone())
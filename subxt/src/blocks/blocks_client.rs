@@ -2,17 +2,21 @@
 // This file is dual-licensed as Apache-2.0 or GPL-3.0.
 // see LICENSE for license details.
 
-use super::Block;
+use super::{
+    canonical_blocks::{canonical_block_stream, CanonicalBlockEvent},
+    Block,
+};
 use crate::{
     backend::{BlockRef, StreamOfResults},
     client::OnlineClientT,
-    config::Config,
+    config::{Config, Header},
     error::{BlockError, Error},
     utils::PhantomDataSendSync,
 };
 use derive_where::derive_where;
-use futures::StreamExt;
+use futures::{stream, StreamExt};
 use std::future::Future;
+use std::ops::{Bound, RangeBounds};
 
 type BlockStream<T> = StreamOfResults<T>;
 type BlockStreamRes<T> = Result<BlockStream<T>, Error>;
@@ -60,6 +64,75 @@ where
         self.at_or_latest(None)
     }
 
+    /// Obtain block details of the finalized block with the given number.
+    ///
+    /// Returns [`BlockError::NumberNotFound`] if no block with this number has been
+    /// finalized yet.
+    ///
+    /// # Warning
+    ///
+    /// As with [`Self::at()`], this call only supports blocks produced since the most recent
+    /// runtime upgrade. When using the `UnstableBackend`, older blocks can only be found if the
+    /// node exposes the `archive` RPC methods.
+    pub fn at_number(
+        &self,
+        number: u64,
+    ) -> impl Future<Output = Result<Block<T, Client>, Error>> + Send + 'static {
+        let client = self.client.clone();
+        async move {
+            let latest_number = latest_finalized_number(&client).await?;
+            if number > latest_number {
+                return Err(BlockError::NumberNotFound(number).into());
+            }
+            block_at_number(client, number).await
+        }
+    }
+
+    /// Obtain the finalized blocks with numbers in the given range, in order. A range with no
+    /// upper bound ends at the latest finalized block.
+    ///
+    /// The returned [`BlockRange`] can be configured with the number of blocks to fetch
+    /// concurrently, and then turned into a stream of blocks with [`BlockRange::stream()`].
+    /// Blocks are only fetched as the stream is polled.
+    ///
+    /// ```rust,no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use subxt::{OnlineClient, PolkadotConfig};
+    ///
+    /// let api = OnlineClient::<PolkadotConfig>::new().await?;
+    ///
+    /// let mut blocks = api.blocks().range(1000..=2000).concurrency(16).stream().await?;
+    /// while let Some(block) = blocks.next().await {
+    ///     let block = block?;
+    ///     println!("Block #{}: {}", block.number(), block.hash());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn range(&self, range: impl RangeBounds<u64>) -> BlockRange<T, Client> {
+        let start = match range.start_bound() {
+            Bound::Included(n) => *n,
+            Bound::Excluded(n) => n.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(n) => Some(*n),
+            // An empty range like `0..0` ends before it starts:
+            Bound::Excluded(0) => return BlockRange::empty(self.client.clone()),
+            Bound::Excluded(n) => Some(n - 1),
+            Bound::Unbounded => None,
+        };
+
+        BlockRange {
+            client: self.client.clone(),
+            start,
+            end,
+            concurrency: BlockRange::<T, Client>::DEFAULT_CONCURRENCY,
+            _marker: PhantomDataSendSync::new(),
+        }
+    }
+
     /// Obtain block details given the provided block hash, or the latest block if `None` is
     /// provided.
     fn at_or_latest(
@@ -117,6 +190,28 @@ where
         })
     }
 
+    /// Subscribe to changes to the canonical chain; the chain ending in the current best block.
+    ///
+    /// Each new best block is reported as [`CanonicalBlockEvent::Enacted`]. When the best block
+    /// moves to another fork, the blocks that are no longer part of the canonical chain are
+    /// first reported as [`CanonicalBlockEvent::Retracted`] (newest first), followed by the
+    /// blocks of the new fork (oldest first). This allows state derived from blocks to be
+    /// rolled back when a reorg happens.
+    ///
+    /// Blocks that have been finalized can no longer be retracted, and so aren't tracked.
+    pub fn subscribe_canonical(
+        &self,
+    ) -> impl Future<Output = BlockStreamRes<CanonicalBlockEvent<T, Client>>> + Send + 'static
+    where
+        Client: Send + Sync + 'static,
+    {
+        let client = self.client.clone();
+        async move {
+            let updates = client.backend().stream_chain_updates().await?;
+            Ok(canonical_block_stream(client, updates))
+        }
+    }
+
     /// Subscribe to finalized blocks.
     pub fn subscribe_finalized(
         &self,
@@ -132,6 +227,102 @@ where
     }
 }
 
+/// A range of finalized blocks to fetch, obtained from [`BlocksClient::range()`].
+#[derive_where(Clone; Client)]
+pub struct BlockRange<T, Client> {
+    client: Client,
+    start: u64,
+    // `None` means "up to the latest finalized block".
+    end: Option<u64>,
+    concurrency: usize,
+    _marker: PhantomDataSendSync<T>,
+}
+
+impl<T, Client> BlockRange<T, Client> {
+    /// The number of blocks that are fetched concurrently by default.
+    pub const DEFAULT_CONCURRENCY: usize = 8;
+
+    fn empty(client: Client) -> Self {
+        BlockRange {
+            client,
+            start: 1,
+            end: Some(0),
+            concurrency: Self::DEFAULT_CONCURRENCY,
+            _marker: PhantomDataSendSync::new(),
+        }
+    }
+
+    /// Set the maximum number of blocks to fetch concurrently. Blocks are always handed back
+    /// in order, and no more than this many are fetched ahead of the one being waited on.
+    ///
+    /// A value of 0 is treated as 1.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+}
+
+impl<T, Client> BlockRange<T, Client>
+where
+    T: Config,
+    Client: OnlineClientT<T>,
+{
+    /// Return a stream of the blocks in this range, in order.
+    ///
+    /// Returns [`BlockError::NumberNotFound`] if the end of the range has not been finalized yet.
+    pub async fn stream(self) -> Result<BlockStream<Block<T, Client>>, Error> {
+        let latest_number = latest_finalized_number(&self.client).await?;
+        let end = match self.end {
+            Some(end) if end > latest_number => {
+                return Err(BlockError::NumberNotFound(end).into());
+            }
+            Some(end) => end,
+            None => latest_number,
+        };
+
+        let client = self.client;
+        let blocks = stream::iter(self.start..=end)
+            .map(move |number| block_at_number(client.clone(), number))
+            // `buffered` keeps the output ordered, and only polls the next blocks once there
+            // is room, so a slow consumer holds back fetching.
+            .buffered(self.concurrency);
+
+        Ok(StreamOfResults::new(Box::pin(blocks)))
+    }
+}
+
+/// Fetch the number of the latest finalized block.
+async fn latest_finalized_number<T: Config, Client: OnlineClientT<T>>(
+    client: &Client,
+) -> Result<u64, Error> {
+    let block_ref = client.backend().latest_finalized_block_ref().await?;
+    let header = client
+        .backend()
+        .block_header(block_ref.hash())
+        .await?
+        .ok_or_else(|| BlockError::not_found(block_ref.hash()))?;
+    Ok(header.number().into())
+}
+
+/// Fetch the block with the given number, which is expected to be finalized.
+async fn block_at_number<T: Config, Client: OnlineClientT<T>>(
+    client: Client,
+    number: u64,
+) -> Result<Block<T, Client>, Error> {
+    let hash = client
+        .backend()
+        .block_hash_at_number(number)
+        .await?
+        .ok_or(BlockError::NumberNotFound(number))?;
+    let header = client
+        .backend()
+        .block_header(hash)
+        .await?
+        .ok_or_else(|| BlockError::not_found(hash))?;
+
+    Ok(Block::new(header, BlockRef::from_hash(hash), client))
+}
+
 /// Take a promise that will return a subscription to some block headers,
 /// and return a subscription to some blocks based on this.
 async fn header_sub_fut_to_block_sub<T, Client, S>(
@@ -156,3 +347,91 @@ where
     });
     BlockStreamRes::Ok(StreamOfResults::new(Box::pin(sub)))
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::test_utils::{metadata, MockBackend};
+    use crate::config::substrate::H256;
+    use crate::{OnlineClient, SubstrateConfig};
+    use std::sync::Arc;
+
+    type Blocks = BlocksClient<SubstrateConfig, OnlineClient<SubstrateConfig>>;
+
+    // Blocks 0 to 10 are finalized, and there are two unfinalized blocks on top.
+    fn blocks() -> (Blocks, Vec<H256>) {
+        let backend = Arc::new(MockBackend::new());
+        let mut hashes = vec![backend.genesis()];
+        hashes.extend(backend.add_chain(hashes[0], 10, 0).iter().map(|h| h.hash()));
+        backend.finalize(hashes[10]);
+        backend.add_chain(hashes[10], 2, 0);
+        (backend.client(metadata()).blocks(), hashes)
+    }
+
+    async fn numbers(
+        range: BlockRange<SubstrateConfig, OnlineClient<SubstrateConfig>>,
+    ) -> Vec<u32> {
+        let blocks = range.stream().await.unwrap();
+        blocks.map(|b| b.unwrap().number()).collect().await
+    }
+
+    fn is_number_not_found(res: Result<impl Sized, Error>, number: u64) -> bool {
+        matches!(res, Err(Error::Block(BlockError::NumberNotFound(n))) if n == number)
+    }
+
+    #[tokio::test]
+    async fn range_bounds_are_respected() {
+        let (blocks, _) = blocks();
+
+        assert_eq!(numbers(blocks.range(2..5)).await, vec![2, 3, 4]);
+        assert_eq!(numbers(blocks.range(2..=5)).await, vec![2, 3, 4, 5]);
+        assert_eq!(numbers(blocks.range(..3)).await, vec![0, 1, 2]);
+        assert_eq!(numbers(blocks.range(8..)).await, vec![8, 9, 10]);
+        assert_eq!(
+            numbers(blocks.range((Bound::Excluded(2), Bound::Included(5)))).await,
+            vec![3, 4, 5]
+        );
+        assert_eq!(
+            numbers(blocks.range(..).concurrency(3)).await,
+            (0..=10).collect::<Vec<_>>()
+        );
+
+        // Empty ranges, including ones which start after they end:
+        assert!(numbers(blocks.range(0..0)).await.is_empty());
+        assert!(numbers(blocks.range(5..5)).await.is_empty());
+        assert!(
+            numbers(blocks.range((Bound::Included(5), Bound::Included(3))))
+                .await
+                .is_empty()
+        );
+        assert!(numbers(blocks.range(11..)).await.is_empty());
+        assert!(
+            numbers(blocks.range((Bound::Excluded(u64::MAX), Bound::Unbounded)))
+                .await
+                .is_empty()
+        );
+
+        // The end of the range must have been finalized:
+        assert!(is_number_not_found(blocks.range(5..=11).stream().await, 11));
+        assert!(is_number_not_found(blocks.range(5..13).stream().await, 12));
+    }
+
+    #[tokio::test]
+    async fn at_number_fetches_finalized_blocks() {
+        let (blocks, hashes) = blocks();
+
+        for number in [0, 1, 10] {
+            let block = blocks.at_number(number).await.unwrap();
+            assert_eq!(block.number() as u64, number);
+            assert_eq!(block.hash(), hashes[number as usize]);
+        }
+
+        // Blocks exist at 11 and 12, but aren't finalized yet:
+        assert!(is_number_not_found(blocks.at_number(11).await, 11));
+        assert!(is_number_not_found(blocks.at_number(12).await, 12));
+        assert!(is_number_not_found(
+            blocks.at_number(u64::MAX).await,
+            u64::MAX
+        ));
+    }
+}
@@ -0,0 +1,356 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Follow the canonical chain, reporting blocks that are added to it and
+//! blocks that are removed from it when the best block switches forks.
+
+use super::Block;
+use crate::{
+    backend::{BlockRef, ChainUpdate, StreamOfResults},
+    client::OnlineClientT,
+    config::{Config, Header},
+    error::{BlockError, Error},
+};
+use codec::{Decode, Encode};
+use futures::{stream, StreamExt};
+use std::collections::VecDeque;
+
+/// A change to the canonical chain, as reported by
+/// [`crate::blocks::BlocksClient::subscribe_canonical()`].
+pub enum CanonicalBlockEvent<T: Config, C> {
+    /// The block was added to the canonical chain. Enacted blocks are reported in
+    /// ascending order, parents before their children.
+    Enacted(Block<T, C>),
+    /// The block was removed from the canonical chain, because the best block moved to
+    /// another fork. Retracted blocks are reported newest first, and always before the
+    /// blocks from the new fork are enacted.
+    Retracted(Block<T, C>),
+}
+
+impl<T: Config, C> CanonicalBlockEvent<T, C> {
+    /// The block that this event is about.
+    pub fn block(&self) -> &Block<T, C> {
+        match self {
+            CanonicalBlockEvent::Enacted(block) | CanonicalBlockEvent::Retracted(block) => block,
+        }
+    }
+
+    /// Consume this event, returning the block that it is about.
+    pub fn into_block(self) -> Block<T, C> {
+        match self {
+            CanonicalBlockEvent::Enacted(block) | CanonicalBlockEvent::Retracted(block) => block,
+        }
+    }
+
+    /// Was the block added to the canonical chain?
+    pub fn is_enacted(&self) -> bool {
+        matches!(self, CanonicalBlockEvent::Enacted(_))
+    }
+}
+
+// A block on the canonical chain that may still be retracted.
+struct TrackedBlock<T: Config> {
+    header: T::Header,
+    block_ref: BlockRef<T::Hash>,
+    number: u64,
+    parent_hash: T::Hash,
+}
+
+impl<T: Config> TrackedBlock<T> {
+    fn new(header: T::Header, block_ref: BlockRef<T::Hash>) -> Result<Self, Error> {
+        Ok(TrackedBlock {
+            number: header.number().into(),
+            parent_hash: header.parent_hash()?,
+            header,
+            block_ref,
+        })
+    }
+
+    fn to_block<C>(&self, client: C) -> Block<T, C>
+    where
+        C: OnlineClientT<T>,
+    {
+        // Headers aren't required to be `Clone`, but they can be copied via their encoding.
+        let header = T::Header::decode(&mut &*self.header.encode())
+            .expect("header was decoded from these bytes; qed");
+        Block::new(header, self.block_ref.clone(), client)
+    }
+}
+
+// The canonical chain from (roughly) the latest finalized block up to the best block.
+struct CanonicalChain<T: Config, C> {
+    client: C,
+    // Tracked blocks, lowest number first. Each is the parent of the next.
+    blocks: VecDeque<TrackedBlock<T>>,
+    finalized_number: u64,
+}
+
+impl<T: Config, C: OnlineClientT<T>> CanonicalChain<T, C> {
+    // Move the canonical chain to the new best block, returning the resulting events.
+    async fn new_best(
+        &mut self,
+        header: T::Header,
+        block_ref: BlockRef<T::Hash>,
+    ) -> Result<Vec<CanonicalBlockEvent<T, C>>, Error> {
+        if let Some(idx) = self.position(block_ref.hash()) {
+            // A block that we already consider canonical is best again; any of its
+            // descendants that we're tracking have been retracted.
+            return Ok(self.retract_from(idx + 1));
+        }
+
+        // Walk back from the new best block until we find a block that we're tracking (the
+        // common ancestor), fetching any blocks that we've not seen on the way.
+        let lowest_number = self.blocks.front().map(|b| b.number);
+        let mut branch = vec![TrackedBlock::<T>::new(header, block_ref)?];
+        let ancestor_idx = loop {
+            let tip = branch.last().expect("branch is never empty; qed");
+            if let Some(idx) = self.position(tip.parent_hash) {
+                break Some(idx);
+            }
+            // We can't go back any further than the blocks that we're tracking.
+            if lowest_number.map_or(true, |n| tip.number <= n) || tip.number == 0 {
+                break None;
+            }
+
+            let parent_hash = tip.parent_hash;
+            let parent_header = self
+                .client
+                .backend()
+                .block_header(parent_hash)
+                .await?
+                .ok_or_else(|| BlockError::not_found(parent_hash))?;
+            branch.push(TrackedBlock::new(
+                parent_header,
+                BlockRef::from_hash(parent_hash),
+            )?);
+        };
+
+        // If we didn't find an ancestor, none of the blocks we're tracking are canonical. This
+        // only happens for the first block we see, or if there's a gap in what we're given.
+        let mut events = self.retract_from(ancestor_idx.map_or(0, |idx| idx + 1));
+
+        for block in branch.into_iter().rev() {
+            events.push(CanonicalBlockEvent::Enacted(
+                block.to_block(self.client.clone()),
+            ));
+            self.blocks.push_back(block);
+        }
+        Ok(events)
+    }
+
+    // Stop tracking blocks that are older than the latest finalized block, since they can
+    // no longer be retracted. The finalized block itself is kept as a common ancestor.
+    //
+    // Pruned blocks will never be finalized. The best block is moved off of any pruned fork
+    // before it's pruned, so we don't expect to be tracking them, but if we are then they
+    // (and anything after them) are retracted.
+    fn new_finalized(
+        &mut self,
+        header: &T::Header,
+        pruned_block_hashes: &[T::Hash],
+    ) -> Vec<CanonicalBlockEvent<T, C>> {
+        let pruned_idx = self
+            .blocks
+            .iter()
+            .position(|b| pruned_block_hashes.contains(&b.block_ref.hash()));
+        let events = match pruned_idx {
+            Some(idx) => self.retract_from(idx),
+            None => Vec::new(),
+        };
+
+        self.finalized_number = self.finalized_number.max(header.number().into());
+        while self
+            .blocks
+            .front()
+            .is_some_and(|b| b.number < self.finalized_number)
+        {
+            self.blocks.pop_front();
+        }
+        events
+    }
+
+    fn position(&self, hash: T::Hash) -> Option<usize> {
+        self.blocks.iter().rposition(|b| b.block_ref.hash() == hash)
+    }
+
+    // Retract every block from the given index onwards, newest first.
+    fn retract_from(&mut self, idx: usize) -> Vec<CanonicalBlockEvent<T, C>> {
+        self.blocks
+            .drain(idx..)
+            .rev()
+            .map(|b| {
+                let block = Block::new(b.header, b.block_ref, self.client.clone());
+                CanonicalBlockEvent::Retracted(block)
+            })
+            .collect()
+    }
+}
+
+/// Turn a stream of changes to the best and finalized blocks into a stream of changes to the
+/// canonical chain.
+pub(super) fn canonical_block_stream<T, C>(
+    client: C,
+    updates: StreamOfResults<ChainUpdate<T>>,
+) -> StreamOfResults<CanonicalBlockEvent<T, C>>
+where
+    T: Config,
+    C: OnlineClientT<T>,
+{
+    let chain = CanonicalChain {
+        client,
+        blocks: VecDeque::new(),
+        finalized_number: 0,
+    };
+
+    let events = stream::unfold((updates, chain), |(mut updates, mut chain)| async move {
+        let events = match updates.next().await? {
+            Ok(ChainUpdate::NewBestBlock { header, block_ref }) => {
+                chain.new_best(header, block_ref).await
+            }
+            Ok(ChainUpdate::Finalized {
+                header,
+                pruned_block_hashes,
+                ..
+            }) => Ok(chain.new_finalized(&header, &pruned_block_hashes)),
+            Err(e) => Err(e),
+        };
+        let events = match events {
+            Ok(events) => events.into_iter().map(Ok).collect(),
+            Err(e) => vec![Err(e)],
+        };
+        Some((stream::iter(events), (updates, chain)))
+    })
+    .flatten();
+
+    StreamOfResults::new(Box::pin(events))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::test_utils::{metadata, MockBackend, MockHeader};
+    use crate::config::substrate::H256;
+    use crate::{OnlineClient, SubstrateConfig};
+    use std::sync::Arc;
+
+    type Update = Result<ChainUpdate<SubstrateConfig>, Error>;
+
+    fn new_best(header: &MockHeader) -> Update {
+        Ok(ChainUpdate::NewBestBlock {
+            header: header.clone(),
+            block_ref: BlockRef::from_hash(header.hash()),
+        })
+    }
+
+    fn finalized(header: &MockHeader, pruned: &[&MockHeader]) -> Update {
+        Ok(ChainUpdate::Finalized {
+            header: header.clone(),
+            block_ref: BlockRef::from_hash(header.hash()),
+            pruned_block_hashes: pruned.iter().map(|h| h.hash()).collect(),
+        })
+    }
+
+    // Each event as whether it's enacted, and the hash of the block.
+    async fn events(
+        client: &OnlineClient<SubstrateConfig>,
+        updates: Vec<Update>,
+    ) -> Vec<(bool, H256)> {
+        let updates = StreamOfResults::new(Box::pin(stream::iter(updates)));
+        canonical_block_stream(client.clone(), updates)
+            .map(|ev| {
+                let ev = ev.unwrap();
+                (ev.is_enacted(), ev.block().hash())
+            })
+            .collect()
+            .await
+    }
+
+    fn enacted(headers: &[&MockHeader]) -> Vec<(bool, H256)> {
+        headers.iter().map(|h| (true, h.hash())).collect()
+    }
+
+    fn retracted(headers: &[&MockHeader]) -> Vec<(bool, H256)> {
+        headers.iter().map(|h| (false, h.hash())).collect()
+    }
+
+    // Blocks a1 to a3 build on genesis, and b2 to b4 fork off from a1.
+    fn chain() -> (
+        OnlineClient<SubstrateConfig>,
+        Vec<MockHeader>,
+        Vec<MockHeader>,
+    ) {
+        let backend = Arc::new(MockBackend::new());
+        let a = backend.add_chain(backend.genesis(), 3, 0);
+        let b = backend.add_chain(a[0].hash(), 3, 1);
+        (backend.client(metadata()), a, b)
+    }
+
+    #[tokio::test]
+    async fn reorgs_retract_and_enact_blocks() {
+        let (client, a, b) = chain();
+        let (a1, a2, a3) = (&a[0], &a[1], &a[2]);
+        let (b2, b3, b4) = (&b[0], &b[1], &b[2]);
+
+        let evs = events(
+            &client,
+            vec![
+                new_best(a1),
+                // Skipping a2 means that it's fetched from the backend:
+                new_best(a3),
+                // Moving to the other fork retracts the blocks after the common ancestor:
+                new_best(b3),
+                finalized(b2, &[a2, a3]),
+                new_best(b4),
+            ],
+        )
+        .await;
+
+        let expected = [
+            enacted(&[a1]),
+            enacted(&[a2, a3]),
+            retracted(&[a3, a2]),
+            enacted(&[b2, b3]),
+            enacted(&[b4]),
+        ]
+        .concat();
+        assert_eq!(evs, expected);
+    }
+
+    #[tokio::test]
+    async fn older_best_block_retracts_descendants() {
+        let (client, a, _) = chain();
+        let (a1, a2, a3) = (&a[0], &a[1], &a[2]);
+
+        let evs = events(
+            &client,
+            vec![new_best(a1), new_best(a3), new_best(a1), new_best(a2)],
+        )
+        .await;
+
+        let expected = [
+            enacted(&[a1]),
+            enacted(&[a2, a3]),
+            retracted(&[a3, a2]),
+            enacted(&[a2]),
+        ]
+        .concat();
+        assert_eq!(evs, expected);
+    }
+
+    #[tokio::test]
+    async fn pruned_blocks_are_retracted() {
+        let (client, a, b) = chain();
+        let (a1, a2, a3) = (&a[0], &a[1], &a[2]);
+        let b2 = &b[0];
+
+        let evs = events(
+            &client,
+            vec![new_best(a1), new_best(a3), finalized(b2, &[a2, a3])],
+        )
+        .await;
+
+        let expected = [enacted(&[a1]), enacted(&[a2, a3]), retracted(&[a3, a2])].concat();
+        assert_eq!(evs, expected);
+    }
+}
@@ -0,0 +1,409 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Decode the extrinsics in a block along with the outcome of each of them, as returned
+//! from [`crate::blocks::Block::decoded()`].
+
+use super::{ExtrinsicDetails, ExtrinsicEvents};
+use crate::{
+    client::OnlineClientT,
+    config::Config,
+    error::{DispatchError, Error},
+    events::{EventDetails, StaticEvent},
+    Metadata,
+};
+use codec::Decode;
+use scale_decode::DecodeAsType;
+use scale_value::Composite;
+
+/// An extrinsic in a block, decoded and joined with the information that the events it
+/// emitted tell us about its execution.
+pub struct DecodedExtrinsic<T: Config, C> {
+    details: ExtrinsicDetails<T, C>,
+    pallet_name: String,
+    call_name: String,
+    call_fields: Composite<u32>,
+    signer: Option<T::Address>,
+    nonce: Option<u64>,
+    tip: Option<u128>,
+    events: ExtrinsicEvents<T>,
+    dispatch_info: Option<DispatchInfo>,
+    result: Option<Result<(), DispatchError>>,
+    fee: Option<u128>,
+}
+
+impl<T, C> DecodedExtrinsic<T, C>
+where
+    T: Config,
+    T::Address: Decode,
+    C: OnlineClientT<T>,
+{
+    pub(crate) async fn new(
+        details: ExtrinsicDetails<T, C>,
+        metadata: &Metadata,
+    ) -> Result<Self, Error> {
+        let pallet_name = details.pallet_name()?.to_owned();
+        let call_name = details.variant_name()?.to_owned();
+        let call_fields = details.field_values()?;
+
+        let signer = details.address::<T::Address>()?;
+        let (nonce, tip) = details
+            .signed_extensions()
+            .map_or((None, None), |e| (e.nonce(), e.tip()));
+
+        let events = details.events().await?;
+
+        // These events may not decode into the types that we expect on every runtime (older
+        // runtimes have a different `DispatchInfo` for instance), so anything which fails to
+        // decode is treated as unknown, rather than failing to decode the whole block.
+        let mut dispatch_info = None;
+        let mut result = None;
+        let mut fee = None;
+        for ev in events.iter() {
+            let ev = ev?;
+            if is_event::<ExtrinsicSuccess>(&ev) {
+                dispatch_info = decode_event::<ExtrinsicSuccess>(&ev).map(|e| e.dispatch_info);
+                result = Some(Ok(()));
+            } else if is_event::<ExtrinsicFailed>(&ev) {
+                dispatch_info = decode_event::<ExtrinsicFailed>(&ev).map(|e| e.dispatch_info);
+                // As in `TxInBlock::wait_for_success()`, the dispatch error is the first field.
+                result = DispatchError::decode_from(ev.field_bytes(), metadata.clone())
+                    .ok()
+                    .map(Err);
+            } else if is_event::<TransactionFeePaid>(&ev) {
+                fee = decode_event::<TransactionFeePaid>(&ev).map(|e| e.actual_fee);
+            }
+        }
+
+        Ok(DecodedExtrinsic {
+            details,
+            pallet_name,
+            call_name,
+            call_fields,
+            signer,
+            nonce,
+            tip,
+            events,
+            dispatch_info,
+            result,
+            fee,
+        })
+    }
+
+    /// The account ID of the account that signed the extrinsic, or `None` if it is unsigned.
+    /// See [`ExtrinsicDetails::signer_account_id()`].
+    pub fn signer_account_id(&self) -> Result<Option<T::AccountId>, Error>
+    where
+        T::AccountId: Decode,
+    {
+        self.details.signer_account_id()
+    }
+}
+
+impl<T: Config, C> DecodedExtrinsic<T, C> {
+    /// The undecoded details of this extrinsic.
+    pub fn details(&self) -> &ExtrinsicDetails<T, C> {
+        &self.details
+    }
+
+    /// Consume this, returning the undecoded details of the extrinsic.
+    pub fn into_details(self) -> ExtrinsicDetails<T, C> {
+        self.details
+    }
+
+    /// The name of the pallet that the call belongs to.
+    pub fn pallet_name(&self) -> &str {
+        &self.pallet_name
+    }
+
+    /// The name of the call.
+    pub fn call_name(&self) -> &str {
+        &self.call_name
+    }
+
+    /// The decoded fields of the call.
+    pub fn call_fields(&self) -> &Composite<u32> {
+        &self.call_fields
+    }
+
+    /// The address of the account that signed the extrinsic, or `None` if it is unsigned.
+    pub fn signer(&self) -> Option<&T::Address> {
+        self.signer.as_ref()
+    }
+
+    /// The nonce of the extrinsic, if it is signed and the chain uses the `CheckNonce`
+    /// signed extension.
+    pub fn nonce(&self) -> Option<u64> {
+        self.nonce
+    }
+
+    /// The tip given to the block author, if the extrinsic is signed and the chain uses the
+    /// `ChargeTransactionPayment` or `ChargeAssetTxPayment` signed extensions.
+    pub fn tip(&self) -> Option<u128> {
+        self.tip
+    }
+
+    /// The events emitted by this extrinsic.
+    pub fn events(&self) -> &ExtrinsicEvents<T> {
+        &self.events
+    }
+
+    /// The dispatch info reported in the `System::ExtrinsicSuccess` or
+    /// `System::ExtrinsicFailed` event for this extrinsic. This is `None` if neither event was
+    /// found, or if the dispatch info could not be decoded.
+    pub fn dispatch_info(&self) -> Option<&DispatchInfo> {
+        self.dispatch_info.as_ref()
+    }
+
+    /// The result of dispatching the extrinsic. This is `None` if neither a
+    /// `System::ExtrinsicSuccess` nor a `System::ExtrinsicFailed` event was found, or if the
+    /// dispatch error could not be decoded.
+    pub fn result(&self) -> Option<&Result<(), DispatchError>> {
+        self.result.as_ref()
+    }
+
+    /// Did the extrinsic dispatch successfully?
+    pub fn is_success(&self) -> bool {
+        matches!(self.result, Some(Ok(())))
+    }
+
+    /// The fee actually paid for the extrinsic, including any tip, as reported by the
+    /// `TransactionPayment::TransactionFeePaid` event. This is `None` for extrinsics which
+    /// did not pay a fee, or if the event could not be decoded.
+    pub fn fee(&self) -> Option<u128> {
+        self.fee
+    }
+}
+
+/// Information about the dispatch of an extrinsic, as reported in the
+/// `System::ExtrinsicSuccess` and `System::ExtrinsicFailed` events.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, DecodeAsType)]
+pub struct DispatchInfo {
+    /// The weight used by the extrinsic.
+    pub weight: Weight,
+    /// The class of the extrinsic.
+    pub class: DispatchClass,
+    /// Whether the extrinsic pays a fee.
+    pub pays_fee: Pays,
+}
+
+/// The weight of an extrinsic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, DecodeAsType)]
+pub struct Weight {
+    /// The computational time used.
+    pub ref_time: u64,
+    /// The size of the proof needed to execute the extrinsic.
+    pub proof_size: u64,
+}
+
+/// The class of an extrinsic, which determines how its weight is accounted for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, DecodeAsType)]
+pub enum DispatchClass {
+    /// A normal extrinsic.
+    Normal,
+    /// An operational extrinsic.
+    Operational,
+    /// A mandatory extrinsic, which is always included in the block.
+    Mandatory,
+}
+
+/// Whether an extrinsic pays a fee.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, DecodeAsType)]
+pub enum Pays {
+    /// The extrinsic pays a fee.
+    Yes,
+    /// The extrinsic does not pay a fee.
+    No,
+}
+
+fn is_event<E: StaticEvent>(ev: &EventDetails<impl Config>) -> bool {
+    ev.pallet_name() == E::PALLET && ev.variant_name() == E::EVENT
+}
+
+fn decode_event<E: StaticEvent>(ev: &EventDetails<impl Config>) -> Option<E> {
+    ev.as_event::<E>().ok().flatten()
+}
+
+// The events that we decode to learn about the outcome of an extrinsic. Only the fields
+// that we need are given; the rest are ignored when decoding.
+
+#[derive(DecodeAsType)]
+struct ExtrinsicSuccess {
+    dispatch_info: DispatchInfo,
+}
+
+impl StaticEvent for ExtrinsicSuccess {
+    const PALLET: &'static str = "System";
+    const EVENT: &'static str = "ExtrinsicSuccess";
+}
+
+#[derive(DecodeAsType)]
+struct ExtrinsicFailed {
+    dispatch_info: DispatchInfo,
+}
+
+impl StaticEvent for ExtrinsicFailed {
+    const PALLET: &'static str = "System";
+    const EVENT: &'static str = "ExtrinsicFailed";
+}
+
+#[derive(DecodeAsType)]
+struct TransactionFeePaid {
+    actual_fee: u128,
+}
+
+impl StaticEvent for TransactionFeePaid {
+    const PALLET: &'static str = "TransactionPayment";
+    const EVENT: &'static str = "TransactionFeePaid";
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::test_utils::{full_metadata, MockBackend};
+    use crate::config::{substrate::H256, Header, PolkadotConfig};
+    use crate::dynamic::Value;
+    use crate::events::Phase;
+    use crate::utils::{AccountId32, MultiAddress};
+    use std::sync::Arc;
+    use subxt_signer::sr25519::dev;
+
+    fn dispatch_info(ref_time: u64, class: &str, pays_fee: &str) -> Value {
+        Value::named_composite([
+            (
+                "weight",
+                Value::named_composite([
+                    ("ref_time", Value::u128(ref_time.into())),
+                    ("proof_size", Value::u128(0)),
+                ]),
+            ),
+            ("class", Value::unnamed_variant(class, [])),
+            ("pays_fee", Value::unnamed_variant(pays_fee, [])),
+        ])
+    }
+
+    fn event(
+        index: u32,
+        pallet: &str,
+        variant: &str,
+        fields: Vec<(&str, Value)>,
+    ) -> (Phase, Value, Vec<H256>) {
+        let event = Value::unnamed_variant(pallet, [Value::named_variant(variant, fields)]);
+        (Phase::ApplyExtrinsic(index), event, vec![])
+    }
+
+    fn fee_paid(index: u32, who: &AccountId32, actual_fee: u128) -> (Phase, Value, Vec<H256>) {
+        let fields = vec![
+            ("who", Value::from_bytes(who)),
+            ("actual_fee", Value::u128(actual_fee)),
+            ("tip", Value::u128(0)),
+        ];
+        event(index, "TransactionPayment", "TransactionFeePaid", fields)
+    }
+
+    #[tokio::test]
+    async fn extrinsics_are_joined_with_their_outcome() {
+        let backend = Arc::new(MockBackend::<PolkadotConfig>::new());
+        let metadata = full_metadata();
+        let block = backend.add_block(backend.genesis(), 0).hash();
+        let client = backend.client(metadata.clone());
+
+        let (alice, bob) = (dev::alice(), dev::bob());
+        let remark =
+            |bytes: &[u8]| crate::dynamic::tx("System", "remark", vec![Value::from_bytes(bytes)]);
+        let tx = client.tx();
+        let extrinsics = [
+            tx.create_signed_offline(&remark(b"a"), &alice, Default::default())
+                .unwrap(),
+            tx.create_signed_offline(&remark(b"b"), &bob, Default::default())
+                .unwrap(),
+            tx.create_unsigned(&remark(b"c")).unwrap(),
+        ];
+        let extrinsics = extrinsics.iter().map(|e| e.encoded().to_vec()).collect();
+        backend.set_body(block, extrinsics);
+
+        let events = vec![
+            event(
+                0,
+                "System",
+                "ExtrinsicSuccess",
+                vec![("dispatch_info", dispatch_info(10, "Normal", "Yes"))],
+            ),
+            fee_paid(0, &alice.public_key().into(), 100),
+            fee_paid(1, &bob.public_key().into(), 200),
+            event(
+                1,
+                "System",
+                "ExtrinsicFailed",
+                vec![
+                    ("dispatch_error", Value::unnamed_variant("BadOrigin", [])),
+                    ("dispatch_info", dispatch_info(20, "Operational", "Yes")),
+                ],
+            ),
+            event(
+                2,
+                "System",
+                "ExtrinsicSuccess",
+                vec![("dispatch_info", dispatch_info(30, "Mandatory", "No"))],
+            ),
+        ];
+        backend.set_events(block, &metadata, events);
+
+        let decoded = client
+            .blocks()
+            .at(block)
+            .await
+            .unwrap()
+            .decoded()
+            .await
+            .unwrap();
+        assert_eq!(decoded.len(), 3);
+
+        let ext = &decoded[0];
+        assert_eq!((ext.pallet_name(), ext.call_name()), ("System", "remark"));
+        let alice_id: AccountId32 = alice.public_key().into();
+        assert_eq!(ext.signer(), Some(&MultiAddress::Id(alice_id.clone())));
+        assert_eq!(ext.signer_account_id().unwrap(), Some(alice_id));
+        assert_eq!(ext.nonce(), Some(0));
+        assert_eq!(ext.tip(), Some(0));
+        assert!(ext.is_success());
+        assert_eq!(
+            ext.dispatch_info(),
+            Some(&DispatchInfo {
+                weight: Weight {
+                    ref_time: 10,
+                    proof_size: 0
+                },
+                class: DispatchClass::Normal,
+                pays_fee: Pays::Yes,
+            })
+        );
+        assert_eq!(ext.fee(), Some(100));
+        assert_eq!(ext.events().iter().count(), 2);
+
+        let ext = &decoded[1];
+        assert_eq!(
+            ext.signer_account_id().unwrap(),
+            Some(bob.public_key().into())
+        );
+        assert!(!ext.is_success());
+        assert!(matches!(ext.result(), Some(Err(DispatchError::BadOrigin))));
+        assert_eq!(
+            ext.dispatch_info().map(|info| info.class),
+            Some(DispatchClass::Operational)
+        );
+        assert_eq!(ext.fee(), Some(200));
+
+        let ext = &decoded[2];
+        assert!(ext.signer().is_none());
+        assert_eq!(ext.signer_account_id().unwrap(), None);
+        assert_eq!((ext.nonce(), ext.tip()), (None, None));
+        assert!(ext.is_success());
+        assert_eq!(
+            ext.dispatch_info().map(|info| info.pays_fee),
+            Some(Pays::No)
+        );
+        assert_eq!(ext.fee(), None);
+    }
+}
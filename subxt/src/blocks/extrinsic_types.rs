@@ -3,13 +3,16 @@
 // see LICENSE for license details.
 
 use crate::{
+    backend::BackendExt,
     blocks::block_types::{get_events, CachedEvents},
-    client::{OfflineClientT, OnlineClientT},
-    config::{Config, Hasher},
+    client::{ClientState, OfflineClientT, OnlineClientT},
+    config::{Config, ExtrinsicParams, Hasher, Header, RefineParams, RefineParamsData},
     error::{BlockError, Error},
     events,
+    utils::Era,
 };
 
+use codec::Decode;
 use derive_where::derive_where;
 use scale_decode::DecodeAsType;
 use subxt_core::blocks::{ExtrinsicDetails as CoreExtrinsicDetails, Extrinsics as CoreExtrinsics};
@@ -129,6 +132,7 @@ where
 }
 
 /// A single extrinsic in a block.
+#[derive_where(Clone; C)]
 pub struct ExtrinsicDetails<T: Config, C> {
     inner: CoreExtrinsicDetails<T>,
     /// The block hash of this extrinsic (needed to fetch events).
@@ -248,6 +252,24 @@ where
     pub fn as_root_extrinsic<E: DecodeAsType>(&self) -> Result<E, Error> {
         self.inner.as_root_extrinsic::<E>().map_err(Into::into)
     }
+
+    /// See [`subxt_core::blocks::ExtrinsicDetails::address()`].
+    pub fn address<A: Decode>(&self) -> Result<Option<A>, Error> {
+        self.inner.address::<A>().map_err(Into::into)
+    }
+
+    /// See [`subxt_core::blocks::ExtrinsicDetails::signature()`].
+    pub fn signature<S: Decode>(&self) -> Result<Option<S>, Error> {
+        self.inner.signature::<S>().map_err(Into::into)
+    }
+
+    /// See [`subxt_core::blocks::ExtrinsicDetails::signer_account_id()`].
+    pub fn signer_account_id(&self) -> Result<Option<T::AccountId>, Error>
+    where
+        T::AccountId: Decode,
+    {
+        self.inner.signer_account_id().map_err(Into::into)
+    }
 }
 
 impl<T, C> ExtrinsicDetails<T, C>
@@ -261,6 +283,145 @@ where
         let ext_hash = T::Hasher::hash_of(&self.bytes());
         Ok(ExtrinsicEvents::new(ext_hash, self.index(), events))
     }
+
+    /// See [`subxt_core::blocks::ExtrinsicDetails::signer_payload()`]. The client state is that
+    /// of the client that this extrinsic was obtained from, but with the runtime version of the
+    /// block that the extrinsic is in, which is fetched from the node.
+    ///
+    /// Params which are not set are refined as when signing, but from the birth block of the
+    /// extrinsic rather than the latest finalized block. In particular, the mortality checkpoint
+    /// of a mortal extrinsic is the hash of its birth block, which is fetched from the node, so
+    /// default params can be given for extrinsics signed with the default params.
+    pub async fn signer_payload(
+        &self,
+        params: <T::ExtrinsicParams as ExtrinsicParams<T>>::Params,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let client_state = self.client_state_at_block().await?;
+        let params = self.refine_params_at_birth(params).await?;
+        self.inner
+            .signer_payload(&client_state, params)
+            .map_err(Into::into)
+    }
+
+    /// See [`subxt_core::blocks::ExtrinsicDetails::verify_signature()`]. The signer payload is
+    /// rebuilt as described in [`ExtrinsicDetails::signer_payload()`].
+    pub async fn verify_signature<F>(
+        &self,
+        params: <T::ExtrinsicParams as ExtrinsicParams<T>>::Params,
+        verify: F,
+    ) -> Result<bool, Error>
+    where
+        T::AccountId: Decode,
+        T::Signature: Decode,
+        F: FnOnce(&T::Signature, &[u8], &T::AccountId) -> bool,
+    {
+        let client_state = self.client_state_at_block().await?;
+        let params = self.refine_params_at_birth(params).await?;
+        self.inner
+            .verify_signature(&client_state, params, verify)
+            .map_err(Into::into)
+    }
+
+    /// The spec version that this extrinsic was signed against, which is that of the block it
+    /// is in. See [`subxt_core::blocks::ExtrinsicSignedExtensions::has_check_spec_version()`].
+    ///
+    /// Returns `None` if the extrinsic is unsigned or has no CheckSpecVersion signed extension.
+    pub async fn spec_version(&self) -> Result<Option<u32>, Error> {
+        let Some(signed_extensions) = self.signed_extensions() else {
+            return Ok(None);
+        };
+        if !signed_extensions.has_check_spec_version() {
+            return Ok(None);
+        }
+        let runtime_version = self.client.backend().runtime_version_at(self.block_hash);
+        Ok(Some(runtime_version.await?.spec_version))
+    }
+
+    /// The transaction version that this extrinsic was signed against, which is that of the
+    /// block it is in. See [`subxt_core::blocks::ExtrinsicSignedExtensions::has_check_tx_version()`].
+    ///
+    /// Returns `None` if the extrinsic is unsigned or has no CheckTxVersion signed extension.
+    pub async fn transaction_version(&self) -> Result<Option<u32>, Error> {
+        let Some(signed_extensions) = self.signed_extensions() else {
+            return Ok(None);
+        };
+        if !signed_extensions.has_check_tx_version() {
+            return Ok(None);
+        }
+        let runtime_version = self.client.backend().runtime_version_at(self.block_hash);
+        Ok(Some(runtime_version.await?.transaction_version))
+    }
+
+    /// Has this extrinsic expired by the block `block_number`? See
+    /// [`subxt_core::blocks::ExtrinsicSignedExtensions::is_expired_at()`]; the extrinsic is
+    /// valid at the block that it is in, whose number is fetched from the node.
+    ///
+    /// Returns `None` if the extrinsic is unsigned, or if its era was not found or decoding
+    /// failed.
+    pub async fn is_expired_at(&self, block_number: u64) -> Result<Option<bool>, Error> {
+        let Some(signed_extensions) = self.signed_extensions() else {
+            return Ok(None);
+        };
+        let valid_at = self.block_number().await?;
+        Ok(signed_extensions.is_expired_at(block_number, valid_at))
+    }
+
+    // Refine the params with the birth block of the extrinsic, which is the block that the
+    // mortality checkpoint refers to. Immortal extrinsics are born at the genesis block.
+    async fn refine_params_at_birth(
+        &self,
+        mut params: <T::ExtrinsicParams as ExtrinsicParams<T>>::Params,
+    ) -> Result<<T::ExtrinsicParams as ExtrinsicParams<T>>::Params, Error> {
+        let Some(signed_extensions) = self.signed_extensions() else {
+            return Ok(params);
+        };
+        let (birth_number, birth_hash) = match signed_extensions.era() {
+            Some(era @ Era::Mortal { .. }) => {
+                let birth_number = era.birth(self.block_number().await?);
+                let birth_hash = self
+                    .client
+                    .backend()
+                    .block_hash_at_number(birth_number)
+                    .await?
+                    .ok_or(BlockError::NumberNotFound(birth_number))?;
+                (birth_number, birth_hash)
+            }
+            _ => (0, self.client.genesis_hash()),
+        };
+        let account_nonce = signed_extensions.nonce().unwrap_or(0);
+        params.refine(&RefineParamsData::new(
+            account_nonce,
+            birth_number,
+            birth_hash,
+        ));
+        Ok(params)
+    }
+
+    // The number of the block that this extrinsic is in.
+    async fn block_number(&self) -> Result<u64, Error> {
+        let header = self
+            .client
+            .backend()
+            .block_header(self.block_hash)
+            .await?
+            .ok_or_else(|| BlockError::not_found(self.block_hash))?;
+        Ok(header.number().into())
+    }
+
+    // The extrinsic was signed against the runtime version of its block, which may be older
+    // than the one that the client is using now.
+    async fn client_state_at_block(&self) -> Result<ClientState<T>, Error> {
+        let runtime_version = self
+            .client
+            .backend()
+            .runtime_version_at(self.block_hash)
+            .await?;
+        Ok(ClientState {
+            genesis_hash: self.client.genesis_hash(),
+            runtime_version,
+            metadata: self.client.metadata(),
+        })
+    }
 }
 
 /// A Static Extrinsic found in a block coupled with it's details.
@@ -361,3 +522,170 @@ impl<T: Config> ExtrinsicEvents<T> {
         Ok(self.find::<Ev>().next().transpose()?.is_some())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::ExtrinsicDetails;
+    use crate::backend::test_utils::{metadata, MockBackend};
+    use crate::client::{OfflineClient, OfflineClientT, OnlineClient, RuntimeVersion};
+    use crate::config::polkadot::PolkadotExtrinsicParamsBuilder;
+    use crate::config::{Header, PolkadotConfig};
+    use crate::dynamic::Value;
+    use std::sync::Arc;
+    use subxt_signer::{sr25519::dev, verify_multi_signature};
+
+    type Details = ExtrinsicDetails<PolkadotConfig, OnlineClient<PolkadotConfig>>;
+
+    const OLD_VERSION: RuntimeVersion = RuntimeVersion {
+        spec_version: 1,
+        transaction_version: 1,
+    };
+    const NEW_VERSION: RuntimeVersion = RuntimeVersion {
+        spec_version: 2,
+        transaction_version: 3,
+    };
+
+    // A client whose runtime has been upgraded since a block containing a signed extrinsic
+    // and then an unsigned one, which are handed back.
+    async fn extrinsics_from_before_an_upgrade() -> (OnlineClient<PolkadotConfig>, Vec<Details>) {
+        let backend = Arc::new(MockBackend::<PolkadotConfig>::new());
+        let metadata = metadata();
+        let old = backend.add_block(backend.genesis(), 0).hash();
+        let new = backend.add_block(old, 0).hash();
+        backend.set_runtime_version(old, OLD_VERSION);
+        backend.set_runtime_version(new, NEW_VERSION);
+        backend.finalize(new);
+
+        let old_client =
+            OfflineClient::<PolkadotConfig>::new(backend.genesis(), OLD_VERSION, metadata.clone());
+        let remark = crate::dynamic::tx("System", "remark", vec![Value::from_bytes(b"hi")]);
+        let signed = old_client
+            .tx()
+            .create_signed_offline(&remark, &dev::alice(), Default::default())
+            .unwrap();
+        let unsigned = old_client.tx().create_unsigned(&remark).unwrap();
+        let body = vec![signed.encoded().to_vec(), unsigned.encoded().to_vec()];
+        backend.set_body(old, body);
+
+        let client = backend.client(metadata);
+        assert_eq!(client.runtime_version(), NEW_VERSION);
+        let extrinsics = client.blocks().at(old).await.unwrap().extrinsics().await;
+        let extrinsics = extrinsics
+            .unwrap()
+            .iter()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        (client, extrinsics)
+    }
+
+    #[tokio::test]
+    async fn signatures_are_verified_against_the_runtime_version_of_their_block() {
+        let (client, extrinsics) = extrinsics_from_before_an_upgrade().await;
+        let extrinsic = &extrinsics[0];
+
+        let is_valid = extrinsic
+            .verify_signature(Default::default(), verify_multi_signature)
+            .await
+            .unwrap();
+        assert!(is_valid);
+
+        // Using the runtime version that the client has now leads to a different payload:
+        let payload = extrinsic.signer_payload(Default::default()).await.unwrap();
+        let payload_now = extrinsic
+            .inner
+            .signer_payload(&client.client_state(), Default::default())
+            .unwrap();
+        assert_ne!(payload, payload_now);
+    }
+
+    #[tokio::test]
+    async fn signatures_of_mortal_extrinsics_are_verified_against_their_birth_block() {
+        let backend = Arc::new(MockBackend::<PolkadotConfig>::new());
+        let metadata = metadata();
+        let genesis = backend.genesis();
+        let headers = backend.add_chain(genesis, 4, 0);
+        backend.finalize(headers[3].hash());
+        let client = backend.client(metadata);
+
+        // Born at block 2 (the start of a period of 4 blocks) and included in block 3.
+        let remark = crate::dynamic::tx("System", "remark", vec![Value::from_bytes(b"hi")]);
+        let params = PolkadotExtrinsicParamsBuilder::new()
+            .mortal(&headers[1], 4)
+            .build();
+        let signed = client
+            .tx()
+            .create_signed_offline(&remark, &dev::alice(), params)
+            .unwrap();
+        backend.set_body(headers[2].hash(), vec![signed.encoded().to_vec()]);
+
+        let extrinsics = client.blocks().at(headers[2].hash()).await.unwrap();
+        let extrinsics = extrinsics.extrinsics().await.unwrap();
+        let extrinsic = extrinsics.iter().next().unwrap().unwrap();
+        let is_valid = extrinsic
+            .verify_signature(Default::default(), verify_multi_signature)
+            .await
+            .unwrap();
+        assert!(is_valid);
+
+        // Without the birth block, the checkpoint is the genesis hash and the payload differs:
+        let payload = extrinsic.signer_payload(Default::default()).await.unwrap();
+        let immortal_payload = extrinsic
+            .inner
+            .signer_payload(&client.client_state(), Default::default())
+            .unwrap();
+        assert_ne!(payload, immortal_payload);
+    }
+
+    #[tokio::test]
+    async fn versions_signed_against_are_those_of_the_block() {
+        let (_, extrinsics) = extrinsics_from_before_an_upgrade().await;
+
+        let signed = &extrinsics[0];
+        assert_eq!(signed.spec_version().await.unwrap(), Some(1));
+        assert_eq!(signed.transaction_version().await.unwrap(), Some(1));
+
+        let unsigned = &extrinsics[1];
+        assert_eq!(unsigned.spec_version().await.unwrap(), None);
+        assert_eq!(unsigned.transaction_version().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn extrinsics_expire_relative_to_their_block() {
+        let backend = Arc::new(MockBackend::<PolkadotConfig>::new());
+        let headers = backend.add_chain(backend.genesis(), 11, 0);
+        backend.finalize(headers[10].hash());
+        let client = backend.client(metadata());
+
+        // Born at block 6 (the start of a period of 4 blocks) and included in block 7.
+        let remark = crate::dynamic::tx("System", "remark", vec![Value::from_bytes(b"hi")]);
+        let mortal = PolkadotExtrinsicParamsBuilder::new()
+            .mortal(&headers[5], 4)
+            .build();
+        let mortal = client
+            .tx()
+            .create_signed_offline(&remark, &dev::alice(), mortal)
+            .unwrap();
+        let immortal = client
+            .tx()
+            .create_signed_offline(&remark, &dev::alice(), Default::default())
+            .unwrap();
+        let unsigned = client.tx().create_unsigned(&remark).unwrap();
+        let body = vec![
+            mortal.encoded().to_vec(),
+            immortal.encoded().to_vec(),
+            unsigned.encoded().to_vec(),
+        ];
+        backend.set_body(headers[6].hash(), body);
+
+        let extrinsics = client.blocks().at(headers[6].hash()).await.unwrap();
+        let extrinsics = extrinsics.extrinsics().await.unwrap();
+        let extrinsics: Vec<_> = extrinsics.iter().collect::<Result<_, _>>().unwrap();
+        assert_eq!(extrinsics[0].is_expired_at(9).await.unwrap(), Some(false));
+        assert_eq!(extrinsics[0].is_expired_at(10).await.unwrap(), Some(true));
+        assert_eq!(
+            extrinsics[1].is_expired_at(u64::MAX - 1).await.unwrap(),
+            Some(false)
+        );
+        assert_eq!(extrinsics[2].is_expired_at(10).await.unwrap(), None);
+    }
+}
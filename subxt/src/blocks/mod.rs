@@ -6,13 +6,17 @@
 
 mod block_types;
 mod blocks_client;
+mod canonical_blocks;
+mod decoded_block;
 mod extrinsic_types;
 
 /// A reference to a block.
 pub use crate::backend::BlockRef;
 
 pub use block_types::Block;
-pub use blocks_client::BlocksClient;
+pub use blocks_client::{BlockRange, BlocksClient};
+pub use canonical_blocks::CanonicalBlockEvent;
+pub use decoded_block::{DecodedExtrinsic, DispatchClass, DispatchInfo, Pays, Weight};
 pub use extrinsic_types::{
     ExtrinsicDetails, ExtrinsicEvents, ExtrinsicSignedExtension, ExtrinsicSignedExtensions,
     Extrinsics, FoundExtrinsic, StaticExtrinsic,
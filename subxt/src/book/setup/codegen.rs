@@ -60,7 +60,25 @@
 //! subxt codegen | rustfmt > interface.rs
 //! ```
 //!
+//! For large runtimes, the generated code can instead be split into one file per pallet (plus a
+//! `runtime_types` directory) by providing an output directory. This is easier on IDEs, and makes
+//! changes to the generated code easier to review when the metadata changes:
+//!
+//! ```shell
+//! # Generate and format rust code, saving it to `src/interface/`:
+//! subxt codegen --out-dir src/interface && rustfmt --edition=2021 src/interface/mod.rs
+//! ```
+//!
 //! Use `subxt codegen --help` for more options; many of the options available via the macro are
 //! also available via the CLI tool, such as the ability to substitute generated types for others,
 //! or strip out docs from the generated code.
 //!
+//! ## Build scripts
+//!
+//! Finally, code can be generated from a `build.rs` script using `subxt_codegen::build` (enable the
+//! `fetch-metadata` feature of `subxt-codegen`). This fetches the metadata from a node the first time
+//! that it runs and saves it alongside a lockfile recording the runtime's spec version and metadata
+//! hash. After that, code is only regenerated when those files change, and the build fails with a
+//! summary of the differences if they no longer agree. Set `SUBXT_UPDATE_METADATA=1` when building
+//! to fetch the latest metadata and update both files.
+//!
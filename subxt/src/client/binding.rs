@@ -0,0 +1,142 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use super::OnlineClientT;
+use crate::{
+    backend::{BlockRef, StreamOfResults},
+    error::Error,
+    runtime_api::{Payload, RuntimeApi, RuntimeApiClient},
+    storage::{Address, Storage, StorageClient, StorageKeyValuePair},
+    Config,
+};
+use derive_where::derive_where;
+use subxt_core::utils::Yes;
+
+/// A reference to some client, optionally pinned to a specific block. This is
+/// used by the client bindings that can be generated via the `client_bindings`
+/// option of the `#[subxt]` macro or the `--client-bindings` flag of `subxt codegen`,
+/// and will otherwise not usually be used directly.
+///
+/// If no block is given via [`Binding::at()`], calls are made against the latest
+/// finalized block at the time of calling.
+#[derive_where(Clone)]
+pub struct Binding<'client, T: Config, Client> {
+    client: &'client Client,
+    block_ref: Option<BlockRef<T::Hash>>,
+}
+
+impl<'client, T, Client> Binding<'client, T, Client>
+where
+    T: Config,
+    Client: OnlineClientT<T>,
+{
+    /// Create a new [`Binding`] to the client given.
+    pub fn new(client: &'client Client) -> Self {
+        Binding {
+            client,
+            block_ref: None,
+        }
+    }
+
+    /// Make calls against the block given rather than the latest finalized block.
+    pub fn at(mut self, block_ref: impl Into<BlockRef<T::Hash>>) -> Self {
+        self.block_ref = Some(block_ref.into());
+        self
+    }
+
+    /// The client that this is bound to.
+    pub fn client(&self) -> &'client Client {
+        self.client
+    }
+
+    /// Obtain storage at the block that this is bound to.
+    pub async fn storage(&self) -> Result<Storage<T, Client>, Error> {
+        let storage = StorageClient::new(self.client.clone());
+        match &self.block_ref {
+            Some(block_ref) => Ok(storage.at(block_ref.clone())),
+            None => storage.at_latest().await,
+        }
+    }
+
+    /// Obtain a runtime API interface at the block that this is bound to.
+    pub async fn runtime_api(&self) -> Result<RuntimeApi<T, Client>, Error> {
+        let runtime_api = RuntimeApiClient::new(self.client.clone());
+        match &self.block_ref {
+            Some(block_ref) => Ok(runtime_api.at(block_ref.clone())),
+            None => runtime_api.at_latest().await,
+        }
+    }
+
+    /// Execute a runtime API call at the block that this is bound to.
+    pub async fn call<Call: Payload>(&self, payload: Call) -> Result<Call::ReturnType, Error> {
+        self.runtime_api().await?.call(payload).await
+    }
+
+    /// Bind some storage address to this client, so that it can be fetched or iterated over.
+    pub fn bind_address<Addr: Address>(
+        &self,
+        address: Addr,
+    ) -> BoundAddress<'client, T, Client, Addr> {
+        BoundAddress {
+            binding: self.clone(),
+            address,
+        }
+    }
+}
+
+/// A storage address which is bound to some client, and so can be used to
+/// fetch or iterate over the storage entries that it points to.
+#[derive_where(Clone; Addr)]
+pub struct BoundAddress<'client, T: Config, Client, Addr> {
+    binding: Binding<'client, T, Client>,
+    address: Addr,
+}
+
+impl<'client, T, Client, Addr> BoundAddress<'client, T, Client, Addr>
+where
+    T: Config,
+    Client: OnlineClientT<T>,
+    Addr: Address,
+{
+    /// Access the storage at the block given rather than the latest finalized block.
+    pub fn at(mut self, block_ref: impl Into<BlockRef<T::Hash>>) -> Self {
+        self.binding = self.binding.at(block_ref);
+        self
+    }
+
+    /// The underlying storage address.
+    pub fn address(&self) -> &Addr {
+        &self.address
+    }
+
+    /// Fetch the decoded value at this address. See [`Storage::fetch()`].
+    pub async fn fetch(&self) -> Result<Option<Addr::Target>, Error>
+    where
+        Addr: Address<IsFetchable = Yes>,
+    {
+        self.binding.storage().await?.fetch(&self.address).await
+    }
+
+    /// Fetch the decoded value at this address, falling back to the default value
+    /// if the entry does not exist. See [`Storage::fetch_or_default()`].
+    pub async fn fetch_or_default(&self) -> Result<Addr::Target, Error>
+    where
+        Addr: Address<IsFetchable = Yes, IsDefaultable = Yes>,
+    {
+        self.binding
+            .storage()
+            .await?
+            .fetch_or_default(&self.address)
+            .await
+    }
+
+    /// Iterate over the key value pairs at this address. See [`Storage::iter()`].
+    pub async fn iter(self) -> Result<StreamOfResults<StorageKeyValuePair<Addr>>, Error>
+    where
+        Addr: Address<IsIterable = Yes> + 'static,
+        Addr::Keys: 'static + Sized,
+    {
+        self.binding.storage().await?.iter(self.address).await
+    }
+}
@@ -8,9 +8,11 @@
 //! require network access. The [`OnlineClient`] requires network
 //! access.
 
+mod binding;
 mod offline_client;
 mod online_client;
 
+pub use binding::{Binding, BoundAddress};
 pub use offline_client::{OfflineClient, OfflineClientT};
 pub use online_client::{
     ClientRuntimeUpdater, OnlineClient, OnlineClientT, RuntimeUpdaterStream, Update, UpgradeError,
@@ -40,4 +40,13 @@ impl<T: Config, Client: OfflineClientT<T>> ConstantsClient<T, Client> {
         let metadata = self.client.metadata();
         subxt_core::constants::get(address, &metadata).map_err(Error::from)
     }
+
+    /// Fetch the SS58 prefix that the chain uses for its addresses, from the `System::SS58Prefix`
+    /// constant. Use this with [`crate::utils::AccountId32::to_ss58check_with_version`] and
+    /// [`crate::utils::AccountId32::from_ss58check_with_expected_prefix`] to display and
+    /// validate addresses for the connected chain.
+    pub fn ss58_prefix(&self) -> Result<u16, Error> {
+        let metadata = self.client.metadata();
+        subxt_core::constants::ss58_prefix(&metadata).map_err(Error::from)
+    }
 }
@@ -8,6 +8,8 @@
 use crate::metadata::{DecodeWithMetadata, Metadata};
 use core::fmt::Debug;
 use scale_decode::{visitor::DecodeAsTypeResult, DecodeAsType, TypeResolver};
+use scale_encode::EncodeAsType;
+use scale_value::{Composite, Value, ValueDef};
 
 use std::{borrow::Cow, marker::PhantomData};
 
@@ -227,6 +229,44 @@ pub struct ModuleErrorDetails<'a> {
 }
 
 impl DispatchError {
+    /// Convert any value that has the shape of `sp_runtime::DispatchError` into a
+    /// [`DispatchError`], resolving the pallet and error names of module errors using the
+    /// metadata given.
+    ///
+    /// This is useful when a `DispatchError` is embedded in the result of a runtime API call
+    /// (for instance `DryRunApi` or `ContractsApi`). The value can be the statically generated
+    /// `DispatchError` type or a dynamic [`scale_value::Value`]. For
+    /// `DispatchErrorWithPostInfo`, pass the inner `error` field, or use
+    /// [`DispatchError::from_value()`] for dynamic values.
+    pub fn from_type<E: EncodeAsType + ?Sized>(
+        value: &E,
+        metadata: Metadata,
+    ) -> Result<Self, super::Error> {
+        let dispatch_error_ty_id = metadata
+            .dispatch_error_ty()
+            .ok_or(MetadataError::DispatchErrorNotFound)?;
+
+        // Encode the value to the shape of the runtime's DispatchError, so that we can decode it
+        // back in the usual way.
+        let bytes = value.encode_as_type(dispatch_error_ty_id, metadata.types())?;
+        Self::decode_from(bytes, metadata)
+    }
+
+    /// Convert a dynamic value into a [`DispatchError`], resolving the pallet and error names of
+    /// module errors using the metadata given.
+    ///
+    /// The value can have the shape of either `sp_runtime::DispatchError`, or of
+    /// `sp_runtime::DispatchErrorWithPostInfo`, in which case the post dispatch info is ignored.
+    pub fn from_value<T>(value: &Value<T>, metadata: Metadata) -> Result<Self, super::Error> {
+        // DispatchErrorWithPostInfo is a struct with `post_info` and `error` fields:
+        if let ValueDef::Composite(Composite::Named(fields)) = &value.value {
+            if let Some((_, error)) = fields.iter().find(|(name, _)| name == "error") {
+                return Self::from_type(error, metadata);
+            }
+        }
+        Self::from_type(value, metadata)
+    }
+
     /// Attempt to decode a runtime [`DispatchError`].
     #[doc(hidden)]
     pub fn decode_from<'a>(
@@ -340,3 +380,60 @@ impl DispatchError {
         Ok(dispatch_error)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use codec::Decode;
+
+    fn metadata() -> Metadata {
+        let bytes = std::fs::read("../artifacts/polkadot_metadata_small.scale").unwrap();
+        Metadata::decode(&mut &*bytes).unwrap()
+    }
+
+    #[test]
+    fn dispatch_error_from_value() {
+        let metadata = metadata();
+
+        let value = Value::unnamed_variant("Arithmetic", [Value::unnamed_variant("Overflow", [])]);
+        assert_eq!(
+            DispatchError::from_value(&value, metadata.clone()).unwrap(),
+            DispatchError::Arithmetic(ArithmeticError::Overflow)
+        );
+
+        // Module errors resolve their pallet and error names:
+        let pallet = metadata.pallet_by_name("Balances").unwrap();
+        let error_name = pallet.error_variant_by_index(0).unwrap().name.clone();
+        let value = Value::unnamed_variant(
+            "Module",
+            [Value::named_composite([
+                ("index", Value::u128(pallet.index() as u128)),
+                ("error", Value::from_bytes([0u8; 4])),
+            ])],
+        );
+        let DispatchError::Module(module_error) =
+            DispatchError::from_type(&value, metadata.clone()).unwrap()
+        else {
+            panic!("expected a module error");
+        };
+        let details = module_error.details().unwrap();
+        assert_eq!(details.pallet.name(), "Balances");
+        assert_eq!(details.variant.name, error_name);
+
+        // DispatchErrorWithPostInfo has its post info ignored:
+        let value = Value::named_composite([
+            (
+                "post_info",
+                Value::named_composite([
+                    ("actual_weight", Value::unnamed_variant("None", [])),
+                    ("pays_fee", Value::unnamed_variant("Yes", [])),
+                ]),
+            ),
+            ("error", Value::unnamed_variant("BadOrigin", [])),
+        ]);
+        assert_eq!(
+            DispatchError::from_value(&value, metadata).unwrap(),
+            DispatchError::BadOrigin
+        );
+    }
+}
@@ -77,6 +77,14 @@ pub enum Error {
     #[cfg_attr(docsrs, doc(cfg(feature = "unstable-light-client")))]
     #[error("An error occurred but it could not be decoded: {0}")]
     LightClient(#[from] LightClientError),
+    /// A signer was unable to sign a transaction, for instance because the device
+    /// or service holding the keys refused to.
+    #[error("Signer error: {0}")]
+    Signer(Box<dyn std::error::Error + Send + Sync + 'static>),
+    /// The backend does not support the method with the given name, for instance because the
+    /// RPC methods that it uses offer no way to implement it.
+    #[error("The method {0} is not supported by this backend")]
+    UnsupportedMethod(String),
     /// Other error.
     #[error("Other error: {0}")]
     Other(String),
@@ -171,6 +179,9 @@ pub enum BlockError {
     /// An error containing the hash of the block that was not found.
     #[error("Could not find a block with hash {0} (perhaps it was on a non-finalized fork?)")]
     NotFound(String),
+    /// An error containing the number of the block that was not found.
+    #[error("Could not find a finalized block with number {0}")]
+    NumberNotFound(u64),
     /// Extrinsic type ID cannot be resolved with the provided metadata.
     #[error("Extrinsic type ID cannot be resolved with the provided metadata. Make sure this is a valid metadata")]
     MissingType,
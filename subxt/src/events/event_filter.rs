@@ -0,0 +1,100 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::{
+    config::Config,
+    events::{EventDetails, StaticEvent},
+};
+use derive_where::derive_where;
+
+/// A filter that decides which events are handed back by
+/// [`crate::events::EventsClient::subscribe_dynamic()`].
+///
+/// An event matches the filter if it is one of the pallets or events given (or if none were
+/// given), and if it has one of the topics given (or if none were given).
+///
+/// ```rust
+/// use subxt::events::EventFilter;
+/// use subxt::utils::H256;
+///
+/// // Any transfer event, or any event from the Multisig pallet:
+/// let filter = EventFilter::<H256>::new()
+///     .event("Balances", "Transfer")
+///     .pallet("Multisig");
+/// ```
+#[derive(Clone, Debug)]
+#[derive_where(Default)]
+pub struct EventFilter<Hash> {
+    // Pallet names, along with an optional event name in that pallet.
+    events: Vec<(String, Option<String>)>,
+    topics: Vec<Hash>,
+}
+
+impl<Hash: PartialEq> EventFilter<Hash> {
+    /// Create a new filter which matches every event.
+    pub fn new() -> Self {
+        EventFilter {
+            events: Vec::new(),
+            topics: Vec::new(),
+        }
+    }
+
+    /// Match any event emitted by the given pallet.
+    pub fn pallet(mut self, pallet_name: impl Into<String>) -> Self {
+        self.events.push((pallet_name.into(), None));
+        self
+    }
+
+    /// Match the event with the given pallet and variant name.
+    pub fn event(mut self, pallet_name: impl Into<String>, event_name: impl Into<String>) -> Self {
+        self.events
+            .push((pallet_name.into(), Some(event_name.into())));
+        self
+    }
+
+    /// Match the given statically generated event.
+    pub fn static_event<E: StaticEvent>(self) -> Self {
+        self.event(E::PALLET, E::EVENT)
+    }
+
+    /// Match events which were deposited with the given topic.
+    pub fn topic(mut self, topic: Hash) -> Self {
+        self.topics.push(topic);
+        self
+    }
+
+    /// Does the given event match this filter?
+    pub fn matches<T: Config<Hash = Hash>>(&self, event: &EventDetails<T>) -> bool {
+        self.matches_name(event.pallet_name(), event.variant_name())
+            && (self.topics.is_empty() || event.topics().iter().any(|t| self.topics.contains(t)))
+    }
+
+    fn matches_name(&self, pallet_name: &str, event_name: &str) -> bool {
+        self.events.is_empty()
+            || self
+                .events
+                .iter()
+                .any(|(p, e)| p == pallet_name && e.as_ref().map_or(true, |e| e == event_name))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::H256;
+
+    #[test]
+    fn matches_pallet_and_event_names() {
+        let filter = EventFilter::<H256>::new();
+        assert!(filter.matches_name("Balances", "Transfer"));
+
+        let filter = EventFilter::<H256>::new()
+            .event("Balances", "Transfer")
+            .pallet("Multisig");
+        assert!(filter.matches_name("Balances", "Transfer"));
+        assert!(!filter.matches_name("Balances", "Deposit"));
+        assert!(filter.matches_name("Multisig", "NewMultisig"));
+        assert!(!filter.matches_name("System", "ExtrinsicSuccess"));
+    }
+}
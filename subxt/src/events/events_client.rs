@@ -2,10 +2,25 @@
 // This file is dual-licensed as Apache-2.0 or GPL-3.0.
 // see LICENSE for license details.
 
-use crate::backend::{Backend, BackendExt, BlockRef};
-use crate::{client::OnlineClientT, error::Error, events::Events, Config};
+use crate::backend::{Backend, BackendExt, BlockRef, StreamOfResults};
+use crate::blocks::{Block, BlocksClient, ExtrinsicDetails};
+use crate::events::{EventDetails, EventFilter, Events, Phase, StaticEvent};
+use crate::{client::OnlineClientT, dynamic::Value, error::Error, Config};
 use derive_where::derive_where;
+use futures::{stream, StreamExt};
 use std::future::Future;
+use std::ops::RangeBounds;
+use std::sync::Arc;
+
+/// An event found by [`EventsClient::subscribe()`] or [`EventsClient::subscribe_dynamic()`],
+/// along with the block it is in and the extrinsic that emitted it, if any.
+pub type EventInBlock<T, Client> = (
+    BlockRef<<T as Config>::Hash>,
+    EventDetails<T>,
+    Option<ExtrinsicDetails<T, Client>>,
+);
+
+type EventStreamRes<T, Client> = Result<StreamOfResults<EventInBlock<T, Client>>, Error>;
 
 /// A client for working with events.
 #[derive_where(Clone; Client)]
@@ -48,6 +63,123 @@ where
         self.at_or_latest(None)
     }
 
+    /// Subscribe to every event of the given type in new finalized blocks. See
+    /// [`Self::subscribe_dynamic()`] for more details.
+    pub fn subscribe<E: StaticEvent>(
+        &self,
+    ) -> impl Future<Output = EventStreamRes<T, Client>> + Send + 'static
+    where
+        Client: Send + Sync + 'static,
+    {
+        self.subscribe_dynamic(EventFilter::new().static_event::<E>())
+    }
+
+    /// Subscribe to the events in new finalized blocks which match the given [`EventFilter`].
+    ///
+    /// Each matching event is handed back along with a reference to the block that it's in,
+    /// and the extrinsic that emitted it (or `None` if it was emitted while initializing or
+    /// finalizing the block). Extrinsics are only fetched for blocks with matching events.
+    ///
+    /// ```rust,no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use subxt::events::EventFilter;
+    /// use subxt::{OnlineClient, PolkadotConfig};
+    ///
+    /// let api = OnlineClient::<PolkadotConfig>::new().await?;
+    ///
+    /// let filter = EventFilter::new().event("Balances", "Transfer");
+    /// let mut transfers = api.events().subscribe_dynamic(filter).await?;
+    /// while let Some(transfer) = transfers.next().await {
+    ///     let (block_ref, event, extrinsic) = transfer?;
+    ///     let extrinsic_hash = extrinsic.map(|ext| ext.hash());
+    ///     let fields = event.field_values()?;
+    ///     println!("{fields:?} in block {:?} from {extrinsic_hash:?}", block_ref.hash());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn subscribe_dynamic(
+        &self,
+        filter: EventFilter<T::Hash>,
+    ) -> impl Future<Output = EventStreamRes<T, Client>> + Send + 'static
+    where
+        Client: Send + Sync + 'static,
+    {
+        let blocks = BlocksClient::new(self.client.clone()).subscribe_finalized();
+        async move {
+            let filter = Arc::new(filter);
+            let events = blocks
+                .await?
+                .then(move |block| {
+                    let filter = filter.clone();
+                    async move { matching_events(block?, &filter).await }
+                })
+                .flat_map(|events| {
+                    let events = match events {
+                        Ok(events) => events.into_iter().map(Ok).collect(),
+                        Err(e) => vec![Err(e)],
+                    };
+                    stream::iter(events)
+                });
+
+            Ok(StreamOfResults::new(Box::pin(events)))
+        }
+    }
+
+    /// Find the events deposited with the given topic in the finalized blocks within the
+    /// given range, in order.
+    ///
+    /// This reads the `System::EventTopics` storage entry at each block in the range, which
+    /// indexes the events in that block by topic. Only the events of blocks which have some
+    /// event with the topic are then fetched.
+    ///
+    /// ```rust,no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use subxt::{utils::H256, OnlineClient, PolkadotConfig};
+    ///
+    /// let api = OnlineClient::<PolkadotConfig>::new().await?;
+    ///
+    /// let topic = H256::repeat_byte(1);
+    /// let mut events = api.events().find_by_topic(topic, 1000..=2000).await?;
+    /// while let Some(event) = events.next().await {
+    ///     let (block_ref, event) = event?;
+    ///     let (pallet, variant) = (event.pallet_name(), event.variant_name());
+    ///     println!("{pallet}::{variant} in block {:?}", block_ref.hash());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_by_topic(
+        &self,
+        topic: T::Hash,
+        block_range: impl RangeBounds<u64>,
+    ) -> impl Future<Output = Result<StreamOfResults<(BlockRef<T::Hash>, EventDetails<T>)>, Error>>
+           + Send
+           + 'static
+    where
+        Client: Send + Sync + 'static,
+    {
+        let blocks = BlocksClient::new(self.client.clone())
+            .range(block_range)
+            .stream();
+        async move {
+            let events = blocks
+                .await?
+                .then(move |block| async move { events_with_topic(block?, topic).await })
+                .flat_map(|events| {
+                    let events = match events {
+                        Ok(events) => events.into_iter().map(Ok).collect(),
+                        Err(e) => vec![Err(e)],
+                    };
+                    stream::iter(events)
+                });
+
+            Ok(StreamOfResults::new(Box::pin(events)))
+        }
+    }
+
     /// Obtain events at some block hash.
     fn at_or_latest(
         &self,
@@ -69,6 +201,84 @@ where
     }
 }
 
+// Find the events in a block which match the filter, along with the extrinsics that emitted them.
+async fn matching_events<T, Client>(
+    block: Block<T, Client>,
+    filter: &EventFilter<T::Hash>,
+) -> Result<Vec<EventInBlock<T, Client>>, Error>
+where
+    T: Config,
+    Client: OnlineClientT<T>,
+{
+    let mut events = Vec::new();
+    for event in block.events().await?.iter() {
+        let event = event?;
+        if filter.matches(&event) {
+            events.push(event);
+        }
+    }
+
+    // Only fetch the block body if we need extrinsics from it.
+    let needs_extrinsics = events
+        .iter()
+        .any(|ev| matches!(ev.phase(), Phase::ApplyExtrinsic(_)));
+    let extrinsics = if needs_extrinsics {
+        block
+            .extrinsics()
+            .await?
+            .iter()
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        Vec::new()
+    };
+
+    let events = events
+        .into_iter()
+        .map(|event| {
+            let extrinsic = match event.phase() {
+                Phase::ApplyExtrinsic(idx) => extrinsics.get(idx as usize).cloned(),
+                _ => None,
+            };
+            (block.reference(), event, extrinsic)
+        })
+        .collect();
+    Ok(events)
+}
+
+// Find the events in a block which have the given topic, using `System::EventTopics`.
+async fn events_with_topic<T, Client>(
+    block: Block<T, Client>,
+    topic: T::Hash,
+) -> Result<Vec<(BlockRef<T::Hash>, EventDetails<T>)>, Error>
+where
+    T: Config,
+    Client: OnlineClientT<T>,
+{
+    // `EventTopics` maps a topic to the (block number, event index) of each event with that
+    // topic. It is cleared at the start of every block, so only holds events of this block.
+    let address = crate::dynamic::storage("System", "EventTopics", vec![Value::from_bytes(topic)]);
+    let Some(entries) = block.storage().fetch(&address).await? else {
+        return Ok(Vec::new());
+    };
+    let indexes: Vec<u32> = entries
+        .as_type::<Vec<(u64, u32)>>()?
+        .into_iter()
+        .map(|(_, index)| index)
+        .collect();
+    if indexes.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut events = Vec::new();
+    for event in block.events().await?.iter() {
+        let event = event?;
+        if indexes.contains(&event.index()) {
+            events.push((block.reference(), event));
+        }
+    }
+    Ok(events)
+}
+
 // The storage key needed to access events.
 fn system_events_key() -> [u8; 32] {
     let a = sp_crypto_hashing::twox_128(b"System");
@@ -89,3 +299,93 @@ pub(crate) async fn get_event_bytes<T: Config>(
         .await?
         .unwrap_or_default())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::test_utils::{metadata, MockBackend};
+    use crate::config::{substrate::H256, Header};
+    use crate::{Metadata, OnlineClient, SubstrateConfig};
+    use codec::Encode;
+
+    // An event which is stored in `System::Events` with the topics given.
+    fn new_account(account: u8, topics: Vec<H256>) -> (Phase, Value, Vec<H256>) {
+        let event = Value::unnamed_variant(
+            "System",
+            [Value::named_variant(
+                "NewAccount",
+                [("account", Value::from_bytes([account; 32]))],
+            )],
+        );
+        (Phase::Initialization, event, topics)
+    }
+
+    // Store the events given in a block, along with the `System::EventTopics` entries which
+    // point at them.
+    fn store_events(
+        backend: &MockBackend<SubstrateConfig>,
+        metadata: &Metadata,
+        block: H256,
+        number: u32,
+        events: Vec<(Phase, Value, Vec<H256>)>,
+    ) {
+        // Each entry is the block number and the index of an event in the block.
+        let mut topics: Vec<(H256, Vec<(u32, u32)>)> = Vec::new();
+        for (index, (_, _, event_topics)) in events.iter().enumerate() {
+            for topic in event_topics {
+                let entry = (number, index as u32);
+                match topics.iter_mut().find(|(t, _)| t == topic) {
+                    Some((_, entries)) => entries.push(entry),
+                    None => topics.push((*topic, vec![entry])),
+                }
+            }
+        }
+        for (topic, entries) in topics {
+            let address =
+                crate::dynamic::storage("System", "EventTopics", vec![Value::from_bytes(topic)]);
+            let key = subxt_core::storage::get_address_bytes(&address, metadata).unwrap();
+            backend.set_storage(block, key, entries.encode());
+        }
+        backend.set_events(block, metadata, events);
+    }
+
+    #[tokio::test]
+    async fn events_are_found_by_topic() {
+        let backend = std::sync::Arc::new(MockBackend::<SubstrateConfig>::new());
+        let metadata = metadata();
+        let blocks = backend.add_chain(backend.genesis(), 3, 0);
+        let [b1, b2, b3] = [0, 1, 2].map(|n| blocks[n].hash());
+        backend.finalize(b3);
+
+        let (t1, t2) = (H256::repeat_byte(1), H256::repeat_byte(2));
+        let events = vec![
+            new_account(0, vec![t1]),
+            new_account(1, vec![]),
+            new_account(2, vec![t1, t2]),
+        ];
+        store_events(&backend, &metadata, b1, 1, events);
+        store_events(&backend, &metadata, b2, 2, vec![new_account(3, vec![])]);
+        let events = vec![new_account(4, vec![t2]), new_account(5, vec![t1])];
+        store_events(&backend, &metadata, b3, 3, events);
+
+        let client: OnlineClient<SubstrateConfig> = backend.client(metadata);
+        let found = |topic| {
+            let events = client.events();
+            async move {
+                let found = events.find_by_topic(topic, 1..=3).await.unwrap();
+                found
+                    .map(|res| {
+                        let (block_ref, event) = res.unwrap();
+                        assert_eq!(event.variant_name(), "NewAccount");
+                        (block_ref.hash(), event.index())
+                    })
+                    .collect::<Vec<_>>()
+                    .await
+            }
+        };
+
+        assert_eq!(found(t1).await, vec![(b1, 0), (b1, 2), (b3, 1)]);
+        assert_eq!(found(t2).await, vec![(b1, 2), (b3, 0)]);
+        assert!(found(H256::repeat_byte(3)).await.is_empty());
+    }
+}
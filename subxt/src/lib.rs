@@ -297,6 +297,84 @@ pub mod ext {
 /// mod polkadot {}
 /// ```
 ///
+/// ## `client_bindings`
+///
+/// By default, the generated interface hands back addresses and payloads which need passing to a client. This attribute
+/// additionally generates an interface which is bound to a client, so that storage entries can be fetched and runtime APIs
+/// called directly:
+///
+/// ```rust,no_run
+/// #[subxt::subxt(
+///     runtime_metadata_path = "../artifacts/polkadot_metadata_full.scale",
+///     client_bindings
+/// )]
+/// mod polkadot {}
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use subxt::{OnlineClient, PolkadotConfig};
+/// use subxt_signer::sr25519::dev;
+///
+/// let client = OnlineClient::<PolkadotConfig>::new().await?;
+/// let account = dev::alice().public_key().into();
+///
+/// let api = polkadot::client_api();
+/// let info = api
+///     .storage(&client)
+///     .system()
+///     .account(&account)
+///     .fetch_or_default()
+///     .await?;
+/// let nonce = api
+///     .runtime_apis(&client)
+///     .account_nonce_api()
+///     .account_nonce(account)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ## `feature_gates`
+///
+/// This attribute gates each generated pallet module behind a `pallet-<name>` feature (for instance `pallet-balances`), and
+/// each runtime API behind a `runtime-api-<name>` feature (for instance `runtime-api-account-nonce-api`). The features
+/// are checked in the crate that the macro is used in, which needs to declare them in its `Cargo.toml`. This allows
+/// crates to compile only the parts of the interface that they need, without generating it from a subset of the metadata.
+///
+/// ```rust,ignore
+/// #[subxt::subxt(
+///     runtime_metadata_path = "../artifacts/polkadot_metadata_full.scale",
+///     feature_gates
+/// )]
+/// mod polkadot {}
+/// ```
+///
+/// `subxt codegen --feature-gates --features-out features.toml` writes out the `[features]` table to declare.
+///
+/// ## `serde`
+///
+/// This attribute derives `serde::Serialize` and `serde::Deserialize` on all of the generated types, using human readable
+/// representations: account IDs are SS58 strings, hashes and bytes are `0x` prefixed hex strings, and `u64`/`u128` fields
+/// (including those in `Option`s and `Vec`s) are strings so that they survive being parsed by JSON consumers which use
+/// floating point numbers. Any types substituted via `substitute_type` must implement the serde traits themselves.
+///
+/// ```rust,no_run
+/// #[subxt::subxt(
+///     runtime_metadata_path = "../artifacts/polkadot_metadata_small.scale",
+///     serde
+/// )]
+/// mod polkadot {}
+///
+/// let transfer = polkadot::balances::events::Transfer {
+///     from: subxt_signer::sr25519::dev::alice().public_key().into(),
+///     to: subxt_signer::sr25519::dev::bob().public_key().into(),
+///     amount: 10_000,
+/// };
+///
+/// // {"from":"5GrwvaEF...","to":"5FHneW46...","amount":"10000"}
+/// let json = serde_json::to_string(&transfer).unwrap();
+/// ```
+///
 /// ## `no_default_derives`
 ///
 /// By default, the macro will add all derives necessary for the generated code to play nicely with Subxt. Adding this attribute
@@ -5,9 +5,11 @@
 //! Types associated with accessing and working with storage items.
 
 mod storage_client;
+mod storage_iter;
 mod storage_type;
 
 pub use storage_client::StorageClient;
+pub use storage_iter::{StorageIterCursor, StorageIterParallel};
 pub use storage_type::{Storage, StorageKeyValuePair};
 pub use subxt_core::storage::address::{
     dynamic, Address, DefaultAddress, DynamicAddress, StaticAddress, StaticStorageKey, StorageKey,
@@ -0,0 +1,328 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Iterate over storage entries using several concurrent requests, in a way that can be
+//! resumed if it's interrupted.
+
+use super::storage_type::{StorageDecoder, StorageKeyValuePair};
+use crate::{
+    backend::{BlockRef, StorageResponse},
+    client::OnlineClientT,
+    error::Error,
+    Config, Metadata,
+};
+use codec::{Decode, Encode};
+use futures::{stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use subxt_core::storage::address::Address;
+
+/// The maximum number of partitions; one for each possible value of the first byte of a key.
+const MAX_PARTITIONS: usize = 256;
+
+/// The position reached by a [`StorageIterParallel`], which can be persisted (it can be
+/// SCALE or serde encoded) and handed to [`crate::storage::Storage::iter_parallel_from()`]
+/// to resume the iteration from that point.
+///
+/// The keys being iterated over are split into partitions by the first byte after the
+/// address being iterated over, and this records the last key seen in each partition.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+pub struct StorageIterCursor {
+    last_keys: Vec<Option<Vec<u8>>>,
+}
+
+impl StorageIterCursor {
+    /// A cursor pointing at the start of an iteration split into the given number of
+    /// partitions. This is clamped to between 1 and 256.
+    pub fn new(partitions: usize) -> Self {
+        let partitions = partitions.clamp(1, MAX_PARTITIONS);
+        StorageIterCursor {
+            last_keys: vec![None; partitions],
+        }
+    }
+
+    /// The number of partitions that the keys are split into.
+    pub fn partitions(&self) -> usize {
+        self.last_keys.len()
+    }
+
+    /// The last key handed back from the given partition, if any.
+    pub fn last_key(&self, partition: usize) -> Option<&[u8]> {
+        self.last_keys.get(partition)?.as_deref()
+    }
+
+    // The partition that keys beginning with the given byte (after the address) belong to.
+    fn partition_of(&self, byte: u8) -> usize {
+        byte as usize * self.partitions() / MAX_PARTITIONS
+    }
+
+    // The first bytes (after the address) of the keys that are still to be fetched
+    // in the given partition, in ascending order.
+    fn first_bytes(
+        &self,
+        partition: usize,
+        address_bytes: &[u8],
+        start_key: Option<&[u8]>,
+    ) -> Vec<u8> {
+        let resume_from = start_key
+            .and_then(|key| key.get(address_bytes.len()))
+            .copied()
+            .unwrap_or(0);
+        (resume_from..=u8::MAX)
+            .filter(|&byte| self.partition_of(byte) == partition)
+            .collect()
+    }
+}
+
+type PartitionedStream =
+    Pin<Box<dyn Stream<Item = (usize, Result<StorageResponse, Error>)> + Send>>;
+
+/// An iterator of key value pairs, returned from
+/// [`crate::storage::Storage::iter_parallel()`]. Key value pairs are handed back in no
+/// particular order.
+pub struct StorageIterParallel<Addr: Address> {
+    inner: PartitionedStream,
+    decoder: StorageDecoder,
+    metadata: Metadata,
+    cursor: StorageIterCursor,
+    _marker: PhantomData<fn() -> Addr>,
+}
+
+impl<Addr> StorageIterParallel<Addr>
+where
+    Addr: Address,
+    Addr::Keys: 'static + Sized,
+{
+    pub(super) fn new<T, C>(
+        client: C,
+        address_bytes: Vec<u8>,
+        block_ref: BlockRef<T::Hash>,
+        decoder: StorageDecoder,
+        metadata: Metadata,
+        cursor: StorageIterCursor,
+    ) -> Result<Self, Error>
+    where
+        T: Config,
+        C: OnlineClientT<T>,
+    {
+        // A cursor is only constructed with too few or too many partitions if it's decoded
+        // from something other than a persisted cursor. With no partitions, we'd silently
+        // hand back no key value pairs at all.
+        let num_partitions = cursor.partitions();
+        if !(1..=MAX_PARTITIONS).contains(&num_partitions) {
+            return Err(Error::Other(format!(
+                "Storage iteration cursors need 1 to {MAX_PARTITIONS} partitions, not {num_partitions}"
+            )));
+        }
+
+        let partitions = (0..cursor.partitions()).map(|partition| {
+            let start_key = cursor.last_keys[partition].clone();
+            partition_stream(
+                client.clone(),
+                address_bytes.clone(),
+                block_ref.clone(),
+                cursor.first_bytes(partition, &address_bytes, start_key.as_deref()),
+                start_key,
+            )
+            .map(move |kv| (partition, kv))
+            .boxed()
+        });
+
+        Ok(StorageIterParallel {
+            inner: Box::pin(stream::select_all(partitions)),
+            decoder,
+            metadata,
+            cursor,
+            _marker: PhantomData,
+        })
+    }
+
+    /// The position reached so far. Every key value pair handed back before this was called
+    /// will be skipped when resuming from it.
+    pub fn cursor(&self) -> StorageIterCursor {
+        self.cursor.clone()
+    }
+
+    /// Returns the next key value pair. This is just a wrapper around
+    /// [`StreamExt::next()`] so that you can avoid the extra import.
+    pub async fn next(&mut self) -> Option<Result<StorageKeyValuePair<Addr>, Error>> {
+        StreamExt::next(self).await
+    }
+}
+
+// Fetch the values below each of `address_bytes ++ [byte]` for the bytes given, one after the
+// other. Values for the first of these begin after `start_key`, if one is given.
+fn partition_stream<T, C>(
+    client: C,
+    address_bytes: Vec<u8>,
+    block_ref: BlockRef<T::Hash>,
+    first_bytes: Vec<u8>,
+    start_key: Option<Vec<u8>>,
+) -> impl Stream<Item = Result<StorageResponse, Error>> + Send + 'static
+where
+    T: Config,
+    C: OnlineClientT<T>,
+{
+    stream::iter(first_bytes)
+        .then(move |byte| {
+            let client = client.clone();
+            let block_ref = block_ref.clone();
+            let mut key = address_bytes.clone();
+            key.push(byte);
+            // Only resume from the start key within the sub-prefix that it belongs to.
+            let start_key = start_key.clone().filter(|k| k.starts_with(&key));
+            async move {
+                let backend = client.backend();
+                match start_key {
+                    Some(start_key) => {
+                        backend
+                            .storage_fetch_descendant_values_from(key, start_key, block_ref.hash())
+                            .await
+                    }
+                    None => {
+                        backend
+                            .storage_fetch_descendant_values(key, block_ref.hash())
+                            .await
+                    }
+                }
+            }
+        })
+        .flat_map(|values| match values {
+            Ok(values) => values.left_stream(),
+            Err(e) => stream::once(async { Err(e) }).right_stream(),
+        })
+}
+
+impl<Addr> Stream for StorageIterParallel<Addr>
+where
+    Addr: Address,
+    Addr::Keys: 'static + Sized,
+{
+    type Item = Result<StorageKeyValuePair<Addr>, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let Poll::Ready(next) = this.inner.poll_next_unpin(cx) else {
+            return Poll::Pending;
+        };
+        let Some((partition, kv)) = next else {
+            return Poll::Ready(None);
+        };
+
+        let kv = kv.and_then(|kv| {
+            this.cursor.last_keys[partition] = Some(kv.key.clone());
+            this.decoder.decode::<Addr>(kv, &this.metadata)
+        });
+        Poll::Ready(Some(kv))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::test_utils::{metadata, MockBackend};
+    use crate::config::{substrate::H256, Header};
+    use crate::dynamic::Value;
+    use crate::{OnlineClient, PolkadotConfig};
+    use std::sync::Arc;
+
+    // A client with the given number of `System::BlockHash` entries at the block handed back.
+    fn client_with_block_hashes(n: u32) -> (OnlineClient<PolkadotConfig>, H256) {
+        let backend = Arc::new(MockBackend::<PolkadotConfig>::new());
+        let metadata = metadata();
+        let block = backend.add_block(backend.genesis(), 0).hash();
+        for i in 0..n {
+            let address =
+                crate::dynamic::storage("System", "BlockHash", vec![Value::u128(i.into())]);
+            let key = subxt_core::storage::get_address_bytes(&address, &metadata).unwrap();
+            backend.set_storage(block, key, H256::from_low_u64_be(i.into()).encode());
+        }
+        backend.finalize(block);
+        (backend.client(metadata), block)
+    }
+
+    fn block_hashes() -> crate::storage::DynamicAddress<Vec<Value>> {
+        crate::dynamic::storage("System", "BlockHash", Vec::<Value>::new())
+    }
+
+    #[tokio::test]
+    async fn iterations_can_be_resumed() {
+        let (client, block) = client_with_block_hashes(50);
+        let storage = client.storage().at(block);
+
+        let mut iter = storage.iter_parallel(block_hashes(), 4).await.unwrap();
+        let mut seen = Vec::new();
+        for _ in 0..20 {
+            seen.push(iter.next().await.unwrap().unwrap().key_bytes);
+        }
+        let cursor = iter.cursor();
+        drop(iter);
+
+        // Persist the cursor, as one would in order to resume later:
+        let cursor = StorageIterCursor::decode(&mut &*cursor.encode()).unwrap();
+        let iter = storage
+            .iter_parallel_from(block_hashes(), cursor)
+            .await
+            .unwrap();
+        let rest: Vec<_> = iter.map(|kv| kv.unwrap().key_bytes).collect().await;
+        seen.extend(rest);
+
+        let mut all: Vec<_> = storage
+            .iter(block_hashes())
+            .await
+            .unwrap()
+            .map(|kv| kv.unwrap().key_bytes)
+            .collect()
+            .await;
+        assert_eq!(all.len(), 50);
+        seen.sort();
+        all.sort();
+        assert_eq!(seen, all);
+    }
+
+    #[tokio::test]
+    async fn cursors_with_invalid_partitions_are_rejected() {
+        let (client, block) = client_with_block_hashes(1);
+        let storage = client.storage().at(block);
+
+        for partitions in [0, 257] {
+            let last_keys: Vec<Option<Vec<u8>>> = vec![None; partitions];
+            let cursor = StorageIterCursor::decode(&mut &*last_keys.encode()).unwrap();
+            assert_eq!(cursor.partitions(), partitions);
+            let res = storage.iter_parallel_from(block_hashes(), cursor).await;
+            assert!(matches!(res, Err(Error::Other(_))));
+        }
+    }
+
+    #[test]
+    fn partitions_cover_every_first_byte_once() {
+        for n in [1, 3, 16, 255, 256] {
+            let cursor = StorageIterCursor::new(n);
+            let mut bytes: Vec<u8> = (0..n)
+                .flat_map(|p| cursor.first_bytes(p, &[], None))
+                .collect();
+            bytes.sort();
+            assert_eq!(bytes, (0..=u8::MAX).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn resuming_skips_first_bytes_already_seen() {
+        let cursor = StorageIterCursor::new(4);
+        let address = [1, 2];
+        // Partition 1 covers the bytes 64..128:
+        let bytes = cursor.first_bytes(1, &address, Some(&[1, 2, 100, 7]));
+        assert_eq!(bytes, (100..128).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn partitions_are_clamped() {
+        assert_eq!(StorageIterCursor::new(0).partitions(), 1);
+        assert_eq!(StorageIterCursor::new(1000).partitions(), 256);
+    }
+}
@@ -2,12 +2,13 @@
 // This file is dual-licensed as Apache-2.0 or GPL-3.0.
 // see LICENSE for license details.
 
+use super::storage_iter::{StorageIterCursor, StorageIterParallel};
 use crate::{
-    backend::{BackendExt, BlockRef},
+    backend::{BackendExt, BlockRef, StorageResponse},
     client::OnlineClientT,
     error::{Error, MetadataError, StorageAddressError},
     metadata::DecodeWithMetadata,
-    Config,
+    Config, Metadata,
 };
 use codec::Decode;
 use derive_where::derive_where;
@@ -194,6 +195,108 @@ where
         &self,
         address: Addr,
     ) -> impl Future<Output = Result<StreamOfResults<StorageKeyValuePair<Addr>>, Error>> + 'static
+    where
+        Addr: Address<IsIterable = Yes> + 'static,
+        Addr::Keys: 'static + Sized,
+    {
+        self.iter_with(address, |client, address_bytes, at| async move {
+            client
+                .backend()
+                .storage_fetch_descendant_values(address_bytes, at)
+                .await
+        })
+    }
+
+    /// Returns an iterator of key value pairs, like [`Storage::iter()`], but beginning with
+    /// the first key after `start_key`.
+    ///
+    /// Keys are handed back in order, so if an iteration is interrupted, it can be resumed by
+    /// passing the `key_bytes` of the last [`StorageKeyValuePair`] that was handled as
+    /// the `start_key` here.
+    pub fn iter_from<Addr>(
+        &self,
+        address: Addr,
+        start_key: Vec<u8>,
+    ) -> impl Future<Output = Result<StreamOfResults<StorageKeyValuePair<Addr>>, Error>> + 'static
+    where
+        Addr: Address<IsIterable = Yes> + 'static,
+        Addr::Keys: 'static + Sized,
+    {
+        self.iter_with(address, |client, address_bytes, at| async move {
+            client
+                .backend()
+                .storage_fetch_descendant_values_from(address_bytes, start_key, at)
+                .await
+        })
+    }
+
+    /// Returns an iterator of key value pairs, like [`Storage::iter()`], but which splits the
+    /// keys into `partitions` ranges (by the first byte of the key after the address given)
+    /// and iterates over each of them concurrently. This is much faster for large storage
+    /// maps, whose keys are usually hashed and thus spread evenly across the ranges. At most
+    /// 256 partitions are used.
+    ///
+    /// Key value pairs are not handed back in order. Use [`StorageIterParallel::cursor()`] to
+    /// obtain a [`StorageIterCursor`] which can be persisted and handed to
+    /// [`Storage::iter_parallel_from()`] in order to resume the iteration if it's interrupted.
+    ///
+    /// ```no_run
+    /// use subxt::{ PolkadotConfig, OnlineClient };
+    ///
+    /// #[subxt::subxt(runtime_metadata_path = "../artifacts/polkadot_metadata_full.scale")]
+    /// pub mod polkadot {}
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let api = OnlineClient::<PolkadotConfig>::new().await.unwrap();
+    /// let address = polkadot::storage().system().account_iter();
+    ///
+    /// let mut iter = api
+    ///     .storage()
+    ///     .at_latest()
+    ///     .await
+    ///     .unwrap()
+    ///     .iter_parallel(address, 16)
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// while let Some(Ok(kv)) = iter.next().await {
+    ///     println!("Key bytes: 0x{}", hex::encode(&kv.key_bytes));
+    ///     // Persist this somewhere to resume from this point via `iter_parallel_from`:
+    ///     let cursor = iter.cursor();
+    /// }
+    /// # }
+    /// ```
+    pub fn iter_parallel<Addr>(
+        &self,
+        address: Addr,
+        partitions: usize,
+    ) -> impl Future<Output = Result<StorageIterParallel<Addr>, Error>> + 'static
+    where
+        Addr: Address<IsIterable = Yes> + 'static,
+        Addr::Keys: 'static + Sized,
+    {
+        self.iter_parallel_from(address, StorageIterCursor::new(partitions))
+    }
+
+    /// Resume an iteration started by [`Storage::iter_parallel()`], from the point at which the
+    /// given [`StorageIterCursor`] was obtained. The iteration must be over the same address
+    /// (and at the same block, if the result is expected to be consistent).
+    ///
+    /// Only the keys sharing their first byte (after the address) with the last key seen in each
+    /// partition are fetched again. How much of that is downloaded again depends on the backend:
+    /// the [`crate::backend::legacy::LegacyBackend`] begins fetching from the last key seen, but
+    /// the `chainHead_v1_storage` method used by the
+    /// [`crate::backend::unstable::UnstableBackend`] can't, and so every value sharing that first
+    /// byte is fetched again, and those already seen are skipped over.
+    ///
+    /// An error is returned if the cursor doesn't have between 1 and 256 partitions, which can
+    /// happen if it was corrupted while it was persisted.
+    pub fn iter_parallel_from<Addr>(
+        &self,
+        address: Addr,
+        cursor: StorageIterCursor,
+    ) -> impl Future<Output = Result<StorageIterParallel<Addr>, Error>> + 'static
     where
         Addr: Address<IsIterable = Yes> + 'static,
         Addr::Keys: 'static + Sized,
@@ -202,59 +305,43 @@ where
         let block_ref = self.block_ref.clone();
         async move {
             let metadata = client.metadata();
-            let (_pallet, entry) = subxt_core::storage::lookup_storage_entry_details(
-                address.pallet_name(),
-                address.entry_name(),
-                &metadata,
-            )?;
-
-            // Metadata validation checks whether the static address given
-            // is likely to actually correspond to a real storage entry or not.
-            // if not, it means static codegen doesn't line up with runtime
-            // metadata.
-            subxt_core::storage::validate(&address, &metadata)?;
-
-            // Look up the return type for flexible decoding. Do this once here to avoid
-            // potentially doing it every iteration if we used `decode_storage_with_metadata`
-            // in the iterator.
-            let entry = entry.entry_type();
+            let decoder = StorageDecoder::new(&address, &metadata)?;
+            let address_bytes = subxt_core::storage::get_address_bytes(&address, &metadata)?;
+            StorageIterParallel::new(
+                client.clone(),
+                address_bytes,
+                block_ref,
+                decoder,
+                metadata,
+                cursor,
+            )
+        }
+    }
 
-            let return_type_id = entry.value_ty();
-            let hashers = StorageHashers::new(entry, metadata.types())?;
+    // Fetch the key value pairs of the storage entry at the given address using the function
+    // given, and decode them.
+    fn iter_with<Addr, F, Fut>(
+        &self,
+        address: Addr,
+        fetch: F,
+    ) -> impl Future<Output = Result<StreamOfResults<StorageKeyValuePair<Addr>>, Error>> + 'static
+    where
+        Addr: Address<IsIterable = Yes> + 'static,
+        Addr::Keys: 'static + Sized,
+        F: FnOnce(Client, Vec<u8>, T::Hash) -> Fut + 'static,
+        Fut: Future<Output = Result<StreamOfResults<StorageResponse>, Error>>,
+    {
+        let client = self.client.clone();
+        let block_ref = self.block_ref.clone();
+        async move {
+            let metadata = client.metadata();
+            let decoder = StorageDecoder::new(&address, &metadata)?;
 
             // The address bytes of this entry:
             let address_bytes = subxt_core::storage::get_address_bytes(&address, &metadata)?;
-            let s = client
-                .backend()
-                .storage_fetch_descendant_values(address_bytes, block_ref.hash())
+            let s = fetch(client.clone(), address_bytes, block_ref.hash())
                 .await?
-                .map(move |kv| {
-                    let kv = match kv {
-                        Ok(kv) => kv,
-                        Err(e) => return Err(e),
-                    };
-                    let value = Addr::Target::decode_with_metadata(
-                        &mut &*kv.value,
-                        return_type_id,
-                        &metadata,
-                    )?;
-
-                    let key_bytes = kv.key;
-                    let cursor = &mut &key_bytes[..];
-                    strip_storage_address_root_bytes(cursor)?;
-
-                    let keys = <Addr::Keys as StorageKey>::decode_storage_key(
-                        cursor,
-                        &mut hashers.iter(),
-                        metadata.types(),
-                    )?;
-
-                    Ok(StorageKeyValuePair::<Addr> {
-                        keys,
-                        key_bytes,
-                        value,
-                    })
-                });
+                .map(move |kv| decoder.decode::<Addr>(kv?, &metadata));
 
             let s = StreamOfResults::new(Box::pin(s));
             Ok(s)
@@ -300,6 +387,68 @@ where
     }
 }
 
+/// The details needed to decode the key value pairs found when iterating over a storage entry.
+pub(super) struct StorageDecoder {
+    return_type_id: u32,
+    hashers: StorageHashers,
+}
+
+impl StorageDecoder {
+    pub(super) fn new<Addr: Address>(address: &Addr, metadata: &Metadata) -> Result<Self, Error> {
+        let (_pallet, entry) = subxt_core::storage::lookup_storage_entry_details(
+            address.pallet_name(),
+            address.entry_name(),
+            metadata,
+        )?;
+
+        // Metadata validation checks whether the static address given
+        // is likely to actually correspond to a real storage entry or not.
+        // if not, it means static codegen doesn't line up with runtime
+        // metadata.
+        subxt_core::storage::validate(address, metadata)?;
+
+        // Look up the return type for flexible decoding. Do this once here to avoid
+        // potentially doing it every iteration if we used `decode_storage_with_metadata`
+        // in the iterator.
+        let entry = entry.entry_type();
+
+        let return_type_id = entry.value_ty();
+        let hashers = StorageHashers::new(entry, metadata.types())?;
+        Ok(StorageDecoder {
+            return_type_id,
+            hashers,
+        })
+    }
+
+    pub(super) fn decode<Addr: Address>(
+        &self,
+        kv: StorageResponse,
+        metadata: &Metadata,
+    ) -> Result<StorageKeyValuePair<Addr>, Error>
+    where
+        Addr::Keys: 'static + Sized,
+    {
+        let value =
+            Addr::Target::decode_with_metadata(&mut &*kv.value, self.return_type_id, metadata)?;
+
+        let key_bytes = kv.key;
+        let cursor = &mut &key_bytes[..];
+        strip_storage_address_root_bytes(cursor)?;
+
+        let keys = <Addr::Keys as StorageKey>::decode_storage_key(
+            cursor,
+            &mut self.hashers.iter(),
+            metadata.types(),
+        )?;
+
+        Ok(StorageKeyValuePair::<Addr> {
+            keys,
+            key_bytes,
+            value,
+        })
+    }
+}
+
 /// Strips the first 32 bytes (16 for the pallet hash, 16 for the entry hash) off some storage address bytes.
 fn strip_storage_address_root_bytes(address_bytes: &mut &[u8]) -> Result<(), StorageAddressError> {
     if address_bytes.len() >= 32 {
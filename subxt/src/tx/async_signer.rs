@@ -0,0 +1,76 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use super::Signer;
+use crate::{error::Error, Config};
+use async_trait::async_trait;
+
+/// A signer which signs payloads asynchronously, and which may fail to do so. This is
+/// useful for signers backed by remote services (KMS or HSM), browser extensions or
+/// hardware wallets, which can take some time to sign a payload, and may refuse to.
+///
+/// This is implemented for every [`Signer`] which is [`Sync`], and is accepted by
+/// [`crate::tx::TxClient::create_signed_async()`], the other `_async` methods on
+/// [`crate::tx::TxClient`] which sign transactions, and
+/// [`crate::tx::PartialExtrinsic::sign_async()`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use subxt::tx::AsyncSigner;
+/// use subxt::utils::{AccountId32, MultiAddress, MultiSignature};
+/// use subxt::{Error, PolkadotConfig};
+///
+/// struct RemoteSigner {
+///     account_id: AccountId32,
+/// }
+///
+/// #[async_trait::async_trait]
+/// impl AsyncSigner<PolkadotConfig> for RemoteSigner {
+///     fn account_id(&self) -> AccountId32 {
+///         self.account_id.clone()
+///     }
+///
+///     fn address(&self) -> MultiAddress<AccountId32, ()> {
+///         self.account_id.clone().into()
+///     }
+///
+///     async fn sign(&self, signer_payload: &[u8]) -> Result<MultiSignature, Error> {
+///         // Send the payload somewhere to be signed, returning
+///         // `Error::Signer` if the request is refused.
+///         # unimplemented!()
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait AsyncSigner<T: Config>: Send + Sync {
+    /// Return the "from" account ID.
+    fn account_id(&self) -> T::AccountId;
+
+    /// Return the "from" address.
+    fn address(&self) -> T::Address;
+
+    /// Takes a signer payload for an extrinsic, and returns a signature based on it, or
+    /// an error (typically [`Error::Signer`]) if the payload could not be signed.
+    async fn sign(&self, signer_payload: &[u8]) -> Result<T::Signature, Error>;
+}
+
+#[async_trait]
+impl<T, S> AsyncSigner<T> for S
+where
+    T: Config,
+    S: Signer<T> + Send + Sync,
+{
+    fn account_id(&self) -> T::AccountId {
+        Signer::account_id(self)
+    }
+
+    fn address(&self) -> T::Address {
+        Signer::address(self)
+    }
+
+    async fn sign(&self, signer_payload: &[u8]) -> Result<T::Signature, Error> {
+        Ok(Signer::sign(self, signer_payload))
+    }
+}
@@ -11,7 +11,9 @@
 
 use crate::macros::cfg_substrate_compat;
 
+mod async_signer;
 mod tx_client;
+mod tx_pool;
 mod tx_progress;
 
 // The PairSigner impl currently relies on Substrate bits and pieces, so make it an optional
@@ -20,10 +22,12 @@ cfg_substrate_compat! {
     pub use subxt_core::tx::signer::PairSigner;
 }
 
+pub use async_signer::AsyncSigner;
 pub use subxt_core::tx::payload::{dynamic, DefaultPayload, DynamicPayload, Payload};
 pub use subxt_core::tx::signer::{self, Signer};
 pub use tx_client::{
     PartialExtrinsic, SubmittableExtrinsic, TransactionInvalid, TransactionUnknown, TxClient,
     ValidationResult,
 };
+pub use tx_pool::{PendingExtrinsic, PendingExtrinsicEvent};
 pub use tx_progress::{TxInBlock, TxProgress, TxStatus};
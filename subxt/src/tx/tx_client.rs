@@ -3,11 +3,14 @@
 // see LICENSE for license details.
 
 use crate::{
-    backend::{BackendExt, BlockRef, TransactionStatus},
+    backend::{BackendExt, BlockRef, StreamOfResults, TransactionStatus},
     client::{OfflineClientT, OnlineClientT},
     config::{Config, ExtrinsicParams, Header, RefineParams, RefineParamsData},
     error::{BlockError, Error},
-    tx::{Payload, Signer as SignerT, TxProgress},
+    tx::{
+        tx_pool, AsyncSigner as AsyncSignerT, Payload, PendingExtrinsic, PendingExtrinsicEvent,
+        Signer as SignerT, TxProgress,
+    },
     utils::PhantomDataSendSync,
 };
 use codec::{Compact, Decode, Encode};
@@ -135,6 +138,31 @@ where
         Ok(())
     }
 
+    /// Fetch the transactions which are waiting in the transaction pool to be included in a
+    /// block.
+    ///
+    /// **Note:** This relies on the `author_pendingExtrinsics` RPC method, and so is only
+    /// supported by the [`crate::backend::legacy::LegacyBackend`].
+    pub async fn pending(&self) -> Result<Vec<PendingExtrinsic<T>>, Error> {
+        tx_pool::pending_extrinsics(&self.client).await
+    }
+
+    /// Subscribe to changes to the transaction pool. The pool is checked each time that a new
+    /// best block is seen, and the transactions which were added to or removed from it since
+    /// the last check are reported. Every transaction in the pool is reported as added at the
+    /// first check.
+    ///
+    /// Since the pool is only checked periodically, transactions which enter and leave the
+    /// pool between two checks are not seen at all.
+    ///
+    /// **Note:** This relies on the `author_pendingExtrinsics` RPC method, and so is only
+    /// supported by the [`crate::backend::legacy::LegacyBackend`].
+    pub async fn subscribe_pending(
+        &self,
+    ) -> Result<StreamOfResults<PendingExtrinsicEvent<T>>, Error> {
+        tx_pool::pending_extrinsic_stream(self.client.clone()).await
+    }
+
     /// Get the account nonce for a given account ID.
     pub async fn account_nonce(&self, account_id: &T::AccountId) -> Result<u64, Error> {
         let block_ref = self.client.backend().latest_finalized_block_ref().await?;
@@ -182,6 +210,25 @@ where
         Ok(partial_signed.sign(signer))
     }
 
+    /// Creates a signed extrinsic using an [`AsyncSigner`](crate::tx::AsyncSigner), which
+    /// signs asynchronously and may fail to sign, without submitting it.
+    pub async fn create_signed_async<Call, Signer>(
+        &self,
+        call: &Call,
+        signer: &Signer,
+        params: <T::ExtrinsicParams as ExtrinsicParams<T>>::Params,
+    ) -> Result<SubmittableExtrinsic<T, C>, Error>
+    where
+        Call: Payload,
+        Signer: AsyncSignerT<T>,
+    {
+        self.validate(call)?;
+        let partial_signed = self
+            .create_partial_signed(call, &signer.account_id(), params)
+            .await?;
+        partial_signed.sign_async(signer).await
+    }
+
     /// Creates and signs an extrinsic and submits it to the chain. Passes default parameters
     /// to construct the "signed extra" and "additional" payloads needed by the extrinsic.
     ///
@@ -221,6 +268,28 @@ where
             .await
     }
 
+    /// Creates and signs an extrinsic using an [`AsyncSigner`](crate::tx::AsyncSigner),
+    /// and submits it to the chain. Use `Default::default()` for the params to construct
+    /// the "signed extra" and "additional" payloads with default values.
+    ///
+    /// Returns a [`TxProgress`], which can be used to track the status of the transaction
+    /// and obtain details about it, once it has made it into a block.
+    pub async fn sign_and_submit_then_watch_async<Call, Signer>(
+        &self,
+        call: &Call,
+        signer: &Signer,
+        params: <T::ExtrinsicParams as ExtrinsicParams<T>>::Params,
+    ) -> Result<TxProgress<T, C>, Error>
+    where
+        Call: Payload,
+        Signer: AsyncSignerT<T>,
+    {
+        self.create_signed_async(call, signer, params)
+            .await?
+            .submit_and_watch()
+            .await
+    }
+
     /// Creates and signs an extrinsic and submits to the chain for block inclusion. Passes
     /// default parameters to construct the "signed extra" and "additional" payloads needed
     /// by the extrinsic.
@@ -267,6 +336,32 @@ where
             .submit()
             .await
     }
+
+    /// Creates and signs an extrinsic using an [`AsyncSigner`](crate::tx::AsyncSigner),
+    /// and submits it to the chain for block inclusion. Use `Default::default()` for the
+    /// params to construct the "signed extra" and "additional" payloads with default values.
+    ///
+    /// Returns `Ok` with the extrinsic hash if it is valid extrinsic.
+    ///
+    /// # Note
+    ///
+    /// Success does not mean the extrinsic has been included in the block, just that it is valid
+    /// and has been included in the transaction pool.
+    pub async fn sign_and_submit_async<Call, Signer>(
+        &self,
+        call: &Call,
+        signer: &Signer,
+        params: <T::ExtrinsicParams as ExtrinsicParams<T>>::Params,
+    ) -> Result<T::Hash, Error>
+    where
+        Call: Payload,
+        Signer: AsyncSignerT<T>,
+    {
+        self.create_signed_async(call, signer, params)
+            .await?
+            .submit()
+            .await
+    }
 }
 
 /// This payload contains the information needed to produce an extrinsic.
@@ -305,6 +400,20 @@ where
         }
     }
 
+    /// Convert this [`PartialExtrinsic`] into a [`SubmittableExtrinsic`], ready to submit,
+    /// using an [`AsyncSigner`](crate::tx::AsyncSigner), which signs asynchronously and may
+    /// fail to sign. Any [`Signer`](crate::tx::Signer) can also be given here.
+    pub async fn sign_async<Signer>(
+        &self,
+        signer: &Signer,
+    ) -> Result<SubmittableExtrinsic<T, C>, Error>
+    where
+        Signer: AsyncSignerT<T>,
+    {
+        let signature = signer.sign(&self.signer_payload()).await?;
+        Ok(self.sign_with_address_and_signature(&signer.address(), &signature))
+    }
+
     /// Convert this [`PartialExtrinsic`] into a [`SubmittableExtrinsic`], ready to submit.
     /// An address, and something representing a signature that can be SCALE encoded, are both
     /// needed in order to construct it. If you have a `Signer` to hand, you can use
@@ -625,6 +734,64 @@ pub enum TransactionInvalid {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::{utils::AccountId32, OfflineClient, PolkadotConfig};
+    use subxt_core::client::RuntimeVersion;
+
+    fn partial_extrinsic() -> PartialExtrinsic<PolkadotConfig, OfflineClient<PolkadotConfig>> {
+        let bytes = std::fs::read("../artifacts/polkadot_metadata_small.scale").unwrap();
+        let metadata = crate::Metadata::decode(&mut &*bytes).unwrap();
+        let client = OfflineClient::<PolkadotConfig>::new(
+            Default::default(),
+            RuntimeVersion {
+                spec_version: 0,
+                transaction_version: 0,
+            },
+            metadata,
+        );
+        let call = crate::dynamic::tx(
+            "System",
+            "remark",
+            vec![crate::dynamic::Value::from_bytes(b"hello")],
+        );
+        client
+            .tx()
+            .create_partial_signed_offline(&call, Default::default())
+            .unwrap()
+    }
+
+    struct RefusingSigner;
+
+    #[async_trait::async_trait]
+    impl AsyncSignerT<PolkadotConfig> for RefusingSigner {
+        fn account_id(&self) -> AccountId32 {
+            AccountId32([0; 32])
+        }
+
+        fn address(&self) -> <PolkadotConfig as Config>::Address {
+            self.account_id().into()
+        }
+
+        async fn sign(
+            &self,
+            _signer_payload: &[u8],
+        ) -> Result<<PolkadotConfig as Config>::Signature, Error> {
+            Err(Error::Signer("refused by device".into()))
+        }
+    }
+
+    #[tokio::test]
+    async fn sign_async_works_with_sync_and_async_signers() {
+        let partial = partial_extrinsic();
+
+        // ed25519 signatures are deterministic, so signing either way gives the same bytes.
+        let signer = subxt_signer::ed25519::dev::alice();
+        let async_signed = partial.sign_async(&signer).await.unwrap();
+        let signed = partial.sign(&signer);
+        assert_eq!(async_signed.encoded(), signed.encoded());
+
+        let res = partial.sign_async(&RefusingSigner).await;
+        assert!(matches!(res, Err(Error::Signer(e)) if e.to_string() == "refused by device"));
+    }
 
     #[test]
     fn transaction_validity_decoding_empty_bytes() {
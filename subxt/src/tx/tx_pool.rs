@@ -0,0 +1,187 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::{
+    backend::StreamOfResults,
+    client::OnlineClientT,
+    config::Config,
+    error::{BlockError, Error},
+};
+use derive_where::derive_where;
+use futures::{stream, StreamExt};
+use std::collections::HashSet;
+use subxt_core::blocks::Extrinsics as CoreExtrinsics;
+
+/// A transaction which is waiting in the transaction pool to be included in a block.
+///
+/// This offers the same ways to decode the transaction as [`crate::blocks::ExtrinsicDetails`],
+/// but since the transaction isn't in a block yet, there are no events associated with it. Its
+/// [`index()`](subxt_core::blocks::ExtrinsicDetails::index()) is its position in the list of
+/// pending transactions handed back by the node.
+pub type PendingExtrinsic<T> = subxt_core::blocks::ExtrinsicDetails<T>;
+
+/// A change to the transaction pool, as reported by
+/// [`crate::tx::TxClient::subscribe_pending()`].
+#[derive_where(Debug)]
+pub enum PendingExtrinsicEvent<T: Config> {
+    /// The transaction was added to the pool.
+    Added(PendingExtrinsic<T>),
+    /// The transaction with the given hash left the pool, because it was included in a block,
+    /// or because it was dropped or became invalid.
+    Removed(T::Hash),
+}
+
+/// Fetch and decode the transactions in the pool.
+pub(crate) async fn pending_extrinsics<T, C>(client: &C) -> Result<Vec<PendingExtrinsic<T>>, Error>
+where
+    T: Config,
+    C: OnlineClientT<T>,
+{
+    let extrinsics = client.backend().pending_extrinsics().await?;
+    let extrinsics = CoreExtrinsics::<T>::decode_from(extrinsics, client.metadata())
+        .map_err(BlockError::from)?;
+    let extrinsics = extrinsics.iter().collect::<Result<_, _>>()?;
+    Ok(extrinsics)
+}
+
+/// Check the transaction pool each time that a new best block is seen, and report the
+/// transactions which were added to it or removed from it since the last check.
+pub(crate) async fn pending_extrinsic_stream<T, C>(
+    client: C,
+) -> Result<StreamOfResults<PendingExtrinsicEvent<T>>, Error>
+where
+    T: Config,
+    C: OnlineClientT<T>,
+{
+    let best_blocks = client.backend().stream_best_block_headers().await?;
+    // The hashes of the transactions seen in the pool at the last check.
+    let seen = HashSet::<T::Hash>::new();
+
+    let events = stream::unfold(
+        (best_blocks, client, seen),
+        |(mut best_blocks, client, mut seen)| async move {
+            let events = match best_blocks.next().await? {
+                Ok(_) => pool_changes(&client, &mut seen).await,
+                Err(e) => Err(e),
+            };
+            let events = match events {
+                Ok(events) => events.into_iter().map(Ok).collect(),
+                Err(e) => vec![Err(e)],
+            };
+            Some((stream::iter(events), (best_blocks, client, seen)))
+        },
+    )
+    .flatten();
+
+    Ok(StreamOfResults::new(Box::pin(events)))
+}
+
+// Fetch the transactions in the pool, and compare them with those seen last time.
+async fn pool_changes<T, C>(
+    client: &C,
+    seen: &mut HashSet<T::Hash>,
+) -> Result<Vec<PendingExtrinsicEvent<T>>, Error>
+where
+    T: Config,
+    C: OnlineClientT<T>,
+{
+    let pending = pending_extrinsics(client).await?;
+    let hashes: HashSet<T::Hash> = pending.iter().map(|ext| ext.hash()).collect();
+
+    let removed = seen
+        .iter()
+        .filter(|hash| !hashes.contains(hash))
+        .map(|hash| PendingExtrinsicEvent::Removed(*hash));
+    let added = pending
+        .into_iter()
+        .filter(|ext| !seen.contains(&ext.hash()))
+        .map(PendingExtrinsicEvent::Added);
+    let events = removed.chain(added).collect();
+
+    *seen = hashes;
+    Ok(events)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::test_utils::{metadata, MockBackend};
+    use crate::config::substrate::H256;
+    use crate::dynamic::Value;
+    use crate::{OnlineClient, PolkadotConfig};
+    use std::sync::Arc;
+
+    // An unsigned extrinsic, encoded as the node hands it back, and its hash.
+    fn extrinsic(client: &OnlineClient<PolkadotConfig>, remark: &[u8]) -> (Vec<u8>, H256) {
+        let call = crate::dynamic::tx("System", "remark", vec![Value::from_bytes(remark)]);
+        let extrinsic = client.tx().create_unsigned(&call).unwrap();
+        (extrinsic.encoded().to_vec(), extrinsic.hash())
+    }
+
+    // The hashes of the added and removed transactions, each in ascending order.
+    fn changes(events: Vec<PendingExtrinsicEvent<PolkadotConfig>>) -> (Vec<H256>, Vec<H256>) {
+        let (mut added, mut removed) = (Vec::new(), Vec::new());
+        for event in events {
+            match event {
+                PendingExtrinsicEvent::Added(ext) => added.push(ext.hash()),
+                PendingExtrinsicEvent::Removed(hash) => removed.push(hash),
+            }
+        }
+        added.sort();
+        removed.sort();
+        (added, removed)
+    }
+
+    fn sorted<const N: usize>(mut hashes: [H256; N]) -> Vec<H256> {
+        hashes.sort();
+        hashes.to_vec()
+    }
+
+    #[tokio::test]
+    async fn pool_changes_are_reported() {
+        let backend = Arc::new(MockBackend::<PolkadotConfig>::new());
+        let client = backend.client(metadata());
+        let (a, a_hash) = extrinsic(&client, b"a");
+        let (b, b_hash) = extrinsic(&client, b"b");
+        let (c, c_hash) = extrinsic(&client, b"c");
+        let mut seen = HashSet::new();
+
+        backend.set_pending_extrinsics(vec![a.clone(), b.clone()]);
+        let events = pool_changes(&client, &mut seen).await.unwrap();
+        assert_eq!(changes(events), (sorted([a_hash, b_hash]), vec![]));
+
+        // Nothing changed:
+        let events = pool_changes(&client, &mut seen).await.unwrap();
+        assert!(events.is_empty());
+
+        backend.set_pending_extrinsics(vec![b.clone(), c.clone()]);
+        let events = pool_changes(&client, &mut seen).await.unwrap();
+        assert_eq!(changes(events), (vec![c_hash], vec![a_hash]));
+
+        backend.set_pending_extrinsics(vec![]);
+        let events = pool_changes(&client, &mut seen).await.unwrap();
+        assert_eq!(changes(events), (vec![], sorted([b_hash, c_hash])));
+
+        // A transaction can come back after leaving the pool:
+        backend.set_pending_extrinsics(vec![a]);
+        let events = pool_changes(&client, &mut seen).await.unwrap();
+        assert_eq!(changes(events), (vec![a_hash], vec![]));
+    }
+
+    #[tokio::test]
+    async fn pending_extrinsics_are_decoded_in_pool_order() {
+        let backend = Arc::new(MockBackend::<PolkadotConfig>::new());
+        let client = backend.client(metadata());
+        let (a, a_hash) = extrinsic(&client, b"a");
+        let (b, b_hash) = extrinsic(&client, b"b");
+        backend.set_pending_extrinsics(vec![b, a]);
+
+        let pending = pending_extrinsics(&client).await.unwrap();
+        let details: Vec<_> = pending
+            .iter()
+            .map(|ext| (ext.index(), ext.hash(), ext.variant_name().unwrap()))
+            .collect();
+        assert_eq!(details, vec![(0, b_hash, "remark"), (1, a_hash, "remark")]);
+    }
+}
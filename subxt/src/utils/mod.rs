@@ -8,10 +8,13 @@ use crate::macros::cfg_jsonrpsee;
 use crate::{error::RpcError, Error};
 use url::Url;
 
+pub mod multisig;
+
 pub use subxt_core::utils::{
-    bits, strip_compact_prefix, to_hex, AccountId32, Encoded, Era, KeyedVec, MultiAddress,
-    MultiSignature, PhantomDataSendSync, Static, UncheckedExtrinsic, WrapperKeepOpaque, Yes, H160,
-    H256, H512,
+    bits, ss58, strip_compact_prefix, to_hex, AccountId32, Encoded, Era, FromSs58Error,
+    FromUnifiedAddress, InvalidSs58Prefix, KeyedVec, MultiAddress, MultiSignature,
+    PhantomDataSendSync, Static, UncheckedExtrinsic, UnifiedAddress, UnifiedAddressError,
+    WrapperKeepOpaque, Yes, H160, H256, H512,
 };
 
 cfg_jsonrpsee! {
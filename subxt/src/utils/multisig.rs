@@ -0,0 +1,485 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Helpers for working with `pallet_multisig`.
+//!
+//! A [`Multisig`] describes a set of signatories and a threshold. From it we can compute the
+//! deterministic multisig account, look up the operations pending approval with
+//! [`Multisig::pending_operations()`], and build the `as_multi`, `approve_as_multi` and
+//! `cancel_as_multi` calls needed to progress them. Any static or dynamic call can be wrapped.
+//!
+//! ```rust,no_run
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use subxt::utils::multisig::Multisig;
+//! use subxt::{dynamic::Value, OnlineClient, PolkadotConfig};
+//! use subxt_signer::sr25519::dev;
+//!
+//! let api = OnlineClient::<PolkadotConfig>::new().await?;
+//!
+//! let signatories = [dev::alice(), dev::bob(), dev::charlie()]
+//!     .map(|k| k.public_key().to_account_id());
+//! let multisig = Multisig::new(signatories, 2)?;
+//!
+//! // The call that we'd like the multisig account to make:
+//! let call = subxt::dynamic::tx(
+//!     "System",
+//!     "remark",
+//!     vec![Value::from_bytes("Hello from a multisig")],
+//! );
+//!
+//! // Build and submit the next approval for Alice:
+//! let alice = dev::alice();
+//! let approval = multisig
+//!     .next_approval(&api, &alice.public_key().to_account_id(), &call)
+//!     .await?;
+//! api.tx().sign_and_submit_default(&approval, &alice).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    backend::BackendExt,
+    client::OnlineClientT,
+    dynamic::Value,
+    error::Error,
+    tx::{DynamicPayload, Payload},
+    utils::AccountId32,
+    Config,
+};
+use codec::{Compact, Encode};
+
+/// The prefix that `pallet_multisig` hashes along with the signatories and threshold
+/// to derive a multisig account.
+const MULTISIG_ACCOUNT_PREFIX: &[u8; 16] = b"modlpy/utilisuba";
+
+/// Compute the deterministic account ID of the multisig made up of the given signatories
+/// and threshold. The signatories can be given in any order, but should not contain duplicates.
+pub fn multisig_account_id(
+    signatories: impl IntoIterator<Item = AccountId32>,
+    threshold: u16,
+) -> AccountId32 {
+    let mut signatories: Vec<_> = signatories.into_iter().collect();
+    signatories.sort();
+    let entropy = (MULTISIG_ACCOUNT_PREFIX, signatories, threshold).encode();
+    AccountId32(sp_crypto_hashing::blake2_256(&entropy))
+}
+
+/// The hash of a call, which identifies a multisig operation. This is the blake2-256 hash
+/// of the SCALE encoded call data.
+pub fn call_hash<Call: Payload>(
+    call: &Call,
+    metadata: &crate::Metadata,
+) -> Result<[u8; 32], Error> {
+    let call_data = call.encode_call_data(metadata)?;
+    Ok(sp_crypto_hashing::blake2_256(&call_data))
+}
+
+/// A block height and extrinsic index, identifying when a multisig operation was first
+/// approved. Every approval after the first must provide this.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale_decode::DecodeAsType)]
+pub struct Timepoint {
+    /// The height of the block.
+    pub height: u32,
+    /// The index of the extrinsic in the block.
+    pub index: u32,
+}
+
+impl Timepoint {
+    fn to_value(self) -> Value {
+        Value::named_composite([
+            ("height", Value::u128(self.height as u128)),
+            ("index", Value::u128(self.index as u128)),
+        ])
+    }
+}
+
+/// The maximum weight that the wrapped call may consume when it is executed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Weight {
+    /// The computational time used to execute the call.
+    pub ref_time: u64,
+    /// The size of the proof needed to execute the call.
+    pub proof_size: u64,
+}
+
+impl Weight {
+    fn to_value(self) -> Value {
+        Value::named_composite([
+            ("ref_time", Value::u128(self.ref_time as u128)),
+            ("proof_size", Value::u128(self.proof_size as u128)),
+        ])
+    }
+}
+
+/// An operation that has been approved by at least one signatory, but not yet executed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingOperation {
+    /// The hash of the call that will be executed.
+    pub call_hash: [u8; 32],
+    /// When the operation was first approved.
+    pub when: Timepoint,
+    /// The amount held in reserve from the depositor until the operation is executed or cancelled.
+    pub deposit: u128,
+    /// The account that placed the deposit; this is the first signatory to approve.
+    pub depositor: AccountId32,
+    /// The signatories that have approved the operation so far.
+    pub approvals: Vec<AccountId32>,
+}
+
+// The shape of `pallet_multisig::Multisig` that we decode storage entries into.
+#[derive(scale_decode::DecodeAsType)]
+struct MultisigInfo {
+    when: Timepoint,
+    deposit: u128,
+    depositor: AccountId32,
+    approvals: Vec<AccountId32>,
+}
+
+/// A set of signatories and a threshold, which together describe a multisig account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Multisig {
+    signatories: Vec<AccountId32>,
+    threshold: u16,
+}
+
+impl Multisig {
+    /// Create a new [`Multisig`]. The signatories can be given in any order.
+    ///
+    /// Returns an error if there are fewer than 2 signatories, if the threshold is less than 2
+    /// or greater than the number of signatories, or if the signatories contain duplicates.
+    /// `pallet_multisig` rejects `as_multi` and friends for a threshold of 1; use
+    /// `Multisig::as_multi_threshold_1` directly in that case.
+    pub fn new(
+        signatories: impl IntoIterator<Item = AccountId32>,
+        threshold: u16,
+    ) -> Result<Self, Error> {
+        let mut signatories: Vec<_> = signatories.into_iter().collect();
+        signatories.sort();
+
+        if signatories.windows(2).any(|w| w[0] == w[1]) {
+            return Err(Error::Other(
+                "Multisig signatories contain duplicates".into(),
+            ));
+        }
+        if signatories.len() < 2 {
+            return Err(Error::Other(format!(
+                "A multisig needs at least 2 signatories, but got {}",
+                signatories.len()
+            )));
+        }
+        if threshold < 2 || threshold as usize > signatories.len() {
+            return Err(Error::Other(format!(
+                "Multisig threshold must be between 2 and {}, but got {threshold}",
+                signatories.len()
+            )));
+        }
+
+        Ok(Multisig {
+            signatories,
+            threshold,
+        })
+    }
+
+    /// The signatories of this multisig, sorted.
+    pub fn signatories(&self) -> &[AccountId32] {
+        &self.signatories
+    }
+
+    /// The number of approvals needed for an operation to be executed.
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    /// The account ID of this multisig.
+    pub fn account_id(&self) -> AccountId32 {
+        multisig_account_id(self.signatories.iter().cloned(), self.threshold)
+    }
+
+    /// The sorted signatories other than the given one, as `pallet_multisig` expects them.
+    ///
+    /// Returns an error if the given account is not a signatory of this multisig.
+    pub fn other_signatories(&self, signatory: &AccountId32) -> Result<Vec<AccountId32>, Error> {
+        if !self.signatories.contains(signatory) {
+            return Err(Error::Other(format!(
+                "{signatory} is not a signatory of multisig {}",
+                self.account_id()
+            )));
+        }
+        Ok(self
+            .signatories
+            .iter()
+            .filter(|s| *s != signatory)
+            .cloned()
+            .collect())
+    }
+
+    /// Build a `Multisig::as_multi` call for the given signatory. The `call` is executed if
+    /// this approval brings the number of approvals up to the threshold.
+    ///
+    /// `maybe_timepoint` must be `None` for the first approval, and the [`Timepoint`] of the
+    /// [`PendingOperation`] after that.
+    pub fn as_multi<Call: Payload>(
+        &self,
+        signatory: &AccountId32,
+        maybe_timepoint: Option<Timepoint>,
+        call: &Call,
+        max_weight: Weight,
+        metadata: &crate::Metadata,
+    ) -> Result<DynamicPayload, Error> {
+        let call_data = call.encode_call_data(metadata)?;
+        let call = scale_value::scale::decode_as_type(
+            &mut &*call_data,
+            metadata.outer_enums().call_enum_ty(),
+            metadata.types(),
+        )?;
+
+        Ok(crate::dynamic::tx(
+            "Multisig",
+            "as_multi",
+            vec![
+                Value::u128(self.threshold as u128),
+                accounts_to_value(self.other_signatories(signatory)?),
+                option_to_value(maybe_timepoint.map(Timepoint::to_value)),
+                call.remove_context(),
+                max_weight.to_value(),
+            ],
+        ))
+    }
+
+    /// Build a `Multisig::approve_as_multi` call for the given signatory, approving the
+    /// operation with the given call hash without providing the call itself.
+    ///
+    /// `maybe_timepoint` must be `None` for the first approval, and the [`Timepoint`] of the
+    /// [`PendingOperation`] after that.
+    pub fn approve_as_multi(
+        &self,
+        signatory: &AccountId32,
+        maybe_timepoint: Option<Timepoint>,
+        call_hash: [u8; 32],
+        max_weight: Weight,
+    ) -> Result<DynamicPayload, Error> {
+        Ok(crate::dynamic::tx(
+            "Multisig",
+            "approve_as_multi",
+            vec![
+                Value::u128(self.threshold as u128),
+                accounts_to_value(self.other_signatories(signatory)?),
+                option_to_value(maybe_timepoint.map(Timepoint::to_value)),
+                Value::from_bytes(call_hash),
+                max_weight.to_value(),
+            ],
+        ))
+    }
+
+    /// Build a `Multisig::cancel_as_multi` call, which cancels a pending operation. This
+    /// must be signed by the depositor of the operation.
+    pub fn cancel_as_multi(
+        &self,
+        signatory: &AccountId32,
+        timepoint: Timepoint,
+        call_hash: [u8; 32],
+    ) -> Result<DynamicPayload, Error> {
+        Ok(crate::dynamic::tx(
+            "Multisig",
+            "cancel_as_multi",
+            vec![
+                Value::u128(self.threshold as u128),
+                accounts_to_value(self.other_signatories(signatory)?),
+                timepoint.to_value(),
+                Value::from_bytes(call_hash),
+            ],
+        ))
+    }
+
+    /// Fetch the operations on this multisig that are waiting for more approvals, as of
+    /// the latest finalized block.
+    pub async fn pending_operations<T, Client>(
+        &self,
+        client: &Client,
+    ) -> Result<Vec<PendingOperation>, Error>
+    where
+        T: Config,
+        Client: OnlineClientT<T>,
+    {
+        let address = crate::dynamic::storage(
+            "Multisig",
+            "Multisigs",
+            vec![Value::from_bytes(self.account_id())],
+        );
+
+        let mut iter = client.storage().at_latest().await?.iter(address).await?;
+        let mut operations = Vec::new();
+        while let Some(kv) = iter.next().await {
+            let kv = kv?;
+
+            // The call hash is the last key, and is stored in full thanks to the
+            // Blake2_128Concat hasher, so we can take it from the end of the key bytes.
+            let call_hash: [u8; 32] = kv
+                .key_bytes
+                .len()
+                .checked_sub(32)
+                .and_then(|start| kv.key_bytes[start..].try_into().ok())
+                .ok_or_else(|| Error::Other("Multisig storage key is too short".into()))?;
+
+            let info: MultisigInfo = kv.value.as_type()?;
+            operations.push(PendingOperation {
+                call_hash,
+                when: info.when,
+                deposit: info.deposit,
+                depositor: info.depositor,
+                approvals: info.approvals,
+            });
+        }
+        Ok(operations)
+    }
+
+    /// Fetch the pending operation for the given call hash, if there is one.
+    pub async fn pending_operation<T, Client>(
+        &self,
+        client: &Client,
+        call_hash: [u8; 32],
+    ) -> Result<Option<PendingOperation>, Error>
+    where
+        T: Config,
+        Client: OnlineClientT<T>,
+    {
+        let operations = self.pending_operations(client).await?;
+        Ok(operations.into_iter().find(|op| op.call_hash == call_hash))
+    }
+
+    /// Build the next approval of the given call for a signatory.
+    ///
+    /// This looks up whether the call is already pending, to find the [`Timepoint`] to use
+    /// and how many approvals it has. If this approval will reach the threshold, an
+    /// `as_multi` call including the call and its estimated weight is built, so that the
+    /// call is executed. Otherwise, an `approve_as_multi` call with the call hash is built.
+    ///
+    /// Returns an error if the signatory has already approved the call.
+    pub async fn next_approval<T, Client, Call>(
+        &self,
+        client: &Client,
+        signatory: &AccountId32,
+        call: &Call,
+    ) -> Result<DynamicPayload, Error>
+    where
+        T: Config,
+        Client: OnlineClientT<T>,
+        Call: Payload,
+    {
+        let metadata = client.metadata();
+        let call_hash = call_hash(call, &metadata)?;
+        let pending = self.pending_operation(client, call_hash).await?;
+
+        let approvals = match &pending {
+            Some(op) if op.approvals.contains(signatory) => {
+                return Err(Error::Other(format!(
+                    "{signatory} has already approved this multisig operation"
+                )));
+            }
+            Some(op) => op.approvals.len(),
+            None => 0,
+        };
+        let maybe_timepoint = pending.map(|op| op.when);
+
+        if approvals + 1 >= self.threshold as usize {
+            let max_weight = estimate_weight(client, call).await?;
+            self.as_multi(signatory, maybe_timepoint, call, max_weight, &metadata)
+        } else {
+            self.approve_as_multi(signatory, maybe_timepoint, call_hash, Weight::default())
+        }
+    }
+}
+
+/// Estimate the weight of a call using the `TransactionPaymentCallApi_query_call_info`
+/// runtime API, at the latest finalized block. This is suitable to use as the `max_weight`
+/// of the final multisig approval.
+pub async fn estimate_weight<T, Client, Call>(client: &Client, call: &Call) -> Result<Weight, Error>
+where
+    T: Config,
+    Client: OnlineClientT<T>,
+    Call: Payload,
+{
+    let mut params = call.encode_call_data(&client.metadata())?;
+    let len = params.len() as u32;
+    len.encode_to(&mut params);
+    let latest_block_ref = client.backend().latest_finalized_block_ref().await?;
+
+    // destructuring RuntimeDispatchInfo, see type information <https://paritytech.github.io/substrate/master/pallet_transaction_payment_rpc_runtime_api/struct.RuntimeDispatchInfo.html>
+    // data layout: {weight_ref_time: Compact<u64>, weight_proof_size: Compact<u64>, class: u8, partial_fee: u128}
+    let (ref_time, proof_size, _, _) = client
+        .backend()
+        .call_decoding::<(Compact<u64>, Compact<u64>, u8, u128)>(
+            "TransactionPaymentCallApi_query_call_info",
+            Some(&params),
+            latest_block_ref.hash(),
+        )
+        .await?;
+
+    Ok(Weight {
+        ref_time: ref_time.0,
+        proof_size: proof_size.0,
+    })
+}
+
+fn accounts_to_value(accounts: Vec<AccountId32>) -> Value {
+    Value::unnamed_composite(accounts.into_iter().map(Value::from_bytes))
+}
+
+fn option_to_value(value: Option<Value>) -> Value {
+    match value {
+        Some(value) => Value::unnamed_variant("Some", [value]),
+        None => Value::unnamed_variant("None", []),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn account(n: u8) -> AccountId32 {
+        AccountId32([n; 32])
+    }
+
+    #[test]
+    fn multisig_account_id_matches_pallet_multisig() {
+        use sp_keyring::AccountKeyring::{Alice, Bob, Charlie};
+        let [alice, bob, charlie] = [Alice, Bob, Charlie].map(|k| AccountId32(k.public().0));
+
+        // The order of the signatories doesn't matter:
+        let id = multisig_account_id([alice.clone(), bob.clone(), charlie.clone()], 2);
+        assert_eq!(id, multisig_account_id([charlie, alice, bob], 2));
+
+        // `pallet_multisig::Pallet::multi_account_id` hashes the prefix, the sorted
+        // signatories as a SCALE encoded Vec and then the threshold:
+        let mut sorted = [Alice, Bob, Charlie].map(|k| k.public().0);
+        sorted.sort();
+        let mut entropy = MULTISIG_ACCOUNT_PREFIX.to_vec();
+        entropy.push(3 << 2);
+        entropy.extend(sorted.concat());
+        entropy.extend(2u16.to_le_bytes());
+        assert_eq!(id.0, sp_crypto_hashing::blake2_256(&entropy));
+    }
+
+    #[test]
+    fn multisig_validates_signatories_and_threshold() {
+        assert!(Multisig::new([account(1), account(2)], 0).is_err());
+        assert!(Multisig::new([account(1), account(2)], 1).is_err());
+        assert!(Multisig::new([account(1)], 1).is_err());
+        assert!(Multisig::new([], 0).is_err());
+        assert!(Multisig::new([account(1), account(2)], 3).is_err());
+        assert!(Multisig::new([account(1), account(1)], 2).is_err());
+        assert!(Multisig::new([account(1), account(2)], 2).is_ok());
+    }
+
+    #[test]
+    fn other_signatories_are_sorted_and_exclude_signer() {
+        let multisig = Multisig::new([account(3), account(1), account(2)], 2).unwrap();
+        assert_eq!(
+            multisig.other_signatories(&account(2)).unwrap(),
+            vec![account(1), account(3)]
+        );
+        assert!(multisig.other_signatories(&account(4)).is_err());
+    }
+}
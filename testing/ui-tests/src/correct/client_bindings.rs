@@ -0,0 +1,84 @@
+use codec::Encode;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use subxt::backend::snapshot::{Snapshot, SnapshotBackend};
+use subxt::config::substrate::{BlakeTwo256, Digest, SubstrateHeader, H256};
+use subxt::{OnlineClient, PolkadotConfig};
+
+#[subxt::subxt(
+    runtime_metadata_path = "../../../../artifacts/polkadot_metadata_small.scale",
+    client_bindings
+)]
+pub mod polkadot {}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let client = client_with_block_number(42).await;
+    let api = polkadot::client_api();
+
+    // Storage entries can be fetched and iterated over directly:
+    let number = api.storage(&client).system().number().fetch().await.unwrap();
+    assert_eq!(number, Some(42));
+    let count = api.storage(&client).system().event_count().fetch_or_default().await.unwrap();
+    assert_eq!(count, 0);
+    let mut accounts = api.storage(&client).system().account_iter().iter().await.unwrap();
+    assert!(accounts.next().await.is_none());
+
+    // And runtime APIs called directly:
+    let versions = api
+        .runtime_apis(&client)
+        .metadata()
+        .metadata_versions()
+        .await
+        .unwrap();
+    assert!(versions.contains(&15));
+
+    // Both can be pinned to a specific block:
+    let block_ref = client.blocks().at_latest().await.unwrap().reference();
+    let number = api
+        .storage(&client)
+        .at(block_ref.clone())
+        .system()
+        .number()
+        .fetch()
+        .await
+        .unwrap();
+    assert_eq!(number, Some(42));
+    let versions = api
+        .runtime_apis(&client)
+        .at(block_ref)
+        .metadata()
+        .metadata_versions()
+        .await
+        .unwrap();
+    assert!(versions.contains(&15));
+}
+
+// A client whose storage contains nothing besides the `System::Number` entry given.
+async fn client_with_block_number(number: u32) -> OnlineClient<PolkadotConfig> {
+    let metadata = include_bytes!("../../../../artifacts/polkadot_metadata_small.scale").to_vec();
+    let decoded_metadata: subxt::Metadata = codec::Decode::decode(&mut &*metadata).unwrap();
+    let number_address = polkadot::storage().system().number();
+    let number_key =
+        subxt::ext::subxt_core::storage::get_address_bytes(&number_address, &decoded_metadata)
+            .unwrap();
+
+    let header = SubstrateHeader::<u32, BlakeTwo256> {
+        parent_hash: H256::zero(),
+        number,
+        state_root: H256::zero(),
+        extrinsics_root: H256::zero(),
+        digest: Digest::default(),
+    };
+    let snapshot = Snapshot {
+        genesis_hash: H256::zero(),
+        block_hash: H256::repeat_byte(1),
+        header: header.encode(),
+        spec_version: 1,
+        transaction_version: 1,
+        metadata,
+        storage: BTreeMap::from([(number_key, number.encode())]),
+    };
+    let backend = SnapshotBackend::<PolkadotConfig>::new(snapshot);
+    OnlineClient::from_backend(Arc::new(backend)).await.unwrap()
+}
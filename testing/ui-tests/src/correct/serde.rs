@@ -0,0 +1,42 @@
+use codec::Encode;
+use subxt::utils::AccountId32;
+
+#[subxt::subxt(
+    runtime_metadata_path = "../../../../artifacts/polkadot_metadata_full.scale",
+    serde
+)]
+pub mod polkadot {}
+
+use polkadot::runtime_types::frame_system::pallet::Call as SystemCall;
+
+fn main() {
+    // Account IDs, large integers and bytes are human readable:
+    let transfer = polkadot::balances::events::Transfer {
+        from: AccountId32([1; 32]),
+        to: AccountId32([2; 32]),
+        amount: u128::MAX,
+    };
+    let json = serde_json::to_value(&transfer).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "from": AccountId32([1; 32]).to_string(),
+            "to": AccountId32([2; 32]).to_string(),
+            "amount": u128::MAX.to_string(),
+        })
+    );
+    let decoded: polkadot::balances::events::Transfer = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded.encode(), transfer.encode());
+
+    // And so are they when nested in the outer enums:
+    let call = polkadot::Call::System(SystemCall::remark {
+        remark: vec![0xde, 0xad],
+    });
+    let json = serde_json::to_value(&call).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({ "System": { "remark": { "remark": "0xdead" } } })
+    );
+    let decoded: polkadot::Call = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded.encode(), call.encode());
+}